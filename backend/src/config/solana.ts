@@ -141,6 +141,20 @@ export const pdaDerivation = {
     );
   },
 
+  platformStats(): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('platform_stats')],
+      programIds.payment!
+    );
+  },
+
+  treasury(): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('treasury')],
+      programIds.payment!
+    );
+  },
+
   creatorVault(creator: PublicKey): [PublicKey, number] {
     return PublicKey.findProgramAddressSync(
       [Buffer.from('vault'), creator.toBuffer()],
@@ -157,6 +171,13 @@ export const pdaDerivation = {
     );
   },
 
+  tipperStats(tipper: PublicKey, creator: PublicKey): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('tipper_stats'), tipper.toBuffer(), creator.toBuffer()],
+      programIds.payment!
+    );
+  },
+
   subscription(subscriber: PublicKey, creator: PublicKey): [PublicKey, number] {
     return PublicKey.findProgramAddressSync(
       [Buffer.from('subscription'), subscriber.toBuffer(), creator.toBuffer()],
@@ -165,6 +186,13 @@ export const pdaDerivation = {
   },
 
   // Token gate program PDAs
+  gateConfig(): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('gate_config')],
+      programIds.tokenGate!
+    );
+  },
+
   accessControl(post: PublicKey): [PublicKey, number] {
     return PublicKey.findProgramAddressSync(
       [Buffer.from('access'), post.toBuffer()],
@@ -179,6 +207,13 @@ export const pdaDerivation = {
     );
   },
 
+  accessPass(post: PublicKey, user: PublicKey): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('pass'), post.toBuffer(), user.toBuffer()],
+      programIds.tokenGate!
+    );
+  },
+
   // Airdrop program PDAs
   airdropCampaign(creator: PublicKey, campaignId: Buffer): [PublicKey, number] {
     return PublicKey.findProgramAddressSync(
@@ -235,6 +270,7 @@ export interface PlatformConfigData {
   feeBasisPoints: number;
   feeRecipient: PublicKey;
   bump: number;
+  trackStats: boolean;
 }
 
 export interface AccessControlData {
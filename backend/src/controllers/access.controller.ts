@@ -290,13 +290,20 @@ export const accessController = {
       const [accessControlPda] = pdaDerivation.accessControl(postPubkey);
       const [verificationPda] = pdaDerivation.accessVerification(userPubkey, postPubkey);
 
-      // Call the check_access instruction (view function)
+      // Call the check_access instruction (view function). Subscription gates
+      // need the viewer's subscription and creator default gates need the
+      // post's accounts, neither of which this check passes.
       const hasAccess = await programs.tokenGate.methods
         .checkAccess()
         .accounts({
           accessControl: accessControlPda,
           verification: verificationPda,
+          subscription: null,
+          paymentProgram: null,
           user: userPubkey,
+          gateDefault: null,
+          post: null,
+          postAccessControl: null,
         })
         .view();
 
@@ -259,11 +259,17 @@ export const airdropService = {
         )
       );
 
-      remainingAccounts.push({
-        pubkey: recipientAta,
-        isWritable: true,
-        isSigner: false,
-      });
+      // The program checks each ATA is owned by the wallet listed before it,
+      // and creates the receipt PDA that stops the wallet being paid twice
+      const [receiptPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from('receipt'), campaign.toBuffer(), owner.toBuffer()],
+        programIds.airdrop
+      );
+      remainingAccounts.push(
+        { pubkey: owner, isWritable: false, isSigner: false },
+        { pubkey: recipientAta, isWritable: true, isSigner: false },
+        { pubkey: receiptPda, isWritable: true, isSigner: false },
+      );
     }
 
     // Add the distribute batch instruction
@@ -19,11 +19,17 @@ import {
 } from '../config/solana.js';
 import { logger } from '../utils/logger.js';
 import { toValidatedPublicKey, toOptionalPublicKey } from '../utils/validation.js';
+import type { PlatformConfigData } from '../config/solana.js';
 import type { TransactionResponse } from '../types/index.js';
 
 const PLATFORM_FEE_BPS = 200; // 2% (used as fallback)
 const METAPLEX_PROGRAM_ID = new PublicKey("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
 
+// The program requires the stats account once the platform tracks stats
+function platformStatsAccount(config: PlatformConfigData | null): PublicKey | null {
+  return config?.trackStats ? pdaDerivation.platformStats()[0] : null;
+}
+
 function serializeTransaction(tx: Transaction): string {
   return tx.serialize({ requireAllSignatures: false }).toString('base64');
 }
@@ -304,14 +310,19 @@ export const solanaService = {
     const { tx, payerPubkey: creatorPubkey, blockhash, lastValidBlockHeight } = await createTxShell(wallet);
 
     if (programs.payment) {
+      const [configPda] = pdaDerivation.platformConfig();
       const [vaultPda] = pdaDerivation.creatorVault(creatorPubkey);
+      const platformConfig = await fetchPlatformConfig();
 
+      // The backend only manages the creator's default, un-namespaced vault
       const ix = await programs.payment.methods
-        .initializeVault()
+        .initializeVault(null)
         .accounts({
+          config: configPda,
           vault: vaultPda,
           creator: creatorPubkey,
           systemProgram: SystemProgram.programId,
+          platformStats: platformStatsAccount(platformConfig),
         })
         .instruction();
 
@@ -334,28 +345,34 @@ export const solanaService = {
     const lamports = Math.floor(amount * LAMPORTS_PER_SOL);
 
     if (programs.payment && programIds.payment) {
-      // Get platform config for fee recipient
+      // Fall back to a direct transfer until the platform is initialized
       const platformConfig = await fetchPlatformConfig();
 
       if (platformConfig) {
         const [configPda] = pdaDerivation.platformConfig();
+        const [treasuryPda] = pdaDerivation.treasury();
         const [vaultPda] = pdaDerivation.creatorVault(creatorPubkey);
 
         // Get next tip index for this tipper (using timestamp as index for uniqueness)
         const tipIndex = BigInt(Date.now());
         const [tipRecordPda] = pdaDerivation.tipRecord(tipperPubkey, tipIndex);
+        // Created on the first tip to this creator, at the tipper's expense
+        const [tipperStatsPda] = pdaDerivation.tipperStats(tipperPubkey, creatorPubkey);
 
         const postPubkey = postId ? new PublicKey(postId) : null;
 
+        // No post index: the tip isn't checked against the creator's posts
         const ix = await programs.payment.methods
-          .tipCreator(new BN(lamports), postPubkey, new BN(tipIndex.toString()))
+          .tipCreator(new BN(lamports), postPubkey, new BN(tipIndex.toString()), null)
           .accounts({
             config: configPda,
             creatorVault: vaultPda,
             tipRecord: tipRecordPda,
             tipper: tipperPubkey,
-            feeRecipient: platformConfig.feeRecipient,
+            tipperStats: tipperStatsPda,
+            treasury: treasuryPda,
             systemProgram: SystemProgram.programId,
+            platformStats: platformStatsAccount(platformConfig),
           })
           .instruction();
 
@@ -407,18 +424,22 @@ export const solanaService = {
 
       if (platformConfig) {
         const [configPda] = pdaDerivation.platformConfig();
+        const [treasuryPda] = pdaDerivation.treasury();
         const [vaultPda] = pdaDerivation.creatorVault(creatorPubkey);
         const [subscriptionPda] = pdaDerivation.subscription(subscriberPubkey, creatorPubkey);
 
         const ix = await programs.payment.methods
-          .subscribe(new BN(lamports))
+          .subscribe(new BN(lamports), null, null, null)
           .accounts({
             config: configPda,
             creatorVault: vaultPda,
             subscription: subscriptionPda,
             subscriber: subscriberPubkey,
-            feeRecipient: platformConfig.feeRecipient,
+            treasury: treasuryPda,
             systemProgram: SystemProgram.programId,
+            platformStats: platformStatsAccount(platformConfig),
+            subscriptionTiers: null,
+            subscriberIndex: null,
           })
           .instruction();
 
@@ -513,6 +534,7 @@ export const solanaService = {
     }
 
     const [accessControlPda] = pdaDerivation.accessControl(postPubkey);
+    const [gateConfigPda] = pdaDerivation.gateConfig();
 
     const tokenPubkey = requiredToken ? new PublicKey(requiredToken) : null;
     const nftCollectionPubkey = requiredNftCollection
@@ -529,6 +551,7 @@ export const solanaService = {
       )
       .accounts({
         accessControl: accessControlPda,
+        gateConfig: gateConfigPda,
         creator: creatorPubkey,
         systemProgram: SystemProgram.programId,
       })
@@ -555,12 +578,14 @@ export const solanaService = {
 
     const [accessControlPda] = pdaDerivation.accessControl(postPubkey);
     const [verificationPda] = pdaDerivation.accessVerification(payerPubkey, postPubkey);
+    const [accessPassPda] = pdaDerivation.accessPass(postPubkey, payerPubkey);
 
     const ix = await programs.tokenGate.methods
       .verifyTokenAccess()
       .accounts({
         accessControl: accessControlPda,
         verification: verificationPda,
+        accessPass: accessPassPda,
         userTokenAccount: tokenAccountPubkey,
         user: payerPubkey,
         systemProgram: SystemProgram.programId,
@@ -589,6 +614,7 @@ export const solanaService = {
 
     const [accessControlPda] = pdaDerivation.accessControl(postPubkey);
     const [verificationPda] = pdaDerivation.accessVerification(payerPubkey, postPubkey);
+    const [accessPassPda] = pdaDerivation.accessPass(postPubkey, payerPubkey);
 
     const [nftMetadataPubkey] = PublicKey.findProgramAddressSync(
       [Buffer.from("metadata"), METAPLEX_PROGRAM_ID.toBuffer(), nftMintPubkey.toBuffer()],
@@ -600,6 +626,7 @@ export const solanaService = {
       .accounts({
         accessControl: accessControlPda,
         verification: verificationPda,
+        accessPass: accessPassPda,
         nftTokenAccount: nftTokenAccountPubkey,
         nftMint: nftMintPubkey,
         nftMetadata: nftMetadataPubkey,
@@ -0,0 +1,78 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{
+    allocate, assign, create_account, transfer, Allocate, Assign, CreateAccount, Transfer,
+};
+
+/// Lamports `payer` must add for `account` to hold `space` bytes rent-free.
+/// The PDA may already hold lamports if someone transferred to it, and those
+/// count towards its rent.
+pub fn rent_shortfall(account: &AccountInfo, space: usize) -> Result<u64> {
+    Ok(Rent::get()?
+        .minimum_balance(space)
+        .saturating_sub(account.lamports()))
+}
+
+/// Creates the PDA signed for by `signer_seeds` with `space` bytes, owned by
+/// `owner` and paid for by `payer`. `create_account` refuses an address that
+/// already holds lamports, so a pre-funded PDA is instead topped up to rent,
+/// then allocated and assigned.
+pub fn create_pda_account<'info>(
+    payer: &AccountInfo<'info>,
+    pda: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    space: usize,
+    owner: &Pubkey,
+    signer_seeds: &[&[u8]],
+) -> Result<()> {
+    let rent_needed = rent_shortfall(pda, space)?;
+    let signer_seeds = &[signer_seeds];
+
+    if pda.lamports() == 0 {
+        return create_account(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                CreateAccount {
+                    from: payer.clone(),
+                    to: pda.clone(),
+                },
+                signer_seeds,
+            ),
+            rent_needed,
+            space as u64,
+            owner,
+        );
+    }
+
+    if rent_needed > 0 {
+        transfer(
+            CpiContext::new(
+                system_program.clone(),
+                Transfer {
+                    from: payer.clone(),
+                    to: pda.clone(),
+                },
+            ),
+            rent_needed,
+        )?;
+    }
+    allocate(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            Allocate {
+                account_to_allocate: pda.clone(),
+            },
+            signer_seeds,
+        ),
+        space as u64,
+    )?;
+    assign(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            Assign {
+                account_to_assign: pda.clone(),
+            },
+            signer_seeds,
+        ),
+        owner,
+    )
+}
@@ -16,4 +16,56 @@ pub enum AirdropError {
     Overflow,
     #[msg("Recipient ATA has incorrect mint")]
     InvalidRecipientMint,
+    #[msg("Refund destination token account is frozen")]
+    FrozenDestination,
+    #[msg("Campaign deadline must be in the future")]
+    InvalidDeadline,
+    #[msg("Escrow token account is not owned by the campaign")]
+    InvalidEscrowAuthority,
+    #[msg("Batch does not start at the campaign's distribution cursor")]
+    DistributionCursorMismatch,
+    #[msg("Allocation has already been claimed")]
+    AlreadyClaimed,
+    #[msg("Merkle proof does not match the campaign root")]
+    InvalidProof,
+    #[msg("Campaign has no Merkle root")]
+    MerkleRootNotSet,
+    #[msg("Merkle campaigns pay out through claims")]
+    MerkleCampaign,
+    #[msg("Campaign cannot move to that status")]
+    InvalidStatusTransition,
+    #[msg("Campaign must have at least one recipient")]
+    InvalidRecipientCount,
+    #[msg("Payouts would exceed the campaign's declared recipient count")]
+    RecipientCountExceeded,
+    #[msg("Crank token account is required when the campaign pays a crank fee")]
+    CrankAccountRequired,
+    #[msg("Rescue requires a campaign deadline")]
+    RescueRequiresDeadline,
+    #[msg("Signer is not the campaign's rescue admin")]
+    UnauthorizedAdmin,
+    #[msg("Token account is not the campaign's rescue destination")]
+    InvalidRescueDestination,
+    #[msg("Rescue window has not opened yet")]
+    RescueNotAvailable,
+    #[msg("Recipient ATA is not owned by the listed recipient")]
+    RecipientOwnerMismatch,
+    #[msg("Recipient was already paid by this campaign")]
+    DuplicateRecipient,
+    #[msg("Receipt account is not the recipient's receipt PDA")]
+    InvalidRecipientReceipt,
+    #[msg("Campaign id is already used by this creator; choose a new id")]
+    CampaignIdAlreadyUsed,
+    #[msg("Per-slot distribution limit reached; wait for the next slot")]
+    DistributionRateLimited,
+    #[msg("Distribution rate limit must be greater than zero")]
+    InvalidRateLimit,
+    #[msg("Refund batch accounts must be (campaign, escrow ATA, destination ATA) triples")]
+    InvalidRefundAccounts,
+    #[msg("Account is not a campaign in an older layout")]
+    InvalidCampaignLayout,
+    #[msg("Creator cannot cover the rent for the campaign and its escrow")]
+    InsufficientRentForCampaign,
+    #[msg("Minimum interval since the campaign's last batch has not passed")]
+    BatchTooSoon,
 }
@@ -1,5 +1,7 @@
 use anchor_lang::prelude::*;
 
+use crate::state::CampaignStatus;
+
 #[event]
 pub struct CampaignCreated {
     pub creator: Pubkey,
@@ -7,6 +9,7 @@ pub struct CampaignCreated {
     pub token_mint: Pubkey,
     pub amount_per_recipient: u64,
     pub crank_authority: Pubkey,
+    pub decimals: u8,
 }
 
 #[event]
@@ -14,6 +17,9 @@ pub struct CampaignFunded {
     pub creator: Pubkey,
     pub campaign_id: [u8; 16],
     pub amount: u64,
+    // Escrowed total after this deposit, and which deposit it was (from 1)
+    pub total_amount: u64,
+    pub funding_events_count: u32,
 }
 
 #[event]
@@ -21,6 +27,40 @@ pub struct BatchDistributed {
     pub campaign_id: [u8; 16],
     pub recipient_count: u32,
     pub total_distributed: u64,
+    pub distribution_cursor: u32,
+    pub crank_fee: u64,
+    // Progress toward completion, so indexers can show a percentage
+    pub distributed_count: u32,
+    pub total_recipients: u32,
+}
+
+#[event]
+pub struct CampaignStatusChanged {
+    pub campaign_id: [u8; 16],
+    pub old_status: CampaignStatus,
+    pub new_status: CampaignStatus,
+}
+
+#[event]
+pub struct CampaignCompleted {
+    pub campaign_id: [u8; 16],
+    pub distributed_count: u32,
+    pub distributed_amount: u64,
+}
+
+#[event]
+pub struct MerkleRootSet {
+    pub campaign_id: [u8; 16],
+    pub merkle_root: [u8; 32],
+    pub eligible_count: u32,
+}
+
+#[event]
+pub struct TokensClaimed {
+    pub campaign_id: [u8; 16],
+    pub index: u32,
+    pub claimant: Pubkey,
+    pub amount: u64,
 }
 
 #[event]
@@ -28,4 +68,21 @@ pub struct CampaignRefunded {
     pub creator: Pubkey,
     pub campaign_id: [u8; 16],
     pub refund_amount: u64,
+    pub destination: Pubkey,
+}
+
+#[event]
+pub struct CampaignRescued {
+    pub campaign_id: [u8; 16],
+    pub admin_authority: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+}
+
+
+#[event]
+pub struct CampaignMigrated {
+    pub campaign_id: [u8; 16],
+    pub old_size: u32,
+    pub new_size: u32,
 }
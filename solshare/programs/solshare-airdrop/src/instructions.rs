@@ -2,8 +2,32 @@ pub mod create_campaign;
 pub mod fund_campaign;
 pub mod distribute_batch;
 pub mod refund;
+pub mod refund_many;
+pub mod get_campaign_progress;
+pub mod remaining_eligible;
+pub mod set_merkle_root;
+pub mod claim;
+pub mod set_crank_fee;
+pub mod set_distribution_rate_limit;
+pub mod set_rescue;
+pub mod rescue_refund;
+pub mod migrate_campaign;
+pub mod get_campaign_funding;
+pub mod set_batch_interval;
 
 pub use create_campaign::*;
 pub use fund_campaign::*;
 pub use distribute_batch::*;
 pub use refund::*;
+pub use refund_many::*;
+pub use get_campaign_progress::*;
+pub use remaining_eligible::*;
+pub use set_merkle_root::*;
+pub use claim::*;
+pub use set_crank_fee::*;
+pub use set_distribution_rate_limit::*;
+pub use set_rescue::*;
+pub use rescue_refund::*;
+pub use migrate_campaign::*;
+pub use get_campaign_funding::*;
+pub use set_batch_interval::*;
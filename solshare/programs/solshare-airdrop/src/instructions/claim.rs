@@ -0,0 +1,116 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+use crate::state::{CampaignState, CampaignStatus, ClaimStatus};
+use crate::error::AirdropError;
+use crate::events::{CampaignCompleted, TokensClaimed};
+use crate::merkle;
+
+#[derive(Accounts)]
+#[instruction(index: u32)]
+pub struct Claim<'info> {
+    #[account(mut)]
+    pub claimant: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = campaign.status == CampaignStatus::Funded || campaign.status == CampaignStatus::Processing @ AirdropError::InvalidStatus,
+    )]
+    pub campaign: Account<'info, CampaignState>,
+
+    #[account(
+        init_if_needed,
+        payer = claimant,
+        space = 8 + ClaimStatus::INIT_SPACE,
+        seeds = [b"claim", campaign.key().as_ref(), &index.to_le_bytes()],
+        bump
+    )]
+    pub claim_status: Account<'info, ClaimStatus>,
+
+    #[account(
+        mut,
+        constraint = escrow_ata.key() == campaign.escrow_ata,
+        constraint = escrow_ata.owner == campaign.key() @ AirdropError::InvalidEscrowAuthority,
+    )]
+    pub escrow_ata: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = claimant_ata.mint == campaign.token_mint @ AirdropError::InvalidRecipientMint,
+    )]
+    pub claimant_ata: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<Claim>, index: u32, amount: u64, proof: Vec<[u8; 32]>) -> Result<()> {
+    let campaign = &ctx.accounts.campaign;
+    let claimant = ctx.accounts.claimant.key();
+
+    require!(!ctx.accounts.claim_status.claimed, AirdropError::AlreadyClaimed);
+
+    let root = campaign.merkle_root.ok_or(AirdropError::MerkleRootNotSet)?;
+    require!(
+        merkle::verify(&proof, &root, merkle::leaf(index, &claimant, amount)),
+        AirdropError::InvalidProof
+    );
+
+    require!(campaign.remaining_budget()? >= amount, AirdropError::InsufficientFunds);
+
+    // PDA signer seeds
+    let creator = campaign.creator;
+    let campaign_id = campaign.campaign_id;
+    let bump = campaign.bump;
+    let seeds = &[
+        b"campaign".as_ref(),
+        creator.as_ref(),
+        campaign_id.as_ref(),
+        &[bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.escrow_ata.to_account_info(),
+        to: ctx.accounts.claimant_ata.to_account_info(),
+        authority: ctx.accounts.campaign.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+        signer_seeds,
+    );
+    token::transfer(cpi_ctx, amount)?;
+
+    let claim_status = &mut ctx.accounts.claim_status;
+    claim_status.campaign = ctx.accounts.campaign.key();
+    claim_status.index = index;
+    claim_status.claimant = claimant;
+    claim_status.amount = amount;
+    claim_status.claimed = true;
+    claim_status.bump = ctx.bumps.claim_status;
+
+    let campaign = &mut ctx.accounts.campaign;
+    let completed = campaign.record_payouts(1, amount)?;
+    campaign.claimed_count = campaign
+        .claimed_count
+        .checked_add(1)
+        .ok_or(AirdropError::Overflow)?;
+
+    emit!(TokensClaimed {
+        campaign_id: campaign.campaign_id,
+        index,
+        claimant,
+        amount,
+    });
+
+    if completed {
+        emit!(CampaignCompleted {
+            campaign_id: campaign.campaign_id,
+            distributed_count: campaign.distributed_count,
+            distributed_amount: campaign.distributed_amount,
+        });
+    }
+
+    Ok(())
+}
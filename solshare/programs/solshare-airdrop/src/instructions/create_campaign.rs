@@ -1,34 +1,38 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{Mint, TokenAccount, Token};
-use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::associated_token::{
+    create_idempotent, get_associated_token_address, AssociatedToken, Create,
+};
 
+use solshare_pda::{create_pda_account, rent_shortfall};
 use crate::state::{CampaignState, CampaignStatus};
+use crate::error::AirdropError;
 use crate::events::CampaignCreated;
 
 #[derive(Accounts)]
-#[instruction(campaign_id: [u8; 16], amount_per_recipient: u64, total_recipients: u32, crank_authority: Pubkey)]
+#[instruction(campaign_id: [u8; 16], amount_per_recipient: u64, total_recipients: u32, crank_authority: Pubkey, deadline: Option<i64>)]
 pub struct CreateCampaign<'info> {
     #[account(mut)]
     pub creator: Signer<'info>,
 
+    /// CHECK: Created in the handler once the creator is known to afford the
+    /// rent for it and the escrow, so a reused campaign_id fails with
+    /// CampaignIdAlreadyUsed instead of an opaque allocation error
     #[account(
-        init,
-        payer = creator,
-        space = CampaignState::SIZE,
+        mut,
         seeds = [b"campaign", creator.key().as_ref(), &campaign_id],
         bump,
     )]
-    pub campaign: Account<'info, CampaignState>,
+    pub campaign: UncheckedAccount<'info>,
 
     pub token_mint: Account<'info, Mint>,
 
+    /// CHECK: The campaign's associated token account, created in the handler
     #[account(
-        init,
-        payer = creator,
-        associated_token::mint = token_mint,
-        associated_token::authority = campaign,
+        mut,
+        address = get_associated_token_address(&campaign.key(), &token_mint.key()),
     )]
-    pub escrow_ata: Account<'info, TokenAccount>,
+    pub escrow_ata: UncheckedAccount<'info>,
 
     pub system_program: Program<'info, System>,
     pub token_program: Program<'info, Token>,
@@ -41,20 +45,60 @@ pub fn handler(
     amount_per_recipient: u64,
     total_recipients: u32,
     crank_authority: Pubkey,
+    deadline: Option<i64>,
 ) -> Result<()> {
-    let campaign = &mut ctx.accounts.campaign;
-    campaign.creator = ctx.accounts.creator.key();
-    campaign.campaign_id = campaign_id;
-    campaign.token_mint = ctx.accounts.token_mint.key();
-    campaign.escrow_ata = ctx.accounts.escrow_ata.key();
-    campaign.amount_per_recipient = amount_per_recipient;
-    campaign.total_amount = 0;
-    campaign.distributed_amount = 0;
-    campaign.total_recipients = total_recipients;
-    campaign.distributed_count = 0;
-    campaign.status = CampaignStatus::Draft;
-    campaign.crank_authority = crank_authority;
-    campaign.bump = ctx.bumps.campaign;
+    // Any data at the PDA means the id is taken
+    require!(ctx.accounts.campaign.data_is_empty(), AirdropError::CampaignIdAlreadyUsed);
+    require!(total_recipients > 0, AirdropError::InvalidRecipientCount);
+    if let Some(deadline) = deadline {
+        require!(deadline > Clock::get()?.unix_timestamp, AirdropError::InvalidDeadline);
+    }
+
+    check_creator_rent(&ctx)?;
+    create_campaign_account(&ctx, &campaign_id)?;
+    create_idempotent(CpiContext::new(
+        ctx.accounts.associated_token_program.to_account_info(),
+        Create {
+            payer: ctx.accounts.creator.to_account_info(),
+            associated_token: ctx.accounts.escrow_ata.to_account_info(),
+            authority: ctx.accounts.campaign.to_account_info(),
+            mint: ctx.accounts.token_mint.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            token_program: ctx.accounts.token_program.to_account_info(),
+        },
+    ))?;
+
+    let campaign = CampaignState {
+        creator: ctx.accounts.creator.key(),
+        campaign_id,
+        token_mint: ctx.accounts.token_mint.key(),
+        escrow_ata: ctx.accounts.escrow_ata.key(),
+        amount_per_recipient,
+        total_amount: 0,
+        distributed_amount: 0,
+        total_recipients,
+        distributed_count: 0,
+        status: CampaignStatus::Draft,
+        crank_authority,
+        bump: ctx.bumps.campaign,
+        deadline,
+        distribution_cursor: 0,
+        merkle_root: None,
+        crank_fee_per_recipient: 0,
+        crank_fees_paid: 0,
+        eligible_count: 0,
+        claimed_count: 0,
+        admin_authority: None,
+        rescue_destination: None,
+        max_distributed_per_slot: None,
+        last_distribution_slot: 0,
+        distributed_this_slot: 0,
+        decimals: ctx.accounts.token_mint.decimals,
+        funding_events_count: 0,
+        min_batch_interval_seconds: 0,
+        last_batch_at: 0,
+    };
+    campaign.try_serialize(&mut &mut ctx.accounts.campaign.data.borrow_mut()[..])?;
 
     emit!(CampaignCreated {
         creator: campaign.creator,
@@ -62,7 +106,48 @@ pub fn handler(
         token_mint: campaign.token_mint,
         amount_per_recipient,
         crank_authority,
+        decimals: campaign.decimals,
     });
 
     Ok(())
 }
+
+/// Checks up front that the creator can pay the rent for both the campaign and
+/// its escrow, so an under-funded creator gets a clear error, with the amounts
+/// logged, instead of a system program failure partway through
+fn check_creator_rent(ctx: &Context<CreateCampaign>) -> Result<()> {
+    let campaign_rent = rent_shortfall(&ctx.accounts.campaign, 8 + CampaignState::INIT_SPACE)?;
+    let escrow_rent = if ctx.accounts.escrow_ata.data_is_empty() {
+        rent_shortfall(&ctx.accounts.escrow_ata, TokenAccount::LEN)?
+    } else {
+        0
+    };
+    let required = campaign_rent
+        .checked_add(escrow_rent)
+        .ok_or(AirdropError::Overflow)?;
+
+    require_gte!(
+        ctx.accounts.creator.lamports(),
+        required,
+        AirdropError::InsufficientRentForCampaign
+    );
+    Ok(())
+}
+
+/// Creates the campaign PDA, paid for by the creator
+fn create_campaign_account(ctx: &Context<CreateCampaign>, campaign_id: &[u8; 16]) -> Result<()> {
+    let creator = &ctx.accounts.creator;
+    create_pda_account(
+        &creator.to_account_info(),
+        &ctx.accounts.campaign.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        8 + CampaignState::INIT_SPACE,
+        &crate::ID,
+        &[
+            b"campaign",
+            creator.key.as_ref(),
+            campaign_id,
+            &[ctx.bumps.campaign],
+        ],
+    )
+}
@@ -2,9 +2,10 @@ use anchor_lang::prelude::*;
 use anchor_lang::AccountDeserialize;
 use anchor_spl::token::{self, Token, TokenAccount, Transfer};
 
-use crate::state::{CampaignState, CampaignStatus};
+use solshare_pda::create_pda_account;
+use crate::state::{CampaignState, CampaignStatus, RecipientReceipt};
 use crate::error::AirdropError;
-use crate::events::BatchDistributed;
+use crate::events::{BatchDistributed, CampaignCompleted};
 
 #[derive(Accounts)]
 pub struct DistributeBatch<'info> {
@@ -15,37 +16,71 @@ pub struct DistributeBatch<'info> {
         mut,
         constraint = campaign.crank_authority == crank_authority.key() @ AirdropError::UnauthorizedCrank,
         constraint = campaign.status == CampaignStatus::Funded || campaign.status == CampaignStatus::Processing @ AirdropError::InvalidStatus,
+        constraint = campaign.merkle_root.is_none() @ AirdropError::MerkleCampaign,
     )]
     pub campaign: Account<'info, CampaignState>,
 
     #[account(
         mut,
         constraint = escrow_ata.key() == campaign.escrow_ata,
+        constraint = escrow_ata.owner == campaign.key() @ AirdropError::InvalidEscrowAuthority,
     )]
     pub escrow_ata: Account<'info, TokenAccount>,
 
+    // Receives the crank fee; required when the campaign sets one
+    #[account(
+        mut,
+        constraint = crank_ata.mint == campaign.token_mint @ AirdropError::InvalidRecipientMint,
+        constraint = crank_ata.owner == crank_authority.key() @ AirdropError::UnauthorizedCrank,
+    )]
+    pub crank_ata: Option<Account<'info, TokenAccount>>,
+
     pub token_program: Program<'info, Token>,
-    // Remaining accounts: pairs of (recipient_ata: TokenAccount) for each recipient
+    pub system_program: Program<'info, System>,
+    // Remaining accounts: (recipient: wallet, recipient_ata: TokenAccount,
+    // receipt: RecipientReceipt PDA, created here) for each recipient
 }
 
 pub fn handler<'info>(
     ctx: Context<'_, '_, 'info, 'info, DistributeBatch<'info>>,
+    start_index: u32,
     recipient_count: u32,
 ) -> Result<()> {
     let campaign = &ctx.accounts.campaign;
     let amount_per = campaign.amount_per_recipient;
 
+    // A batch must pick up exactly where the last one left off, so a crank that
+    // retries an already-landed batch fails instead of paying recipients twice
+    require!(
+        start_index == campaign.distribution_cursor,
+        AirdropError::DistributionCursorMismatch
+    );
+
+    // The crank fee comes out of the same escrow as the payouts
+    let crank_fee = (campaign.crank_fee_per_recipient as u128)
+        .checked_mul(recipient_count as u128)
+        .ok_or(AirdropError::Overflow)?;
     let total_needed = (amount_per as u128)
         .checked_mul(recipient_count as u128)
+        .and_then(|payouts| payouts.checked_add(crank_fee))
         .ok_or(AirdropError::Overflow)?;
 
-    let remaining = campaign
-        .total_amount
-        .checked_sub(campaign.distributed_amount)
-        .ok_or(AirdropError::InsufficientFunds)?;
-
-    require!(remaining as u128 >= total_needed, AirdropError::InsufficientFunds);
-    require!(recipient_count as usize <= ctx.remaining_accounts.len(), AirdropError::BatchTooLarge);
+    require!(
+        campaign.remaining_budget()? as u128 >= total_needed,
+        AirdropError::InsufficientFunds
+    );
+    require!(
+        (recipient_count as usize).saturating_mul(3) <= ctx.remaining_accounts.len(),
+        AirdropError::BatchTooLarge
+    );
+
+    let clock = Clock::get()?;
+    ctx.accounts.campaign.record_batch_time(clock.unix_timestamp)?;
+    // Bounded by the u64 remaining budget checked above
+    ctx.accounts
+        .campaign
+        .record_slot_distribution(clock.slot, total_needed as u64)?;
+    let campaign = &ctx.accounts.campaign;
 
     // PDA signer seeds
     let creator = campaign.creator;
@@ -62,7 +97,9 @@ pub fn handler<'info>(
     let mut distributed_this_batch: u64 = 0;
 
     for i in 0..recipient_count as usize {
-        let recipient_ata_info = &ctx.remaining_accounts[i];
+        let recipient_info = &ctx.remaining_accounts[3 * i];
+        let recipient_ata_info = &ctx.remaining_accounts[3 * i + 1];
+        let receipt_info = &ctx.remaining_accounts[3 * i + 2];
 
         // Validate recipient ATA has correct mint
         let recipient_ata_data = TokenAccount::try_deserialize(
@@ -72,6 +109,25 @@ pub fn handler<'info>(
             recipient_ata_data.mint == campaign.token_mint,
             AirdropError::InvalidRecipientMint
         );
+        // The ATA must belong to the wallet listed alongside it, so payouts and
+        // receipts name the same wallet. Which wallets are listed is still up
+        // to the crank; nothing on-chain records the recipient list.
+        require!(
+            recipient_ata_data.owner == recipient_info.key(),
+            AirdropError::RecipientOwnerMismatch
+        );
+
+        create_recipient_receipt(
+            receipt_info,
+            &ctx.accounts.crank_authority.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            RecipientReceipt {
+                campaign: ctx.accounts.campaign.key(),
+                recipient: recipient_info.key(),
+                amount: amount_per,
+                bump: 0,
+            },
+        )?;
 
         let cpi_accounts = Transfer {
             from: ctx.accounts.escrow_ata.to_account_info(),
@@ -90,27 +146,91 @@ pub fn handler<'info>(
             .ok_or(AirdropError::Overflow)?;
     }
 
+    // Bounded by total_needed, which fit in the u64 remaining budget
+    let crank_fee = crank_fee as u64;
+    if crank_fee > 0 {
+        let crank_ata = ctx
+            .accounts
+            .crank_ata
+            .as_ref()
+            .ok_or(AirdropError::CrankAccountRequired)?;
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.escrow_ata.to_account_info(),
+            to: crank_ata.to_account_info(),
+            authority: ctx.accounts.campaign.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+        token::transfer(cpi_ctx, crank_fee)?;
+    }
+
     let campaign = &mut ctx.accounts.campaign;
-    campaign.distributed_amount = campaign
-        .distributed_amount
-        .checked_add(distributed_this_batch)
+    let completed = campaign.record_payouts(recipient_count, distributed_this_batch)?;
+    campaign.crank_fees_paid = campaign
+        .crank_fees_paid
+        .checked_add(crank_fee)
         .ok_or(AirdropError::Overflow)?;
-    campaign.distributed_count = campaign
-        .distributed_count
+    campaign.distribution_cursor = campaign
+        .distribution_cursor
         .checked_add(recipient_count)
         .ok_or(AirdropError::Overflow)?;
-    campaign.status = CampaignStatus::Processing;
-
-    // Check if all recipients have been distributed to
-    if campaign.distributed_count >= campaign.total_recipients && campaign.total_recipients > 0 {
-        campaign.status = CampaignStatus::Completed;
-    }
 
     emit!(BatchDistributed {
         campaign_id: campaign.campaign_id,
         recipient_count,
         total_distributed: campaign.distributed_amount,
+        distribution_cursor: campaign.distribution_cursor,
+        crank_fee,
+        distributed_count: campaign.distributed_count,
+        total_recipients: campaign.total_recipients,
     });
 
+    if completed {
+        emit!(CampaignCompleted {
+            campaign_id: campaign.campaign_id,
+            distributed_count: campaign.distributed_count,
+            distributed_amount: campaign.distributed_amount,
+        });
+    }
+
     Ok(())
 }
+
+/// Creates the receipt PDA for `receipt.recipient`, which only succeeds the
+/// first time the campaign pays them. The crank pays the rent.
+fn create_recipient_receipt<'info>(
+    receipt_info: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    mut receipt: RecipientReceipt,
+) -> Result<()> {
+    let (expected, bump) = Pubkey::find_program_address(
+        &[b"receipt", receipt.campaign.as_ref(), receipt.recipient.as_ref()],
+        &crate::ID,
+    );
+    require_keys_eq!(receipt_info.key(), expected, AirdropError::InvalidRecipientReceipt);
+    require!(
+        receipt_info.owner != &crate::ID,
+        AirdropError::DuplicateRecipient
+    );
+
+    create_pda_account(
+        payer,
+        receipt_info,
+        system_program,
+        8 + RecipientReceipt::INIT_SPACE,
+        &crate::ID,
+        &[
+            b"receipt",
+            receipt.campaign.as_ref(),
+            receipt.recipient.as_ref(),
+            &[bump],
+        ],
+    )?;
+
+    receipt.bump = bump;
+    receipt.try_serialize(&mut &mut receipt_info.data.borrow_mut()[..])
+}
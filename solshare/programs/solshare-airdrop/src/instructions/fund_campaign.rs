@@ -13,7 +13,11 @@ pub struct FundCampaign<'info> {
     #[account(
         mut,
         has_one = creator,
-        constraint = campaign.status == CampaignStatus::Draft @ AirdropError::InvalidStatus,
+        // Funded campaigns can still be topped up until distribution starts
+        constraint = matches!(
+            campaign.status,
+            CampaignStatus::Draft | CampaignStatus::Funded
+        ) @ AirdropError::InvalidStatus,
     )]
     pub campaign: Account<'info, CampaignState>,
 
@@ -44,16 +48,17 @@ pub fn handler(ctx: Context<FundCampaign>, amount: u64) -> Result<()> {
     token::transfer(cpi_ctx, amount)?;
 
     let campaign = &mut ctx.accounts.campaign;
-    campaign.total_amount = campaign
-        .total_amount
-        .checked_add(amount)
-        .ok_or(AirdropError::Overflow)?;
-    campaign.status = CampaignStatus::Funded;
+    campaign.record_funding(amount)?;
+    if campaign.status == CampaignStatus::Draft {
+        campaign.set_status(CampaignStatus::Funded)?;
+    }
 
     emit!(CampaignFunded {
         creator: campaign.creator,
         campaign_id: campaign.campaign_id,
         amount,
+        total_amount: campaign.total_amount,
+        funding_events_count: campaign.funding_events_count,
     });
 
     Ok(())
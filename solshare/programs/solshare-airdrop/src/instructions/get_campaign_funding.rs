@@ -0,0 +1,14 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{CampaignFunding, CampaignState};
+
+#[derive(Accounts)]
+pub struct GetCampaignFunding<'info> {
+    pub campaign: Account<'info, CampaignState>,
+}
+
+/// Escrowed total against the tokens needed to pay every recipient, so
+/// clients can show how far along funding is
+pub fn handler(ctx: Context<GetCampaignFunding>) -> Result<CampaignFunding> {
+    ctx.accounts.campaign.funding()
+}
@@ -0,0 +1,21 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{CampaignProgress, CampaignState};
+
+#[derive(Accounts)]
+pub struct GetCampaignProgress<'info> {
+    pub campaign: Account<'info, CampaignState>,
+}
+
+pub fn handler(ctx: Context<GetCampaignProgress>) -> Result<CampaignProgress> {
+    let campaign = &ctx.accounts.campaign;
+
+    Ok(CampaignProgress {
+        distributed_count: campaign.distributed_count,
+        total_recipients: campaign.total_recipients,
+        distribution_cursor: campaign.distribution_cursor,
+        distributed_amount: campaign.distributed_amount,
+        total_amount: campaign.total_amount,
+        status: campaign.status,
+    })
+}
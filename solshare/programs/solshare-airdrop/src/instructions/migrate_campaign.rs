@@ -0,0 +1,67 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_spl::token::Mint;
+
+use crate::state::CampaignState;
+use crate::events::CampaignMigrated;
+
+#[derive(Accounts)]
+pub struct MigrateCampaign<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    /// CHECK: A campaign in an older layout can't be deserialized as
+    /// CampaignState; ownership, discriminator and creator are checked in the handler
+    #[account(mut)]
+    pub campaign: UncheckedAccount<'info>,
+
+    // The campaign's token_mint, read for the decimals older layouts lack
+    pub token_mint: Account<'info, Mint>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Grows a campaign created under an older layout to its current size, with
+/// the creator paying the extra rent. Current campaigns are left untouched.
+pub fn handler(ctx: Context<MigrateCampaign>) -> Result<()> {
+    let campaign_info = ctx.accounts.campaign.to_account_info();
+    require_keys_eq!(*campaign_info.owner, crate::ID, ErrorCode::AccountOwnedByWrongProgram);
+
+    let old_size = campaign_info.data_len();
+    let new_size = 8 + CampaignState::INIT_SPACE;
+    if old_size >= new_size {
+        return Ok(());
+    }
+
+    let mut campaign = CampaignState::upgrade_layout(&campaign_info.data.borrow())?;
+    require_keys_eq!(campaign.creator, ctx.accounts.creator.key(), ErrorCode::ConstraintHasOne);
+    require_keys_eq!(campaign.token_mint, ctx.accounts.token_mint.key(), ErrorCode::ConstraintAddress);
+    campaign.decimals = ctx.accounts.token_mint.decimals;
+
+    let rent_needed = Rent::get()?
+        .minimum_balance(new_size)
+        .saturating_sub(campaign_info.lamports());
+    if rent_needed > 0 {
+        transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.creator.to_account_info(),
+                    to: campaign_info.clone(),
+                },
+            ),
+            rent_needed,
+        )?;
+    }
+
+    campaign_info.resize(new_size)?;
+    campaign.try_serialize(&mut &mut campaign_info.data.borrow_mut()[..])?;
+
+    emit!(CampaignMigrated {
+        campaign_id: campaign.campaign_id,
+        old_size: old_size as u32,
+        new_size: new_size as u32,
+    });
+
+    Ok(())
+}
@@ -13,7 +13,6 @@ pub struct Refund<'info> {
     #[account(
         mut,
         has_one = creator,
-        constraint = campaign.status != CampaignStatus::Cancelled @ AirdropError::AlreadyCompleted,
     )]
     pub campaign: Account<'info, CampaignState>,
 
@@ -24,66 +23,111 @@ pub struct Refund<'info> {
     )]
     pub creator_ata: Account<'info, TokenAccount>,
 
+    // Sends the refund somewhere other than creator_ata, e.g. a treasury multisig
+    #[account(
+        mut,
+        constraint = refund_destination_ata.mint == campaign.token_mint @ AirdropError::InvalidRecipientMint,
+    )]
+    pub refund_destination_ata: Option<Account<'info, TokenAccount>>,
+
+    /// CHECK: Closed by the first refund, so only deserialized while the
+    /// campaign is still refundable
     #[account(
         mut,
         constraint = escrow_ata.key() == campaign.escrow_ata,
     )]
-    pub escrow_ata: Account<'info, TokenAccount>,
+    pub escrow_ata: UncheckedAccount<'info>,
 
     pub token_program: Program<'info, Token>,
 }
 
 pub fn handler(ctx: Context<Refund>) -> Result<()> {
-    let campaign = &ctx.accounts.campaign;
-    let refund_amount = ctx.accounts.escrow_ata.amount;
+    // A repeated refund is a no-op; the escrow is already drained and closed
+    if ctx.accounts.campaign.status == CampaignStatus::Cancelled {
+        return Ok(());
+    }
+
+    // Checks-effects-interactions: record the cancellation before any CPI
+    ctx.accounts.campaign.set_status(CampaignStatus::Cancelled)?;
+
+    let destination = ctx
+        .accounts
+        .refund_destination_ata
+        .as_ref()
+        .unwrap_or(&ctx.accounts.creator_ata);
+    refund_escrow(
+        &ctx.accounts.campaign,
+        &ctx.accounts.escrow_ata.to_account_info(),
+        destination,
+        &ctx.accounts.creator.to_account_info(),
+        &ctx.accounts.token_program.to_account_info(),
+    )?;
+
+    Ok(())
+}
+
+/// Sends everything left in a cancelled campaign's escrow to `destination`
+/// and closes the escrow, returning its rent to `creator`; shared with
+/// refund_many
+pub(crate) fn refund_escrow<'info>(
+    campaign: &Account<'info, CampaignState>,
+    escrow_ata: &AccountInfo<'info>,
+    destination: &Account<'info, TokenAccount>,
+    creator: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+) -> Result<u64> {
+    require_keys_eq!(*escrow_ata.owner, Token::id(), ErrorCode::AccountOwnedByWrongProgram);
+    let refund_amount = TokenAccount::try_deserialize(&mut &escrow_ata.data.borrow()[..])?.amount;
+
+    let creator_key = campaign.creator;
+    let campaign_id = campaign.campaign_id;
+    let bump = campaign.bump;
+    let seeds = &[
+        b"campaign".as_ref(),
+        creator_key.as_ref(),
+        campaign_id.as_ref(),
+        &[bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
 
     if refund_amount > 0 {
-        let creator = campaign.creator;
-        let campaign_id = campaign.campaign_id;
-        let bump = campaign.bump;
-        let seeds = &[
-            b"campaign".as_ref(),
-            creator.as_ref(),
-            campaign_id.as_ref(),
-            &[bump],
-        ];
-        let signer_seeds = &[&seeds[..]];
-
-        // Transfer remaining tokens back to creator
+        // A frozen destination would reject the transfer and strand the escrow
+        require!(!destination.is_frozen(), AirdropError::FrozenDestination);
+
+        // Transfer remaining tokens to the refund destination
         let cpi_accounts = Transfer {
-            from: ctx.accounts.escrow_ata.to_account_info(),
-            to: ctx.accounts.creator_ata.to_account_info(),
-            authority: ctx.accounts.campaign.to_account_info(),
+            from: escrow_ata.clone(),
+            to: destination.to_account_info(),
+            authority: campaign.to_account_info(),
         };
         let cpi_ctx = CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
+            token_program.clone(),
             cpi_accounts,
             signer_seeds,
         );
         token::transfer(cpi_ctx, refund_amount)?;
-
-        // Close escrow ATA
-        let close_accounts = CloseAccount {
-            account: ctx.accounts.escrow_ata.to_account_info(),
-            destination: ctx.accounts.creator.to_account_info(),
-            authority: ctx.accounts.campaign.to_account_info(),
-        };
-        let close_ctx = CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
-            close_accounts,
-            signer_seeds,
-        );
-        token::close_account(close_ctx)?;
     }
 
-    let campaign = &mut ctx.accounts.campaign;
-    campaign.status = CampaignStatus::Cancelled;
+    // Close the escrow ATA even when it was already empty, so its rent always
+    // returns to the creator
+    let close_accounts = CloseAccount {
+        account: escrow_ata.clone(),
+        destination: creator.clone(),
+        authority: campaign.to_account_info(),
+    };
+    let close_ctx = CpiContext::new_with_signer(
+        token_program.clone(),
+        close_accounts,
+        signer_seeds,
+    );
+    token::close_account(close_ctx)?;
 
     emit!(CampaignRefunded {
-        creator: campaign.creator,
-        campaign_id: campaign.campaign_id,
+        creator: creator_key,
+        campaign_id,
         refund_amount,
+        destination: destination.key(),
     });
 
-    Ok(())
+    Ok(refund_amount)
 }
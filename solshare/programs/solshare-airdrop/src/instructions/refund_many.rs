@@ -0,0 +1,66 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount};
+
+use crate::state::{CampaignState, CampaignStatus};
+use crate::error::AirdropError;
+use super::refund::refund_escrow;
+
+#[derive(Accounts)]
+pub struct RefundMany<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    // Remaining accounts: (campaign: CampaignState, escrow_ata,
+    // destination_ata: TokenAccount of the campaign's mint) for each campaign
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, RefundMany<'info>>) -> Result<()> {
+    let remaining = ctx.remaining_accounts;
+    require!(
+        !remaining.is_empty() && remaining.len() % 3 == 0,
+        AirdropError::InvalidRefundAccounts
+    );
+
+    let creator = ctx.accounts.creator.to_account_info();
+    let token_program = ctx.accounts.token_program.to_account_info();
+
+    for set in remaining.chunks_exact(3) {
+        let (campaign_info, escrow_info, destination_info) = (&set[0], &set[1], &set[2]);
+
+        let mut campaign = Account::<CampaignState>::try_from(campaign_info)?;
+        require_keys_eq!(campaign.creator, creator.key(), ErrorCode::ConstraintHasOne);
+        require_keys_eq!(
+            escrow_info.key(),
+            campaign.escrow_ata,
+            AirdropError::InvalidRefundAccounts
+        );
+        require!(campaign_info.is_writable, ErrorCode::ConstraintMut);
+
+        // Completed campaigns paid out in full and cancelled ones are already
+        // refunded
+        let eligible = !matches!(
+            campaign.status,
+            CampaignStatus::Completed | CampaignStatus::Cancelled
+        );
+        if !eligible {
+            continue;
+        }
+
+        let destination = Account::<TokenAccount>::try_from(destination_info)?;
+        require_keys_eq!(
+            destination.mint,
+            campaign.token_mint,
+            AirdropError::InvalidRecipientMint
+        );
+
+        // Checks-effects-interactions: persist the cancellation before any CPI,
+        // so a campaign listed twice is skipped the second time
+        campaign.set_status(CampaignStatus::Cancelled)?;
+        campaign.exit(&crate::ID)?;
+
+        refund_escrow(&campaign, escrow_info, &destination, &creator, &token_program)?;
+    }
+
+    Ok(())
+}
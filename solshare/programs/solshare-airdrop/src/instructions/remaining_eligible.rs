@@ -0,0 +1,14 @@
+use anchor_lang::prelude::*;
+
+use crate::state::CampaignState;
+
+#[derive(Accounts)]
+pub struct RemainingEligible<'info> {
+    pub campaign: Account<'info, CampaignState>,
+}
+
+/// Allowlist spots not yet claimed
+pub fn handler(ctx: Context<RemainingEligible>) -> Result<u32> {
+    let campaign = &ctx.accounts.campaign;
+    Ok(campaign.eligible_count.saturating_sub(campaign.claimed_count))
+}
@@ -0,0 +1,97 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer, CloseAccount};
+
+use crate::state::{CampaignState, CampaignStatus};
+use crate::error::AirdropError;
+use crate::events::CampaignRescued;
+
+#[derive(Accounts)]
+pub struct RescueRefund<'info> {
+    #[account(mut)]
+    pub admin_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = campaign.admin_authority == Some(admin_authority.key()) @ AirdropError::UnauthorizedAdmin,
+    )]
+    pub campaign: Account<'info, CampaignState>,
+
+    #[account(
+        mut,
+        constraint = campaign.rescue_destination == Some(rescue_ata.key()) @ AirdropError::InvalidRescueDestination,
+        constraint = rescue_ata.mint == campaign.token_mint @ AirdropError::InvalidRecipientMint,
+    )]
+    pub rescue_ata: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = escrow_ata.key() == campaign.escrow_ata,
+    )]
+    pub escrow_ata: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Last-resort refund for a campaign whose creator can no longer sign:
+/// drains the escrow to the registered rescue account and cancels the campaign
+pub fn handler(ctx: Context<RescueRefund>) -> Result<()> {
+    require!(
+        ctx.accounts.campaign.rescuable(Clock::get()?.unix_timestamp),
+        AirdropError::RescueNotAvailable
+    );
+
+    let amount = ctx.accounts.escrow_ata.amount;
+
+    // Checks-effects-interactions: record the cancellation before any CPI
+    let campaign = &mut ctx.accounts.campaign;
+    campaign.set_status(CampaignStatus::Cancelled)?;
+
+    let creator = campaign.creator;
+    let campaign_id = campaign.campaign_id;
+    let bump = campaign.bump;
+    let seeds = &[
+        b"campaign".as_ref(),
+        creator.as_ref(),
+        campaign_id.as_ref(),
+        &[bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    if amount > 0 {
+        require!(!ctx.accounts.rescue_ata.is_frozen(), AirdropError::FrozenDestination);
+
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.escrow_ata.to_account_info(),
+            to: ctx.accounts.rescue_ata.to_account_info(),
+            authority: ctx.accounts.campaign.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+        token::transfer(cpi_ctx, amount)?;
+    }
+
+    // The admin covers the transaction, so the escrow rent goes to them
+    let close_accounts = CloseAccount {
+        account: ctx.accounts.escrow_ata.to_account_info(),
+        destination: ctx.accounts.admin_authority.to_account_info(),
+        authority: ctx.accounts.campaign.to_account_info(),
+    };
+    let close_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        close_accounts,
+        signer_seeds,
+    );
+    token::close_account(close_ctx)?;
+
+    emit!(CampaignRescued {
+        campaign_id,
+        admin_authority: ctx.accounts.admin_authority.key(),
+        destination: ctx.accounts.rescue_ata.key(),
+        amount,
+    });
+
+    Ok(())
+}
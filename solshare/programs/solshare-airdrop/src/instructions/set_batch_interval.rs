@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{CampaignState, CampaignStatus};
+use crate::error::AirdropError;
+
+#[derive(Accounts)]
+pub struct SetBatchInterval<'info> {
+    pub creator: Signer<'info>,
+
+    // Can be tightened or relaxed while the campaign is still distributing
+    #[account(
+        mut,
+        has_one = creator,
+        constraint = matches!(
+            campaign.status,
+            CampaignStatus::Draft | CampaignStatus::Funded | CampaignStatus::Processing
+        ) @ AirdropError::InvalidStatus,
+    )]
+    pub campaign: Account<'info, CampaignState>,
+}
+
+/// 0 lets batches run back to back
+pub fn handler(ctx: Context<SetBatchInterval>, min_batch_interval_seconds: u32) -> Result<()> {
+    ctx.accounts.campaign.min_batch_interval_seconds = min_batch_interval_seconds;
+
+    Ok(())
+}
@@ -0,0 +1,23 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{CampaignState, CampaignStatus};
+use crate::error::AirdropError;
+
+#[derive(Accounts)]
+pub struct SetCrankFee<'info> {
+    pub creator: Signer<'info>,
+
+    // Fixed before funding so the creator can budget for it
+    #[account(
+        mut,
+        has_one = creator,
+        constraint = campaign.status == CampaignStatus::Draft @ AirdropError::InvalidStatus,
+    )]
+    pub campaign: Account<'info, CampaignState>,
+}
+
+pub fn handler(ctx: Context<SetCrankFee>, crank_fee_per_recipient: u64) -> Result<()> {
+    ctx.accounts.campaign.crank_fee_per_recipient = crank_fee_per_recipient;
+
+    Ok(())
+}
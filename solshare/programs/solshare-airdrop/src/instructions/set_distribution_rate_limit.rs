@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{CampaignState, CampaignStatus};
+use crate::error::AirdropError;
+
+#[derive(Accounts)]
+pub struct SetDistributionRateLimit<'info> {
+    pub creator: Signer<'info>,
+
+    // Can be tightened or relaxed while the campaign is still distributing
+    #[account(
+        mut,
+        has_one = creator,
+        constraint = matches!(
+            campaign.status,
+            CampaignStatus::Draft | CampaignStatus::Funded | CampaignStatus::Processing
+        ) @ AirdropError::InvalidStatus,
+    )]
+    pub campaign: Account<'info, CampaignState>,
+}
+
+pub fn handler(ctx: Context<SetDistributionRateLimit>, max_distributed_per_slot: Option<u64>) -> Result<()> {
+    require!(
+        max_distributed_per_slot != Some(0),
+        AirdropError::InvalidRateLimit
+    );
+    ctx.accounts.campaign.max_distributed_per_slot = max_distributed_per_slot;
+
+    Ok(())
+}
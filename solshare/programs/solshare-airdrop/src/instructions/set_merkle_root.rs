@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{CampaignState, CampaignStatus};
+use crate::error::AirdropError;
+use crate::events::MerkleRootSet;
+
+#[derive(Accounts)]
+pub struct SetMerkleRoot<'info> {
+    pub creator: Signer<'info>,
+
+    // The root is fixed once anything has been paid out
+    #[account(
+        mut,
+        has_one = creator,
+        constraint = campaign.status == CampaignStatus::Draft || campaign.status == CampaignStatus::Funded @ AirdropError::InvalidStatus,
+    )]
+    pub campaign: Account<'info, CampaignState>,
+}
+
+pub fn handler(ctx: Context<SetMerkleRoot>, merkle_root: [u8; 32], eligible_count: u32) -> Result<()> {
+    let campaign = &mut ctx.accounts.campaign;
+    // Every claim counts toward total_recipients, so the tree can't hold more leaves
+    require!(
+        eligible_count > 0 && eligible_count <= campaign.total_recipients,
+        AirdropError::InvalidRecipientCount
+    );
+
+    campaign.merkle_root = Some(merkle_root);
+    campaign.eligible_count = eligible_count;
+
+    emit!(MerkleRootSet {
+        campaign_id: campaign.campaign_id,
+        merkle_root,
+        eligible_count,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{CampaignState, CampaignStatus};
+use crate::error::AirdropError;
+
+#[derive(Accounts)]
+pub struct SetRescue<'info> {
+    pub creator: Signer<'info>,
+
+    // Registered before funding so the rescue path can't be added to a live escrow
+    #[account(
+        mut,
+        has_one = creator,
+        constraint = campaign.status == CampaignStatus::Draft @ AirdropError::InvalidStatus,
+    )]
+    pub campaign: Account<'info, CampaignState>,
+}
+
+pub fn handler(ctx: Context<SetRescue>, admin_authority: Pubkey, rescue_destination: Pubkey) -> Result<()> {
+    let campaign = &mut ctx.accounts.campaign;
+    // The rescue window is measured from the deadline
+    require!(campaign.deadline.is_some(), AirdropError::RescueRequiresDeadline);
+
+    campaign.admin_authority = Some(admin_authority);
+    campaign.rescue_destination = Some(rescue_destination);
+
+    Ok(())
+}
@@ -1,10 +1,12 @@
 pub mod error;
 pub mod events;
 pub mod instructions;
+pub mod merkle;
 pub mod state;
 
 use anchor_lang::prelude::*;
 use instructions::*;
+use state::{CampaignFunding, CampaignProgress};
 
 declare_id!("BDz31MWVhr9GHkQq3q8BL4Sp2tcEWqoss2zjNz5dhZKw");
 
@@ -18,8 +20,9 @@ pub mod solshare_airdrop {
         amount_per_recipient: u64,
         total_recipients: u32,
         crank_authority: Pubkey,
+        deadline: Option<i64>,
     ) -> Result<()> {
-        create_campaign::handler(ctx, campaign_id, amount_per_recipient, total_recipients, crank_authority)
+        create_campaign::handler(ctx, campaign_id, amount_per_recipient, total_recipients, crank_authority, deadline)
     }
 
     pub fn fund_campaign(ctx: Context<FundCampaign>, amount: u64) -> Result<()> {
@@ -28,12 +31,64 @@ pub mod solshare_airdrop {
 
     pub fn distribute_batch<'info>(
         ctx: Context<'_, '_, 'info, 'info, DistributeBatch<'info>>,
+        start_index: u32,
         recipient_count: u32,
     ) -> Result<()> {
-        distribute_batch::handler(ctx, recipient_count)
+        distribute_batch::handler(ctx, start_index, recipient_count)
     }
 
     pub fn refund(ctx: Context<Refund>) -> Result<()> {
         refund::handler(ctx)
     }
+
+    pub fn refund_many<'info>(ctx: Context<'_, '_, 'info, 'info, RefundMany<'info>>) -> Result<()> {
+        refund_many::handler(ctx)
+    }
+
+    pub fn set_merkle_root(ctx: Context<SetMerkleRoot>, merkle_root: [u8; 32], eligible_count: u32) -> Result<()> {
+        set_merkle_root::handler(ctx, merkle_root, eligible_count)
+    }
+
+    pub fn claim(ctx: Context<Claim>, index: u32, amount: u64, proof: Vec<[u8; 32]>) -> Result<()> {
+        claim::handler(ctx, index, amount, proof)
+    }
+
+    pub fn set_crank_fee(ctx: Context<SetCrankFee>, crank_fee_per_recipient: u64) -> Result<()> {
+        set_crank_fee::handler(ctx, crank_fee_per_recipient)
+    }
+
+    pub fn set_distribution_rate_limit(
+        ctx: Context<SetDistributionRateLimit>,
+        max_distributed_per_slot: Option<u64>,
+    ) -> Result<()> {
+        set_distribution_rate_limit::handler(ctx, max_distributed_per_slot)
+    }
+
+    pub fn set_rescue(ctx: Context<SetRescue>, admin_authority: Pubkey, rescue_destination: Pubkey) -> Result<()> {
+        set_rescue::handler(ctx, admin_authority, rescue_destination)
+    }
+
+    pub fn rescue_refund(ctx: Context<RescueRefund>) -> Result<()> {
+        rescue_refund::handler(ctx)
+    }
+
+    pub fn migrate_campaign(ctx: Context<MigrateCampaign>) -> Result<()> {
+        migrate_campaign::handler(ctx)
+    }
+
+    pub fn get_campaign_progress(ctx: Context<GetCampaignProgress>) -> Result<CampaignProgress> {
+        get_campaign_progress::handler(ctx)
+    }
+
+    pub fn remaining_eligible(ctx: Context<RemainingEligible>) -> Result<u32> {
+        remaining_eligible::handler(ctx)
+    }
+
+    pub fn get_campaign_funding(ctx: Context<GetCampaignFunding>) -> Result<CampaignFunding> {
+        get_campaign_funding::handler(ctx)
+    }
+
+    pub fn set_batch_interval(ctx: Context<SetBatchInterval>, min_batch_interval_seconds: u32) -> Result<()> {
+        set_batch_interval::handler(ctx, min_batch_interval_seconds)
+    }
 }
@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+use solana_sha256_hasher::hashv;
+
+// Domain separation so an internal node can never be presented as a leaf
+const LEAF_PREFIX: &[u8] = &[0];
+const NODE_PREFIX: &[u8] = &[1];
+
+/// Leaf for a recipient's allocation; binding the amount means a proof for
+/// one amount can't be used to claim another
+pub fn leaf(index: u32, claimant: &Pubkey, amount: u64) -> [u8; 32] {
+    hashv(&[
+        LEAF_PREFIX,
+        &index.to_le_bytes(),
+        claimant.as_ref(),
+        &amount.to_le_bytes(),
+    ])
+    .to_bytes()
+}
+
+/// Verifies `proof` links `leaf` to `root`; sibling pairs are hashed in
+/// sorted order so the proof doesn't need to encode left/right positions
+pub fn verify(proof: &[[u8; 32]], root: &[u8; 32], leaf: [u8; 32]) -> bool {
+    let computed = proof.iter().fold(leaf, |node, sibling| {
+        let (a, b) = if node <= *sibling { (node, *sibling) } else { (*sibling, node) };
+        hashv(&[NODE_PREFIX, &a, &b]).to_bytes()
+    });
+    computed == *root
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(a: [u8; 32], b: [u8; 32]) -> [u8; 32] {
+        let (a, b) = if a <= b { (a, b) } else { (b, a) };
+        hashv(&[NODE_PREFIX, &a, &b]).to_bytes()
+    }
+
+    #[test]
+    fn proof_binds_index_claimant_and_amount() {
+        let alice = Pubkey::new_unique();
+        let bob = Pubkey::new_unique();
+        let alice_leaf = leaf(0, &alice, 100);
+        let bob_leaf = leaf(1, &bob, 250);
+        let root = node(alice_leaf, bob_leaf);
+
+        assert!(verify(&[bob_leaf], &root, alice_leaf));
+        assert!(verify(&[alice_leaf], &root, bob_leaf));
+
+        assert!(!verify(&[bob_leaf], &root, leaf(0, &alice, 1_000)));
+        assert!(!verify(&[bob_leaf], &root, leaf(1, &alice, 100)));
+        assert!(!verify(&[bob_leaf], &root, leaf(0, &bob, 100)));
+    }
+}
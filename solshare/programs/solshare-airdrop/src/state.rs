@@ -1,6 +1,10 @@
 use anchor_lang::prelude::*;
 
+use crate::error::AirdropError;
+use crate::events::CampaignStatusChanged;
+
 #[account]
+#[derive(InitSpace)]
 pub struct CampaignState {
     pub creator: Pubkey,
     pub campaign_id: [u8; 16],
@@ -14,25 +18,245 @@ pub struct CampaignState {
     pub status: CampaignStatus,
     pub crank_authority: Pubkey,
     pub bump: u8,
+    // When the crank should have finished by. Refunds never wait on the
+    // crank, so this only starts the rescue clock.
+    pub deadline: Option<i64>,
+    // Index into the crank's ordered recipient list of the next recipient to pay
+    pub distribution_cursor: u32,
+    // Root of the (index, claimant, amount) allocation tree for claim-based campaigns
+    pub merkle_root: Option<[u8; 32]>,
+    // Tokens paid from escrow to the crank per distributed recipient, and the running total
+    pub crank_fee_per_recipient: u64,
+    pub crank_fees_paid: u64,
+    // Leaves in the Merkle allocation and how many of them have claimed so far
+    pub eligible_count: u32,
+    pub claimed_count: u32,
+    // Opt-in recovery if the creator loses their key: once the rescue window
+    // opens, the admin can send the escrow to the pre-registered token account
+    pub admin_authority: Option<Pubkey>,
+    pub rescue_destination: Option<Pubkey>,
+    // Optional throttle on tokens leaving escrow per slot (payouts plus crank
+    // fees), so a compromised crank can't drain a large campaign in one block
+    pub max_distributed_per_slot: Option<u64>,
+    pub last_distribution_slot: u64,
+    pub distributed_this_slot: u64,
+    // token_mint's decimals, so amounts in events can be shown without
+    // fetching the mint
+    pub decimals: u8,
+    // Deposits made into escrow so far; CampaignFunded carries the running
+    // total after each one
+    pub funding_events_count: u32,
+    // Minimum spacing between distribute_batch calls, against a crank firing
+    // batches back to back; 0 disables it. last_batch_at is 0 before the first.
+    pub min_batch_interval_seconds: u32,
+    pub last_batch_at: i64,
 }
 
 impl CampaignState {
-    pub const SIZE: usize = 8  // discriminator
-        + 32  // creator
-        + 16  // campaign_id
-        + 32  // token_mint
-        + 32  // escrow_ata
-        + 8   // amount_per_recipient
-        + 8   // total_amount
-        + 8   // distributed_amount
-        + 4   // total_recipients
-        + 4   // distributed_count
-        + 1   // status
-        + 32  // crank_authority
-        + 1;  // bump
+    /// The original layout, creator through bump, before any fields were appended
+    pub const LEGACY_SIZE: usize = 186;
+
+    /// Reads a campaign saved under an older, shorter layout. Fields are only
+    /// ever appended, and each one added so far reads zeroed bytes as its
+    /// default (None or 0), so padding the data out to full size is enough;
+    /// only `decimals` needs filling in from the mint afterwards.
+    pub fn upgrade_layout(data: &[u8]) -> Result<Self> {
+        let size = 8 + Self::INIT_SPACE;
+        require!(
+            (Self::LEGACY_SIZE..size).contains(&data.len()),
+            AirdropError::InvalidCampaignLayout
+        );
+        let mut padded = data.to_vec();
+        padded.resize(size, 0);
+        let mut campaign = Self::try_deserialize(&mut &padded[..])?;
+
+        // Campaigns from before the cursor existed were paid out in order
+        campaign.distribution_cursor = campaign
+            .distribution_cursor
+            .max(campaign.distributed_count);
+        // A campaign holding tokens was funded before deposits were counted
+        if campaign.total_amount > 0 {
+            campaign.funding_events_count = campaign.funding_events_count.max(1);
+        }
+        Ok(campaign)
+    }
 }
 
+/// How long after the deadline the admin must wait before rescuing a campaign
+pub const RESCUE_DELAY_SECONDS: i64 = 180 * 24 * 60 * 60;
+
+/// Returned by `get_campaign_progress`
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct CampaignProgress {
+    pub distributed_count: u32,
+    pub total_recipients: u32,
+    pub distribution_cursor: u32,
+    pub distributed_amount: u64,
+    pub total_amount: u64,
+    pub status: CampaignStatus,
+}
+
+/// Returned by `get_campaign_funding`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct CampaignFunding {
+    pub total_amount: u64,
+    pub required_amount: u64,
+    pub fully_funded: bool,
+}
+
+impl CampaignState {
+    /// The rescue window opens RESCUE_DELAY_SECONDS after the deadline;
+    /// campaigns without a deadline can never be rescued
+    pub fn rescuable(&self, now: i64) -> bool {
+        self.deadline
+            .and_then(|deadline| deadline.checked_add(RESCUE_DELAY_SECONDS))
+            .is_some_and(|opens_at| now >= opens_at)
+    }
+
+    /// Tokens the escrow needs to pay every declared recipient plus the
+    /// crank's fee for each
+    pub fn required_funding(&self) -> Result<u64> {
+        let per_recipient = (self.amount_per_recipient as u128)
+            .checked_add(self.crank_fee_per_recipient as u128)
+            .ok_or(AirdropError::Overflow)?;
+        let required = per_recipient
+            .checked_mul(self.total_recipients as u128)
+            .ok_or(AirdropError::Overflow)?;
+        u64::try_from(required).map_err(|_| error!(AirdropError::Overflow))
+    }
+
+    pub fn funding(&self) -> Result<CampaignFunding> {
+        let required_amount = self.required_funding()?;
+        Ok(CampaignFunding {
+            total_amount: self.total_amount,
+            required_amount,
+            fully_funded: self.total_amount >= required_amount,
+        })
+    }
+
+    /// Adds a deposit of `amount` to the escrowed total
+    pub fn record_funding(&mut self, amount: u64) -> Result<()> {
+        self.total_amount = self
+            .total_amount
+            .checked_add(amount)
+            .ok_or(AirdropError::Overflow)?;
+        self.funding_events_count = self
+            .funding_events_count
+            .checked_add(1)
+            .ok_or(AirdropError::Overflow)?;
+        Ok(())
+    }
+
+    /// Escrowed tokens not yet paid out to recipients or the crank
+    pub fn remaining_budget(&self) -> Result<u64> {
+        self.total_amount
+            .checked_sub(self.distributed_amount)
+            .and_then(|remaining| remaining.checked_sub(self.crank_fees_paid))
+            .ok_or(error!(AirdropError::InsufficientFunds))
+    }
+
+    /// Records `recipients` payouts totalling `amount` and returns whether that
+    /// completed the campaign. Completion only fires once exactly the declared
+    /// recipient count has been paid; overshooting it is rejected.
+    pub fn record_payouts(&mut self, recipients: u32, amount: u64) -> Result<bool> {
+        let distributed_count = self
+            .distributed_count
+            .checked_add(recipients)
+            .ok_or(AirdropError::Overflow)?;
+        require!(
+            distributed_count <= self.total_recipients,
+            AirdropError::RecipientCountExceeded
+        );
+
+        self.distributed_amount = self
+            .distributed_amount
+            .checked_add(amount)
+            .ok_or(AirdropError::Overflow)?;
+        self.distributed_count = distributed_count;
+        self.set_status(CampaignStatus::Processing)?;
+
+        let completed = self.total_recipients > 0 && self.distributed_count == self.total_recipients;
+        if completed {
+            self.set_status(CampaignStatus::Completed)?;
+        }
+        Ok(completed)
+    }
+
+    /// Counts `amount` against the per-slot limit, starting a fresh count
+    /// whenever `slot` moves on. Once the limit is hit the crank has to wait
+    /// for the next slot.
+    pub fn record_slot_distribution(&mut self, slot: u64, amount: u64) -> Result<()> {
+        let already = if slot == self.last_distribution_slot {
+            self.distributed_this_slot
+        } else {
+            0
+        };
+        let distributed = already.checked_add(amount).ok_or(AirdropError::Overflow)?;
+        if let Some(max) = self.max_distributed_per_slot {
+            require!(distributed <= max, AirdropError::DistributionRateLimited);
+        }
+
+        self.last_distribution_slot = slot;
+        self.distributed_this_slot = distributed;
+        Ok(())
+    }
+
+    /// Records a batch at `now`, refusing it until min_batch_interval_seconds
+    /// have passed since the previous one
+    pub fn record_batch_time(&mut self, now: i64) -> Result<()> {
+        let earliest = self
+            .last_batch_at
+            .saturating_add(i64::from(self.min_batch_interval_seconds));
+        require!(now >= earliest, AirdropError::BatchTooSoon);
+        self.last_batch_at = now;
+        Ok(())
+    }
+
+    /// Moves the campaign to `next`, rejecting transitions the lifecycle
+    /// doesn't allow (e.g. reviving a completed campaign). Every status change
+    /// goes through here, so this is where CampaignStatusChanged is emitted.
+    pub fn set_status(&mut self, next: CampaignStatus) -> Result<()> {
+        require!(
+            self.status.can_transition_to(next),
+            AirdropError::InvalidStatusTransition
+        );
+        let old_status = self.status;
+        self.status = next;
+        if old_status != next {
+            emit!(CampaignStatusChanged {
+                campaign_id: self.campaign_id,
+                old_status,
+                new_status: next,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Marks a Merkle leaf as claimed so it can only be paid out once
+#[account]
+#[derive(InitSpace)]
+pub struct ClaimStatus {
+    pub campaign: Pubkey,
+    pub index: u32,
+    pub claimant: Pubkey,
+    pub amount: u64,
+    pub claimed: bool,
+    pub bump: u8,
+}
+
+// Created by distribute_batch on a recipient's first payout; its existence
+// stops the crank paying the same recipient again in a later batch
+#[account]
+#[derive(InitSpace)]
+pub struct RecipientReceipt {
+    pub campaign: Pubkey,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
 pub enum CampaignStatus {
     Draft,
     Funded,
@@ -40,3 +264,245 @@ pub enum CampaignStatus {
     Completed,
     Cancelled,
 }
+
+impl CampaignStatus {
+    /// Draft -> Funded -> Processing -> Completed, with Cancelled reachable from
+    /// any live state. Completed can still be cancelled to reclaim excess escrow,
+    /// and Processing repeats across batches.
+    pub fn can_transition_to(self, next: CampaignStatus) -> bool {
+        use CampaignStatus::*;
+        matches!(
+            (self, next),
+            (Draft, Funded)
+                | (Draft, Cancelled)
+                | (Funded, Processing)
+                | (Funded, Cancelled)
+                | (Processing, Processing)
+                | (Processing, Completed)
+                | (Processing, Cancelled)
+                | (Completed, Cancelled)
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CampaignStatus::{self, *};
+    use super::*;
+
+    const ALL: [CampaignStatus; 5] = [Draft, Funded, Processing, Completed, Cancelled];
+
+    #[test]
+    fn status_transitions() {
+        let valid = [
+            (Draft, Funded),
+            (Draft, Cancelled),
+            (Funded, Processing),
+            (Funded, Cancelled),
+            (Processing, Processing),
+            (Processing, Completed),
+            (Processing, Cancelled),
+            (Completed, Cancelled),
+        ];
+
+        for from in ALL {
+            for to in ALL {
+                assert_eq!(
+                    from.can_transition_to(to),
+                    valid.contains(&(from, to)),
+                    "{:?} -> {:?}",
+                    from,
+                    to
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn rescue_opens_long_after_the_deadline() {
+        let mut state = campaign(2);
+        assert!(!state.rescuable(i64::MAX));
+
+        state.deadline = Some(1_000);
+        assert!(!state.rescuable(1_000));
+        assert!(!state.rescuable(1_000 + RESCUE_DELAY_SECONDS - 1));
+        assert!(state.rescuable(1_000 + RESCUE_DELAY_SECONDS));
+    }
+
+    #[test]
+    fn terminal_states_cannot_be_revived() {
+        for to in [Draft, Funded, Processing] {
+            assert!(!Completed.can_transition_to(to));
+            assert!(!Cancelled.can_transition_to(to));
+        }
+        assert!(!Cancelled.can_transition_to(Cancelled));
+    }
+
+    fn campaign(total_recipients: u32) -> CampaignState {
+        CampaignState {
+            creator: Pubkey::new_unique(),
+            campaign_id: [0; 16],
+            token_mint: Pubkey::new_unique(),
+            escrow_ata: Pubkey::new_unique(),
+            amount_per_recipient: 10,
+            total_amount: 10 * total_recipients as u64,
+            distributed_amount: 0,
+            total_recipients,
+            distributed_count: 0,
+            status: Funded,
+            crank_authority: Pubkey::new_unique(),
+            bump: 255,
+            deadline: None,
+            distribution_cursor: 0,
+            merkle_root: None,
+            crank_fee_per_recipient: 0,
+            crank_fees_paid: 0,
+            eligible_count: 0,
+            claimed_count: 0,
+            admin_authority: None,
+            rescue_destination: None,
+            max_distributed_per_slot: None,
+            last_distribution_slot: 0,
+            distributed_this_slot: 0,
+            decimals: 6,
+            funding_events_count: 1,
+            min_batch_interval_seconds: 0,
+            last_batch_at: 0,
+        }
+    }
+
+    #[test]
+    fn completes_only_at_declared_recipient_count() {
+        let mut state = campaign(3);
+        assert!(!state.record_payouts(2, 20).unwrap());
+        assert_eq!(state.status, Processing);
+
+        assert!(state.record_payouts(1, 10).unwrap());
+        assert_eq!(state.status, Completed);
+        assert_eq!(state.distributed_amount, 30);
+    }
+
+    #[test]
+    fn batches_must_be_spaced_by_the_interval() {
+        let mut state = campaign(4);
+        // Disabled: back-to-back batches are fine
+        state.record_batch_time(1_000).unwrap();
+        state.record_batch_time(1_000).unwrap();
+
+        state.min_batch_interval_seconds = 60;
+        assert_eq!(
+            state.record_batch_time(1_059).unwrap_err(),
+            AirdropError::BatchTooSoon.into()
+        );
+        assert_eq!(state.last_batch_at, 1_000);
+        state.record_batch_time(1_060).unwrap();
+        assert_eq!(state.last_batch_at, 1_060);
+    }
+
+    #[test]
+    fn funding_keeps_a_running_total() {
+        let mut state = campaign(3);
+        state.total_amount = 0;
+        state.funding_events_count = 0;
+
+        state.record_funding(10).unwrap();
+        state.record_funding(25).unwrap();
+        assert_eq!((state.total_amount, state.funding_events_count), (35, 2));
+
+        assert!(state.record_funding(u64::MAX).is_err());
+    }
+
+    #[test]
+    fn slot_limit_resets_on_the_next_slot() {
+        let mut state = campaign(4);
+        state.max_distributed_per_slot = Some(25);
+
+        state.record_slot_distribution(7, 20).unwrap();
+        assert!(state.record_slot_distribution(7, 10).is_err());
+        assert_eq!(state.distributed_this_slot, 20);
+
+        state.record_slot_distribution(7, 5).unwrap();
+        assert_eq!(state.distributed_this_slot, 25);
+
+        state.record_slot_distribution(8, 20).unwrap();
+        assert_eq!(state.last_distribution_slot, 8);
+        assert_eq!(state.distributed_this_slot, 20);
+    }
+
+    #[test]
+    fn slot_usage_is_tracked_without_a_limit() {
+        let mut state = campaign(4);
+        state.record_slot_distribution(3, u64::MAX).unwrap();
+        assert_eq!(state.distributed_this_slot, u64::MAX);
+        assert!(state.record_slot_distribution(3, 1).is_err());
+    }
+
+    #[test]
+    fn rejects_payouts_beyond_declared_recipients() {
+        let mut state = campaign(2);
+        assert!(state.record_payouts(3, 30).is_err());
+        assert_eq!(state.distributed_count, 0);
+        assert_eq!(state.status, Funded);
+    }
+
+    #[test]
+    fn derived_size_matches_the_manual_layout() {
+        // The hand-counted SIZE was 347 bytes; decimals, funding_events_count
+        // and the batch interval fields have been appended since
+        assert_eq!(8 + CampaignState::INIT_SPACE, 347 + 1 + 4 + 4 + 8);
+    }
+
+    #[test]
+    fn upgrades_a_legacy_layout() {
+        let mut legacy = campaign(5);
+        legacy.status = Processing;
+        legacy.distributed_count = 2;
+        legacy.distributed_amount = 20;
+        let mut data = Vec::new();
+        legacy.try_serialize(&mut data).unwrap();
+        data.truncate(CampaignState::LEGACY_SIZE);
+
+        let upgraded = CampaignState::upgrade_layout(&data).unwrap();
+        assert_eq!(upgraded.creator, legacy.creator);
+        assert_eq!(upgraded.crank_authority, legacy.crank_authority);
+        assert_eq!(upgraded.status, Processing);
+        assert_eq!(upgraded.distributed_amount, 20);
+        assert_eq!(upgraded.bump, 255);
+        assert_eq!(upgraded.deadline, None);
+        assert_eq!(upgraded.merkle_root, None);
+        assert_eq!(upgraded.admin_authority, None);
+        assert_eq!(upgraded.max_distributed_per_slot, None);
+        // The crank resumes after the recipients already paid
+        assert_eq!(upgraded.distribution_cursor, 2);
+        // Its one deposit predates the count
+        assert_eq!(upgraded.funding_events_count, 1);
+
+        let mut current = Vec::new();
+        upgraded.try_serialize(&mut current).unwrap();
+        current.resize(8 + CampaignState::INIT_SPACE, 0);
+        assert!(CampaignState::upgrade_layout(&current).is_err());
+        assert!(CampaignState::upgrade_layout(&data[..CampaignState::LEGACY_SIZE - 1]).is_err());
+    }
+
+    #[test]
+    fn funding_compares_escrow_to_the_full_budget() {
+        // 5 recipients at 10 tokens each
+        let mut state = campaign(5);
+        for (total_amount, fully_funded) in [(30, false), (50, true), (80, true)] {
+            state.total_amount = total_amount;
+            let funding = state.funding().unwrap();
+            assert_eq!(funding.required_amount, 50);
+            assert_eq!(funding.total_amount, total_amount);
+            assert_eq!(funding.fully_funded, fully_funded);
+        }
+
+        // Crank fees come out of the same escrow
+        state.total_amount = 50;
+        state.crank_fee_per_recipient = 2;
+        assert_eq!(state.required_funding().unwrap(), 60);
+        assert!(!state.funding().unwrap().fully_funded);
+
+        state.amount_per_recipient = u64::MAX;
+        assert!(state.required_funding().is_err());
+    }
+}
@@ -26,4 +26,72 @@ pub enum PaymentError {
     ArithmeticOverflow,
     #[msg("Invalid creator account - does not match vault owner")]
     InvalidCreatorAccount,
+    #[msg("Post does not belong to the tipped creator")]
+    InvalidPost,
+    #[msg("Subscription amount exceeds the platform maximum")]
+    SubscriptionAmountTooHigh,
+    #[msg("Subscription is paid in a different currency")]
+    SubscriptionMintMismatch,
+    #[msg("Creator does not accept payments in this mint")]
+    UnacceptedMint,
+    #[msg("Earnings were already rolled up today")]
+    RollupAlreadyRecorded,
+    #[msg("Insufficient funds for the first payment plus subscription account rent")]
+    InsufficientFundsForSubscription,
+    #[msg("Subscription is disputed; charges are paused until the creator resolves it")]
+    SubscriptionDisputed,
+    #[msg("Subscription is not disputed")]
+    SubscriptionNotDisputed,
+    #[msg("Too many subscription tiers")]
+    TooManyTiers,
+    #[msg("Tier name is too long")]
+    TierNameTooLong,
+    #[msg("Tier index is out of range")]
+    InvalidTierIndex,
+    #[msg("Subscribing to a tier requires the creator's tier catalog")]
+    TierCatalogRequired,
+    #[msg("Amount does not match the tier price")]
+    TierAmountMismatch,
+    #[msg("Subscription is still active")]
+    SubscriptionStillActive,
+    #[msg("Subscription has not been inactive long enough to close")]
+    SubscriptionRecentlyActive,
+    #[msg("Vault subscriber count is already zero")]
+    SubscriberCountUnderflow,
+    #[msg("Minimum subscription duration must be greater than zero")]
+    InvalidMinDuration,
+    #[msg("Subscription is still within its minimum commitment")]
+    MinimumDurationNotMet,
+    #[msg("Tip percentage cannot exceed 10000 basis points")]
+    InvalidTipPercentage,
+    #[msg("Fee recipient must be a system-owned wallet")]
+    InvalidFeeRecipient,
+    #[msg("Vault has no earnings available to withdraw")]
+    NothingToWithdraw,
+    #[msg("Vault keeps a subscriber index; pass the page to update")]
+    SubscriberIndexRequired,
+    #[msg("Subscriber index page is full")]
+    SubscriberIndexPageFull,
+    #[msg("Subscriber is not listed on this index page")]
+    SubscriberNotIndexed,
+    #[msg("Subscription is not between this subscriber and creator")]
+    SubscriptionMismatch,
+    #[msg("Creator has reached their maximum number of subscribers")]
+    SubscriberLimitReached,
+    #[msg("Vault bump is not the canonical bump for its seeds")]
+    VaultBumpMismatch,
+    #[msg("Vault has not been offered to this wallet")]
+    NotPendingCreator,
+    #[msg("Vault still has active subscribers")]
+    VaultHasSubscribers,
+    #[msg("Account is not a platform config in an older layout")]
+    InvalidConfigLayout,
+    #[msg("Account is not a creator vault in an older layout")]
+    InvalidVaultLayout,
+    #[msg("Platform stats are tracked; pass the platform_stats account")]
+    PlatformStatsRequired,
+    #[msg("Account is not a subscription in an older layout")]
+    InvalidSubscriptionLayout,
+    #[msg("Account is not a tip record in an older layout")]
+    InvalidTipRecordLayout,
 }
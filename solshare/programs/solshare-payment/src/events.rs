@@ -14,6 +14,22 @@ pub struct TipSent {
     pub amount: u64,
     pub fee: u64,
     pub post: Option<Pubkey>,
+    pub mint: Option<Pubkey>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct FirstSupporterRecorded {
+    pub vault: Pubkey,
+    pub creator: Pubkey,
+    pub supporter: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TipRecordClosed {
+    pub tip_record: Pubkey,
+    pub from: Pubkey,
     pub timestamp: i64,
 }
 
@@ -22,6 +38,9 @@ pub struct SubscriptionCreated {
     pub subscriber: Pubkey,
     pub creator: Pubkey,
     pub amount_per_month: u64,
+    pub payment_count: u32,
+    pub mint: Option<Pubkey>,
+    pub reference: Option<[u8; 16]>,
     pub timestamp: i64,
 }
 
@@ -31,6 +50,10 @@ pub struct SubscriptionProcessed {
     pub creator: Pubkey,
     pub amount: u64,
     pub fee: u64,
+    pub payment_count: u32,
+    pub mint: Option<Pubkey>,
+    pub reference: Option<[u8; 16]>,
+    pub crank_reward: u64,
     pub timestamp: i64,
 }
 
@@ -41,10 +64,83 @@ pub struct SubscriptionCancelled {
     pub timestamp: i64,
 }
 
+#[event]
+pub struct DisputeOpened {
+    pub subscriber: Pubkey,
+    pub creator: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DisputeResolved {
+    pub subscriber: Pubkey,
+    pub creator: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VaultVerificationChanged {
+    pub vault: Pubkey,
+    pub creator: Pubkey,
+    pub verified: bool,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SubscriptionTiersUpdated {
+    pub creator: Pubkey,
+    pub vault: Pubkey,
+    pub tier_count: u8,
+}
+
+#[event]
+pub struct SubscriptionClosed {
+    pub subscriber: Pubkey,
+    pub creator: Pubkey,
+    pub closed_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AutoRenewUpdated {
+    pub subscriber: Pubkey,
+    pub creator: Pubkey,
+    pub auto_renew: bool,
+}
+
+#[event]
+pub struct SubscriptionLapsed {
+    pub subscriber: Pubkey,
+    pub creator: Pubkey,
+    pub payment_count: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DailyEarnings {
+    pub creator: Pubkey,
+    pub day_index: i64,
+    pub earned_since_last: u64,
+    pub timestamp: i64,
+}
+
 #[event]
 pub struct Withdrawal {
     pub creator: Pubkey,
     pub amount: u64,
+    // Part of `amount` kept by the treasury; the creator received amount - fee
+    pub fee: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct StateCorrectionApplied {
+    pub vault: Pubkey,
+    pub creator: Pubkey,
+    pub total_earned: u64,
+    // withdrawn before it was clamped back to total_earned
+    pub previous_withdrawn: u64,
     pub timestamp: i64,
 }
 
@@ -54,3 +150,73 @@ pub struct PlatformConfigUpdated {
     pub fee_basis_points: u16,
     pub timestamp: i64,
 }
+
+#[event]
+pub struct TreasurySwept {
+    pub fee_recipient: Pubkey,
+    pub amount: u64,
+    pub total_swept: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SubscriptionPeriodGifted {
+    pub subscriber: Pubkey,
+    pub creator: Pubkey,
+    pub gifter: Pubkey,
+    pub amount: u64,
+    pub fee: u64,
+    pub payment_count: u32,
+    pub last_payment: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VaultTransferProposed {
+    pub vault: Pubkey,
+    pub creator: Pubkey,
+    // None when the creator withdrew the offer
+    pub pending_creator: Option<Pubkey>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VaultTransferred {
+    pub old_vault: Pubkey,
+    pub new_vault: Pubkey,
+    pub old_creator: Pubkey,
+    pub new_creator: Pubkey,
+    // Escrowed lamports moved into the new vault
+    pub lamports: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PlatformConfigMigrated {
+    pub old_size: u32,
+    pub new_size: u32,
+}
+
+#[event]
+pub struct VaultMigrated {
+    pub vault: Pubkey,
+    pub creator: Pubkey,
+    pub old_size: u32,
+    pub new_size: u32,
+}
+
+#[event]
+pub struct SubscriptionMigrated {
+    pub subscription: Pubkey,
+    pub subscriber: Pubkey,
+    pub old_size: u32,
+    pub new_size: u32,
+}
+
+#[event]
+pub struct TipRecordMigrated {
+    pub tip_record: Pubkey,
+    pub from: Pubkey,
+    pub old_size: u32,
+    pub new_size: u32,
+}
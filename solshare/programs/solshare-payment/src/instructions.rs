@@ -1,15 +1,85 @@
 pub mod initialize_platform;
+pub mod initialize_platform_stats;
+pub mod initialize_treasury;
+pub mod set_stats_tracking;
 pub mod initialize_vault;
 pub mod tip_creator;
+pub mod tip_creator_wrapped;
+pub mod tip_creator_percentage;
+pub mod tip_and_init_vault;
+pub mod close_tip_record;
 pub mod subscribe;
+pub mod set_subscription_tiers;
 pub mod process_subscription;
+pub mod gift_subscription_period;
+pub mod subscribe_spl;
+pub mod process_subscription_spl;
+pub mod process_subscription_prepaid;
 pub mod cancel_subscription;
+pub mod close_inactive_subscription;
+pub mod open_subscriber_index_page;
+pub mod is_subscription_due;
+pub mod next_payment_due;
+pub mod is_subscription_active;
+pub mod set_auto_renew;
+pub mod dispute_subscription;
+pub mod resolve_dispute;
 pub mod withdraw;
+pub mod set_required_mint;
+pub mod set_max_subscribers;
+pub mod record_daily_rollup;
+pub mod sweep_treasury;
+pub mod set_max_subscription_amount;
+pub mod set_vault_verified;
+pub mod set_free_withdrawal_threshold;
+pub mod set_crank_reward;
+pub mod set_fee_rounding;
+pub mod transfer_vault;
+pub mod accept_vault;
+pub mod migrate_platform_config;
+pub mod migrate_vault;
+pub mod migrate_subscription;
+pub mod migrate_tip_record;
 
 pub use initialize_platform::*;
+pub use initialize_platform_stats::*;
+pub use initialize_treasury::*;
+pub use set_stats_tracking::*;
 pub use initialize_vault::*;
 pub use tip_creator::*;
+pub use tip_creator_wrapped::*;
+pub use tip_creator_percentage::*;
+pub use tip_and_init_vault::*;
+pub use close_tip_record::*;
 pub use subscribe::*;
+pub use set_subscription_tiers::*;
 pub use process_subscription::*;
+pub use gift_subscription_period::*;
+pub use subscribe_spl::*;
+pub use process_subscription_spl::*;
+pub use process_subscription_prepaid::*;
 pub use cancel_subscription::*;
+pub use close_inactive_subscription::*;
+pub use open_subscriber_index_page::*;
+pub use is_subscription_due::*;
+pub use next_payment_due::*;
+pub use is_subscription_active::*;
+pub use set_auto_renew::*;
+pub use dispute_subscription::*;
+pub use resolve_dispute::*;
 pub use withdraw::*;
+pub use set_required_mint::*;
+pub use set_max_subscribers::*;
+pub use record_daily_rollup::*;
+pub use sweep_treasury::*;
+pub use set_max_subscription_amount::*;
+pub use set_vault_verified::*;
+pub use set_free_withdrawal_threshold::*;
+pub use set_crank_reward::*;
+pub use set_fee_rounding::*;
+pub use transfer_vault::*;
+pub use accept_vault::*;
+pub use migrate_platform_config::*;
+pub use migrate_vault::*;
+pub use migrate_subscription::*;
+pub use migrate_tip_record::*;
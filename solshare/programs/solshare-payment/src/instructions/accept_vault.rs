@@ -0,0 +1,74 @@
+use anchor_lang::prelude::*;
+use crate::state::CreatorVault;
+use crate::error::PaymentError;
+use crate::events::VaultTransferred;
+use crate::utils::transfer_from_pda;
+
+// Vault addresses are seeded by the creator's key, so a vault can't change
+// hands in place. Accepting it opens a vault at the new wallet's address in
+// the same namespace, carries the books and escrowed lamports over, and
+// closes the old vault, refunding its rent to the old wallet.
+#[derive(Accounts)]
+pub struct AcceptVault<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", creator.key().as_ref(), old_vault.namespace_seed()],
+        bump = old_vault.bump,
+        has_one = creator @ PaymentError::Unauthorized,
+        constraint = old_vault.pending_creator == Some(new_creator.key()) @ PaymentError::NotPendingCreator,
+        close = creator
+    )]
+    pub old_vault: Account<'info, CreatorVault>,
+
+    /// CHECK: The vault's current creator, checked by has_one; only receives
+    /// the old vault's rent
+    #[account(mut)]
+    pub creator: UncheckedAccount<'info>,
+
+    // Fails if the new wallet already has a vault in this namespace
+    #[account(
+        init,
+        payer = new_creator,
+        space = 8 + CreatorVault::INIT_SPACE,
+        seeds = [b"vault", new_creator.key().as_ref(), old_vault.namespace_seed()],
+        bump
+    )]
+    pub new_vault: Account<'info, CreatorVault>,
+
+    #[account(mut)]
+    pub new_creator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Second step of moving a vault: the wallet it was offered to takes it over.
+/// Subscriptions are seeded by the creator's key too, so the vault must have
+/// no active subscribers left to strand.
+pub fn handler(ctx: Context<AcceptVault>) -> Result<()> {
+    let old_vault = &ctx.accounts.old_vault;
+    require!(old_vault.subscribers == 0, PaymentError::VaultHasSubscribers);
+
+    let new_creator = ctx.accounts.new_creator.key();
+    ctx.accounts
+        .new_vault
+        .set_inner(old_vault.handed_over(new_creator, ctx.bumps.new_vault));
+
+    // Everything above the old vault's rent is escrow; the rent goes back to
+    // the old wallet when the account closes
+    let old_vault_info = old_vault.to_account_info();
+    let escrow = old_vault_info
+        .lamports()
+        .saturating_sub(Rent::get()?.minimum_balance(old_vault_info.data_len()));
+    transfer_from_pda(&old_vault_info, &ctx.accounts.new_vault.to_account_info(), escrow)?;
+
+    emit!(VaultTransferred {
+        old_vault: old_vault.key(),
+        new_vault: ctx.accounts.new_vault.key(),
+        old_creator: old_vault.creator,
+        new_creator,
+        lamports: escrow,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
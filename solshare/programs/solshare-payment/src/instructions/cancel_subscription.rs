@@ -1,5 +1,5 @@
 use anchor_lang::prelude::*;
-use crate::state::{CreatorVault, Subscription};
+use crate::state::{CreatorVault, Subscription, SubscriberIndex};
 use crate::error::PaymentError;
 use crate::events::SubscriptionCancelled;
 
@@ -7,20 +7,28 @@ use crate::events::SubscriptionCancelled;
 pub struct CancelSubscription<'info> {
     #[account(
         mut,
-        seeds = [b"vault", creator_vault.creator.as_ref()],
+        seeds = [b"vault", creator_vault.creator.as_ref(), creator_vault.namespace_seed()],
         bump = creator_vault.bump
     )]
     pub creator_vault: Account<'info, CreatorVault>,
     
     #[account(
         mut,
-        seeds = [b"subscription", subscriber.key().as_ref(), creator_vault.creator.as_ref()],
+        seeds = [b"subscription", subscriber.key().as_ref(), creator_vault.creator.as_ref(), creator_vault.namespace_seed()],
         bump = subscription.bump,
         has_one = subscriber
     )]
     pub subscription: Account<'info, Subscription>,
     
     pub subscriber: Signer<'info>,
+
+    // Required when the subscription is listed: the page listing it
+    #[account(
+        mut,
+        seeds = [b"subscriber_index", creator_vault.key().as_ref(), &subscriber_index.page.to_le_bytes()],
+        bump = subscriber_index.bump
+    )]
+    pub subscriber_index: Option<Account<'info, SubscriberIndex>>,
 }
 
 pub fn handler(ctx: Context<CancelSubscription>) -> Result<()> {
@@ -29,9 +37,14 @@ pub fn handler(ctx: Context<CancelSubscription>) -> Result<()> {
     let clock = Clock::get()?;
 
     require!(subscription.is_active, PaymentError::SubscriptionNotActive);
+    require!(
+        subscription.commitment_met(clock.unix_timestamp),
+        PaymentError::MinimumDurationNotMet
+    );
 
-    subscription.is_active = false;
-    vault.subscribers = vault.subscribers.saturating_sub(1);
+    subscription.deactivate(clock.unix_timestamp);
+    vault.remove_subscriber()?;
+    unindex_subscriber(subscription, ctx.accounts.subscriber_index.as_mut())?;
 
     emit!(SubscriptionCancelled {
         subscriber: subscription.subscriber,
@@ -41,3 +54,18 @@ pub fn handler(ctx: Context<CancelSubscription>) -> Result<()> {
 
     Ok(())
 }
+
+/// Takes the subscriber off the roster page listing them, if any
+pub(crate) fn unindex_subscriber(
+    subscription: &mut Subscription,
+    subscriber_index: Option<&mut Account<SubscriberIndex>>,
+) -> Result<()> {
+    let Some(page) = subscription.index_page else {
+        return Ok(());
+    };
+    let index = subscriber_index.ok_or(PaymentError::SubscriberIndexRequired)?;
+    require!(index.page == page, PaymentError::SubscriberNotIndexed);
+    index.remove(&subscription.subscriber)?;
+    subscription.index_page = None;
+    Ok(())
+}
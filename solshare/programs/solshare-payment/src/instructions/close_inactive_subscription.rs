@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+use crate::state::{CreatorVault, Subscription, SubscriberIndex};
+use crate::error::PaymentError;
+use crate::events::SubscriptionClosed;
+use super::cancel_subscription::unindex_subscriber;
+
+#[derive(Accounts)]
+pub struct CloseInactiveSubscription<'info> {
+    #[account(
+        seeds = [b"vault", creator_vault.creator.as_ref(), creator_vault.namespace_seed()],
+        bump = creator_vault.bump
+    )]
+    pub creator_vault: Account<'info, CreatorVault>,
+
+    #[account(
+        mut,
+        close = subscriber,
+        seeds = [b"subscription", subscription.subscriber.as_ref(), creator_vault.creator.as_ref(), creator_vault.namespace_seed()],
+        bump = subscription.bump,
+        has_one = subscriber
+    )]
+    pub subscription: Account<'info, Subscription>,
+
+    /// CHECK: Receives the rent; must be the subscription's subscriber
+    #[account(mut)]
+    pub subscriber: UncheckedAccount<'info>,
+
+    // Either the subscriber or the creator may clean up
+    #[account(
+        constraint = closer.key() == subscription.subscriber
+            || closer.key() == creator_vault.creator @ PaymentError::Unauthorized
+    )]
+    pub closer: Signer<'info>,
+
+    // Required when the subscription is still listed, e.g. after it lapsed
+    #[account(
+        mut,
+        seeds = [b"subscriber_index", creator_vault.key().as_ref(), &subscriber_index.page.to_le_bytes()],
+        bump = subscriber_index.bump
+    )]
+    pub subscriber_index: Option<Account<'info, SubscriberIndex>>,
+}
+
+pub fn handler(ctx: Context<CloseInactiveSubscription>) -> Result<()> {
+    let subscription = &mut ctx.accounts.subscription;
+    let clock = Clock::get()?;
+
+    require!(!subscription.is_active, PaymentError::SubscriptionStillActive);
+    require!(
+        subscription.closable(clock.unix_timestamp),
+        PaymentError::SubscriptionRecentlyActive
+    );
+    unindex_subscriber(subscription, ctx.accounts.subscriber_index.as_mut())?;
+
+    emit!(SubscriptionClosed {
+        subscriber: subscription.subscriber,
+        creator: subscription.creator,
+        closed_by: ctx.accounts.closer.key(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+use crate::state::TipRecord;
+use crate::error::PaymentError;
+use crate::events::TipRecordClosed;
+
+#[derive(Accounts)]
+pub struct CloseTipRecord<'info> {
+    #[account(
+        mut,
+        close = tipper,
+        constraint = tip_record.from == tipper.key() @ PaymentError::Unauthorized
+    )]
+    pub tip_record: Account<'info, TipRecord>,
+
+    #[account(mut)]
+    pub tipper: Signer<'info>,
+}
+
+/// Returns the tip record's rent to the tipper; the tip itself is kept in the TipSent event
+pub fn handler(ctx: Context<CloseTipRecord>) -> Result<()> {
+    let clock = Clock::get()?;
+
+    emit!(TipRecordClosed {
+        tip_record: ctx.accounts.tip_record.key(),
+        from: ctx.accounts.tip_record.from,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
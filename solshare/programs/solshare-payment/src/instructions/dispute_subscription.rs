@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+use crate::state::{CreatorVault, Subscription};
+use crate::error::PaymentError;
+use crate::events::DisputeOpened;
+
+#[derive(Accounts)]
+pub struct DisputeSubscription<'info> {
+    #[account(
+        seeds = [b"vault", creator_vault.creator.as_ref(), creator_vault.namespace_seed()],
+        bump = creator_vault.bump
+    )]
+    pub creator_vault: Account<'info, CreatorVault>,
+
+    #[account(
+        mut,
+        seeds = [b"subscription", subscriber.key().as_ref(), creator_vault.creator.as_ref(), creator_vault.namespace_seed()],
+        bump = subscription.bump,
+        has_one = subscriber
+    )]
+    pub subscription: Account<'info, Subscription>,
+
+    pub subscriber: Signer<'info>,
+}
+
+/// Pauses renewals until the creator acknowledges the dispute
+pub fn handler(ctx: Context<DisputeSubscription>) -> Result<()> {
+    let subscription = &mut ctx.accounts.subscription;
+    let clock = Clock::get()?;
+
+    require!(subscription.is_active, PaymentError::SubscriptionNotActive);
+    require!(!subscription.disputed, PaymentError::SubscriptionDisputed);
+
+    subscription.disputed = true;
+
+    emit!(DisputeOpened {
+        subscriber: subscription.subscriber,
+        creator: subscription.creator,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
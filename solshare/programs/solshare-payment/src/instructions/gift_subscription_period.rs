@@ -0,0 +1,127 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use crate::state::{PlatformConfig, CreatorVault, Subscription, Treasury, PlatformStats};
+use crate::error::PaymentError;
+use crate::events::SubscriptionPeriodGifted;
+use super::process_subscription::SECONDS_PER_MONTH;
+
+#[derive(Accounts)]
+pub struct GiftSubscriptionPeriod<'info> {
+    #[account(
+        seeds = [b"platform_config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, PlatformConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", creator_vault.creator.as_ref(), creator_vault.namespace_seed()],
+        bump = creator_vault.bump
+    )]
+    pub creator_vault: Account<'info, CreatorVault>,
+
+    #[account(
+        mut,
+        seeds = [b"subscription", subscription.subscriber.as_ref(), creator_vault.creator.as_ref(), creator_vault.namespace_seed()],
+        bump = subscription.bump
+    )]
+    pub subscription: Account<'info, Subscription>,
+
+    // Anyone may pay, including the creator; the subscriber doesn't sign
+    #[account(mut)]
+    pub gifter: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    pub system_program: Program<'info, System>,
+
+    #[account(
+        mut,
+        seeds = [b"platform_stats"],
+        bump = platform_stats.bump
+    )]
+    pub platform_stats: Option<Account<'info, PlatformStats>>,
+}
+
+/// Pays one period of a SOL subscription on the subscriber's behalf, pushing
+/// their next payment back a month. Fees and vault credit work as for a
+/// renewal.
+pub fn handler(ctx: Context<GiftSubscriptionPeriod>) -> Result<()> {
+    let config = &ctx.accounts.config;
+    let clock = Clock::get()?;
+
+    require!(ctx.accounts.subscription.is_active, PaymentError::SubscriptionNotActive);
+    require!(
+        ctx.accounts.subscription.mint.is_none(),
+        PaymentError::SubscriptionMintMismatch
+    );
+
+    let amount = ctx.accounts.subscription.amount_per_month;
+    config.check_subscription_amount(amount)?;
+    config.check_stats_passed(&ctx.accounts.platform_stats)?;
+    let fee = config.platform_fee(amount)?;
+    let creator_amount = amount.checked_sub(fee).ok_or(PaymentError::ArithmeticOverflow)?;
+    require!(
+        ctx.accounts.gifter.lamports() >= amount,
+        PaymentError::InsufficientFunds
+    );
+
+    if fee > 0 {
+        transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.gifter.to_account_info(),
+                    to: ctx.accounts.treasury.to_account_info(),
+                },
+            ),
+            fee,
+        )?;
+
+        let treasury = &mut ctx.accounts.treasury;
+        treasury.total_collected = treasury.total_collected
+            .checked_add(fee)
+            .ok_or(PaymentError::ArithmeticOverflow)?;
+    }
+
+    transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.gifter.to_account_info(),
+                to: ctx.accounts.creator_vault.to_account_info(),
+            },
+        ),
+        creator_amount,
+    )?;
+
+    let vault = &mut ctx.accounts.creator_vault;
+    vault.total_earned = vault.total_earned
+        .checked_add(creator_amount)
+        .ok_or(PaymentError::ArithmeticOverflow)?;
+
+    if let Some(stats) = ctx.accounts.platform_stats.as_mut() {
+        stats.record_subscription_payment(amount)?;
+    }
+
+    let subscription = &mut ctx.accounts.subscription;
+    let payment_count = subscription.record_renewal(clock.unix_timestamp, SECONDS_PER_MONTH)?;
+
+    emit!(SubscriptionPeriodGifted {
+        subscriber: subscription.subscriber,
+        creator: subscription.creator,
+        gifter: ctx.accounts.gifter.key(),
+        amount,
+        fee,
+        payment_count,
+        last_payment: subscription.last_payment,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
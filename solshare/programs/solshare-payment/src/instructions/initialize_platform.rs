@@ -1,5 +1,5 @@
 use anchor_lang::prelude::*;
-use crate::state::PlatformConfig;
+use crate::state::{PlatformConfig, Treasury};
 use crate::error::PaymentError;
 use crate::events::PlatformConfigUpdated;
 
@@ -13,13 +13,24 @@ pub struct InitializePlatform<'info> {
         bump
     )]
     pub config: Account<'info, PlatformConfig>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Treasury::INIT_SPACE,
+        seeds = [b"treasury"],
+        bump
+    )]
+    pub treasury: Account<'info, Treasury>,
     
     #[account(mut)]
     pub authority: Signer<'info>,
     
-    /// CHECK: Fee recipient wallet address — intentionally unconstrained.
-    /// The platform authority (signer) chooses where fees are sent.
+    /// CHECK: Fee recipient wallet address chosen by the platform authority.
+    /// Must be system-owned (a plain wallet, funded or not) so swept fees can
+    /// always be spent; a program-owned account could strand them.
     /// This is set once during initialization and stored in PlatformConfig.
+    #[account(owner = System::id() @ PaymentError::InvalidFeeRecipient)]
     pub fee_recipient: AccountInfo<'info>,
     
     pub system_program: Program<'info, System>,
@@ -35,6 +46,16 @@ pub fn handler(ctx: Context<InitializePlatform>, fee_basis_points: u16) -> Resul
     config.fee_basis_points = fee_basis_points;
     config.fee_recipient = ctx.accounts.fee_recipient.key();
     config.bump = ctx.bumps.config;
+    config.max_amount_per_month = None;
+    config.free_withdrawal_threshold = None;
+    config.crank_reward_lamports = None;
+    config.round_fee_up = false;
+    config.track_stats = false;
+
+    let treasury = &mut ctx.accounts.treasury;
+    treasury.total_collected = 0;
+    treasury.total_swept = 0;
+    treasury.bump = ctx.bumps.treasury;
 
     emit!(PlatformConfigUpdated {
         authority: config.authority,
@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+use crate::state::{PlatformConfig, PlatformStats};
+use crate::error::PaymentError;
+
+#[derive(Accounts)]
+pub struct InitializePlatformStats<'info> {
+    #[account(
+        mut,
+        seeds = [b"platform_config"],
+        bump = config.bump,
+        has_one = authority @ PaymentError::Unauthorized
+    )]
+    pub config: Account<'info, PlatformConfig>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + PlatformStats::INIT_SPACE,
+        seeds = [b"platform_stats"],
+        bump
+    )]
+    pub platform_stats: Account<'info, PlatformStats>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<InitializePlatformStats>) -> Result<()> {
+    let stats = &mut ctx.accounts.platform_stats;
+    stats.total_tip_volume = 0;
+    stats.total_subscription_volume = 0;
+    stats.total_vaults = 0;
+    stats.bump = ctx.bumps.platform_stats;
+    ctx.accounts.config.track_stats = true;
+    Ok(())
+}
@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+use crate::state::{PlatformConfig, Treasury};
+use crate::error::PaymentError;
+
+// Platforms initialized before the treasury existed have no Treasury PDA, and
+// every tip and subscription needs one; this lets the authority create it
+#[derive(Accounts)]
+pub struct InitializeTreasury<'info> {
+    #[account(
+        seeds = [b"platform_config"],
+        bump = config.bump,
+        has_one = authority @ PaymentError::Unauthorized
+    )]
+    pub config: Account<'info, PlatformConfig>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Treasury::INIT_SPACE,
+        seeds = [b"treasury"],
+        bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<InitializeTreasury>) -> Result<()> {
+    let treasury = &mut ctx.accounts.treasury;
+    treasury.total_collected = 0;
+    treasury.total_swept = 0;
+    treasury.bump = ctx.bumps.treasury;
+    Ok(())
+}
@@ -1,14 +1,23 @@
 use anchor_lang::prelude::*;
-use crate::state::CreatorVault;
+use crate::state::{namespace_seed, CreatorVault, PlatformConfig, PlatformStats};
 use crate::events::VaultInitialized;
 
 #[derive(Accounts)]
+#[instruction(namespace: Option<[u8; 8]>)]
 pub struct InitializeVault<'info> {
     #[account(
-        init,
+        seeds = [b"platform_config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, PlatformConfig>,
+
+    // init_if_needed so clients can call this defensively; an existing vault
+    // is returned untouched by the handler
+    #[account(
+        init_if_needed,
         payer = creator,
         space = 8 + CreatorVault::INIT_SPACE,
-        seeds = [b"vault", creator.key().as_ref()],
+        seeds = [b"vault", creator.key().as_ref(), namespace_seed(&namespace)],
         bump
     )]
     pub vault: Account<'info, CreatorVault>,
@@ -17,22 +26,66 @@ pub struct InitializeVault<'info> {
     pub creator: Signer<'info>,
     
     pub system_program: Program<'info, System>,
+
+    #[account(
+        mut,
+        seeds = [b"platform_stats"],
+        bump = platform_stats.bump
+    )]
+    pub platform_stats: Option<Account<'info, PlatformStats>>,
 }
 
-pub fn handler(ctx: Context<InitializeVault>) -> Result<()> {
-    let vault = &mut ctx.accounts.vault;
-    let clock = Clock::get()?;
-    
-    vault.creator = ctx.accounts.creator.key();
+pub fn handler(ctx: Context<InitializeVault>, namespace: Option<[u8; 8]>) -> Result<()> {
+    let creator = ctx.accounts.creator.key();
+    init_vault(
+        &mut ctx.accounts.vault,
+        &ctx.accounts.config,
+        creator,
+        namespace,
+        ctx.bumps.vault,
+        ctx.accounts.platform_stats.as_mut(),
+    )
+}
+
+/// Sets up a freshly allocated vault for `creator`; an existing vault is left
+/// untouched. Shared with tip_and_init_vault, where the tipper pays the rent.
+pub(crate) fn init_vault(
+    vault: &mut Account<CreatorVault>,
+    config: &PlatformConfig,
+    creator: Pubkey,
+    namespace: Option<[u8; 8]>,
+    bump: u8,
+    platform_stats: Option<&mut Account<PlatformStats>>,
+) -> Result<()> {
+    // A freshly allocated vault is zeroed; anything else is already set up
+    if vault.creator != Pubkey::default() {
+        return Ok(());
+    }
+    config.check_stats_passed(&platform_stats)?;
+
+    vault.creator = creator;
     vault.total_earned = 0;
     vault.withdrawn = 0;
     vault.subscribers = 0;
-    vault.bump = ctx.bumps.vault;
+    vault.bump = bump;
+    vault.require_mint = None;
+    vault.last_rollup_day = 0;
+    vault.last_rollup_earned = 0;
+    vault.namespace = namespace;
+    vault.verified = false;
+    vault.first_supporter = None;
+    vault.subscriber_index_pages = 0;
+    vault.max_subscribers = 0;
+    vault.pending_creator = None;
+
+    if let Some(stats) = platform_stats {
+        stats.record_vault()?;
+    }
 
     emit!(VaultInitialized {
         creator: vault.creator,
         vault: vault.key(),
-        timestamp: clock.unix_timestamp,
+        timestamp: Clock::get()?.unix_timestamp,
     });
 
     Ok(())
@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+use crate::state::Subscription;
+use crate::error::PaymentError;
+use super::process_subscription::SECONDS_PER_MONTH;
+
+#[derive(Accounts)]
+#[instruction(subscriber: Pubkey, creator: Pubkey)]
+pub struct IsSubscriptionActive<'info> {
+    #[account(
+        constraint = subscription.subscriber == subscriber
+            && subscription.creator == creator @ PaymentError::SubscriptionMismatch
+    )]
+    pub subscription: Account<'info, Subscription>,
+}
+
+/// Whether `subscriber` currently holds a live subscription to `creator`, so
+/// other programs (token-gate's subscription gates) can ask over CPI instead
+/// of re-deriving the account and its rules
+pub fn handler(ctx: Context<IsSubscriptionActive>, _subscriber: Pubkey, _creator: Pubkey) -> Result<bool> {
+    let clock = Clock::get()?;
+    Ok(ctx
+        .accounts
+        .subscription
+        .is_current(clock.unix_timestamp, SECONDS_PER_MONTH))
+}
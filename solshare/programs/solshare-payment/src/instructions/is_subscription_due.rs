@@ -0,0 +1,17 @@
+use anchor_lang::prelude::*;
+use crate::state::Subscription;
+use super::process_subscription::SECONDS_PER_MONTH;
+
+#[derive(Accounts)]
+pub struct IsSubscriptionDue<'info> {
+    pub subscription: Account<'info, Subscription>,
+}
+
+/// Lets cranks skip subscriptions that aren't due without sending a transaction
+pub fn handler(ctx: Context<IsSubscriptionDue>) -> Result<bool> {
+    let clock = Clock::get()?;
+    Ok(ctx
+        .accounts
+        .subscription
+        .is_due(clock.unix_timestamp, SECONDS_PER_MONTH))
+}
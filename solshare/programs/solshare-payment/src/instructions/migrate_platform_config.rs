@@ -0,0 +1,59 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use crate::state::PlatformConfig;
+use crate::events::PlatformConfigMigrated;
+
+#[derive(Accounts)]
+pub struct MigratePlatformConfig<'info> {
+    /// CHECK: A config in an older layout can't be deserialized as
+    /// PlatformConfig; ownership, discriminator and authority are checked in the handler
+    #[account(mut, seeds = [b"platform_config"], bump)]
+    pub config: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Grows a config created under an older layout to its current size, with
+/// the authority paying the extra rent. A current config is left untouched.
+pub fn handler(ctx: Context<MigratePlatformConfig>) -> Result<()> {
+    let config_info = ctx.accounts.config.to_account_info();
+    require_keys_eq!(*config_info.owner, crate::ID, ErrorCode::AccountOwnedByWrongProgram);
+
+    let old_size = config_info.data_len();
+    let new_size = 8 + PlatformConfig::INIT_SPACE;
+    if old_size >= new_size {
+        return Ok(());
+    }
+
+    let config = PlatformConfig::upgrade_layout(&config_info.data.borrow())?;
+    require_keys_eq!(config.authority, ctx.accounts.authority.key(), ErrorCode::ConstraintHasOne);
+
+    let rent_needed = Rent::get()?
+        .minimum_balance(new_size)
+        .saturating_sub(config_info.lamports());
+    if rent_needed > 0 {
+        transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.authority.to_account_info(),
+                    to: config_info.clone(),
+                },
+            ),
+            rent_needed,
+        )?;
+    }
+
+    config_info.resize(new_size)?;
+    config.try_serialize(&mut &mut config_info.data.borrow_mut()[..])?;
+
+    emit!(PlatformConfigMigrated {
+        old_size: old_size as u32,
+        new_size: new_size as u32,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,66 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use crate::state::Subscription;
+use crate::events::SubscriptionMigrated;
+
+#[derive(Accounts)]
+pub struct MigrateSubscription<'info> {
+    /// CHECK: A subscription in an older layout can't be deserialized as
+    /// Subscription; ownership, discriminator and subscriber are checked in the handler
+    #[account(mut)]
+    pub subscription: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub subscriber: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Grows a subscription created under an older layout to its current size,
+/// with the subscriber paying the extra rent. Current subscriptions are left untouched.
+pub fn handler(ctx: Context<MigrateSubscription>) -> Result<()> {
+    let subscription_info = ctx.accounts.subscription.to_account_info();
+    require_keys_eq!(*subscription_info.owner, crate::ID, ErrorCode::AccountOwnedByWrongProgram);
+
+    let old_size = subscription_info.data_len();
+    let new_size = 8 + Subscription::INIT_SPACE;
+    if old_size >= new_size {
+        return Ok(());
+    }
+
+    let subscription = Subscription::upgrade_layout(&subscription_info.data.borrow())?;
+    require_keys_eq!(
+        subscription.subscriber,
+        ctx.accounts.subscriber.key(),
+        ErrorCode::ConstraintHasOne
+    );
+
+    let rent = Rent::get()?;
+    let rent_needed = rent
+        .minimum_balance(new_size)
+        .saturating_sub(rent.minimum_balance(old_size));
+    if rent_needed > 0 {
+        transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.subscriber.to_account_info(),
+                    to: subscription_info.clone(),
+                },
+            ),
+            rent_needed,
+        )?;
+    }
+
+    subscription_info.resize(new_size)?;
+    subscription.try_serialize(&mut &mut subscription_info.data.borrow_mut()[..])?;
+
+    emit!(SubscriptionMigrated {
+        subscription: subscription_info.key(),
+        subscriber: subscription.subscriber,
+        old_size: old_size as u32,
+        new_size: new_size as u32,
+    });
+
+    Ok(())
+}
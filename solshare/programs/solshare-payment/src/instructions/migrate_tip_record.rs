@@ -0,0 +1,61 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use crate::state::TipRecord;
+use crate::events::TipRecordMigrated;
+
+#[derive(Accounts)]
+pub struct MigrateTipRecord<'info> {
+    /// CHECK: A tip record in an older layout can't be deserialized as
+    /// TipRecord; ownership, discriminator and tipper are checked in the handler
+    #[account(mut)]
+    pub tip_record: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub tipper: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Grows a tip record created under an older layout to its current size, with
+/// the tipper paying the extra rent. Current tip records are left untouched.
+pub fn handler(ctx: Context<MigrateTipRecord>) -> Result<()> {
+    let tip_record_info = ctx.accounts.tip_record.to_account_info();
+    require_keys_eq!(*tip_record_info.owner, crate::ID, ErrorCode::AccountOwnedByWrongProgram);
+
+    let old_size = tip_record_info.data_len();
+    let new_size = 8 + TipRecord::INIT_SPACE;
+    if old_size >= new_size {
+        return Ok(());
+    }
+
+    let tip_record = TipRecord::upgrade_layout(&tip_record_info.data.borrow())?;
+    require_keys_eq!(tip_record.from, ctx.accounts.tipper.key(), ErrorCode::ConstraintHasOne);
+
+    let rent_needed = Rent::get()?
+        .minimum_balance(new_size)
+        .saturating_sub(tip_record_info.lamports());
+    if rent_needed > 0 {
+        transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.tipper.to_account_info(),
+                    to: tip_record_info.clone(),
+                },
+            ),
+            rent_needed,
+        )?;
+    }
+
+    tip_record_info.resize(new_size)?;
+    tip_record.try_serialize(&mut &mut tip_record_info.data.borrow_mut()[..])?;
+
+    emit!(TipRecordMigrated {
+        tip_record: tip_record_info.key(),
+        from: tip_record.from,
+        old_size: old_size as u32,
+        new_size: new_size as u32,
+    });
+
+    Ok(())
+}
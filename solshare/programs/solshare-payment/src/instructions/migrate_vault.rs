@@ -0,0 +1,65 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use crate::state::CreatorVault;
+use crate::events::VaultMigrated;
+
+#[derive(Accounts)]
+pub struct MigrateVault<'info> {
+    /// CHECK: A vault in an older layout can't be deserialized as
+    /// CreatorVault; ownership, discriminator and creator are checked in the
+    /// handler. Older vaults predate namespaces, so only the default seeds apply.
+    #[account(mut, seeds = [b"vault", creator.key().as_ref()], bump)]
+    pub vault: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Grows a vault created under an older layout to its current size, with the
+/// creator paying the extra rent. Current vaults are left untouched.
+pub fn handler(ctx: Context<MigrateVault>) -> Result<()> {
+    let vault_info = ctx.accounts.vault.to_account_info();
+    require_keys_eq!(*vault_info.owner, crate::ID, ErrorCode::AccountOwnedByWrongProgram);
+
+    let old_size = vault_info.data_len();
+    let new_size = 8 + CreatorVault::INIT_SPACE;
+    if old_size >= new_size {
+        return Ok(());
+    }
+
+    let vault = CreatorVault::upgrade_layout(&vault_info.data.borrow())?;
+    require_keys_eq!(vault.creator, ctx.accounts.creator.key(), ErrorCode::ConstraintHasOne);
+
+    // The balance above rent is escrowed earnings, so the creator covers the
+    // whole rent difference rather than letting the escrow absorb it
+    let rent = Rent::get()?;
+    let rent_needed = rent
+        .minimum_balance(new_size)
+        .saturating_sub(rent.minimum_balance(old_size));
+    if rent_needed > 0 {
+        transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.creator.to_account_info(),
+                    to: vault_info.clone(),
+                },
+            ),
+            rent_needed,
+        )?;
+    }
+
+    vault_info.resize(new_size)?;
+    vault.try_serialize(&mut &mut vault_info.data.borrow_mut()[..])?;
+
+    emit!(VaultMigrated {
+        vault: vault_info.key(),
+        creator: vault.creator,
+        old_size: old_size as u32,
+        new_size: new_size as u32,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,14 @@
+use anchor_lang::prelude::*;
+use crate::state::Subscription;
+use super::process_subscription::SECONDS_PER_MONTH;
+
+#[derive(Accounts)]
+pub struct NextPaymentDue<'info> {
+    pub subscription: Account<'info, Subscription>,
+}
+
+/// Unix timestamp of the subscription's next payment, so clients can show a
+/// renewal date without hardcoding the billing period
+pub fn handler(ctx: Context<NextPaymentDue>) -> Result<i64> {
+    ctx.accounts.subscription.next_payment_due(SECONDS_PER_MONTH)
+}
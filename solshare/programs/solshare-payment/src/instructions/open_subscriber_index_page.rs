@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+use crate::state::{CreatorVault, SubscriberIndex};
+use crate::error::PaymentError;
+
+#[derive(Accounts)]
+pub struct OpenSubscriberIndexPage<'info> {
+    // Opening page 0 turns the roster on, which only the creator may do;
+    // anyone can open later pages once the existing ones are full
+    #[account(
+        mut,
+        seeds = [b"vault", creator_vault.creator.as_ref(), creator_vault.namespace_seed()],
+        bump = creator_vault.bump,
+        constraint = creator_vault.subscriber_index_pages > 0
+            || payer.key() == creator_vault.creator @ PaymentError::Unauthorized
+    )]
+    pub creator_vault: Account<'info, CreatorVault>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + SubscriberIndex::INIT_SPACE,
+        seeds = [
+            b"subscriber_index",
+            creator_vault.key().as_ref(),
+            &creator_vault.subscriber_index_pages.to_le_bytes()
+        ],
+        bump
+    )]
+    pub subscriber_index: Account<'info, SubscriberIndex>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Opens the vault's next subscriber index page. Subscribers who joined before
+/// the roster was turned on aren't listed.
+pub fn handler(ctx: Context<OpenSubscriberIndexPage>) -> Result<()> {
+    let vault = &mut ctx.accounts.creator_vault;
+    let index = &mut ctx.accounts.subscriber_index;
+    index.vault = vault.key();
+    index.page = vault.subscriber_index_pages;
+    index.subscribers = Vec::new();
+    index.bump = ctx.bumps.subscriber_index;
+
+    vault.subscriber_index_pages = vault
+        .subscriber_index_pages
+        .checked_add(1)
+        .ok_or(PaymentError::ArithmeticOverflow)?;
+
+    Ok(())
+}
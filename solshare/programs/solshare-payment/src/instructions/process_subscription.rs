@@ -1,10 +1,10 @@
 use anchor_lang::prelude::*;
 use anchor_lang::system_program::{transfer, Transfer};
-use crate::state::{PlatformConfig, CreatorVault, Subscription};
+use crate::state::{PlatformConfig, CreatorVault, Subscription, Treasury, PlatformStats};
 use crate::error::PaymentError;
-use crate::events::SubscriptionProcessed;
+use crate::events::{SubscriptionLapsed, SubscriptionProcessed};
 
-const SECONDS_PER_MONTH: i64 = 30 * 24 * 60 * 60; // 30 days
+pub(crate) const SECONDS_PER_MONTH: i64 = 30 * 24 * 60 * 60; // 30 days
 
 #[derive(Accounts)]
 pub struct ProcessSubscription<'info> {
@@ -16,14 +16,14 @@ pub struct ProcessSubscription<'info> {
     
     #[account(
         mut,
-        seeds = [b"vault", creator_vault.creator.as_ref()],
+        seeds = [b"vault", creator_vault.creator.as_ref(), creator_vault.namespace_seed()],
         bump = creator_vault.bump
     )]
     pub creator_vault: Account<'info, CreatorVault>,
     
     #[account(
         mut,
-        seeds = [b"subscription", subscriber.key().as_ref(), creator_vault.creator.as_ref()],
+        seeds = [b"subscription", subscriber.key().as_ref(), creator_vault.creator.as_ref(), creator_vault.namespace_seed()],
         bump = subscription.bump,
         has_one = subscriber
     )]
@@ -32,42 +32,65 @@ pub struct ProcessSubscription<'info> {
     #[account(mut)]
     pub subscriber: Signer<'info>,
 
-    /// CHECK: Fee recipient — validated against platform config
-    #[account(mut, address = config.fee_recipient)]
-    pub fee_recipient: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, Treasury>,
 
     pub system_program: Program<'info, System>,
+
+    #[account(
+        mut,
+        seeds = [b"platform_stats"],
+        bump = platform_stats.bump
+    )]
+    pub platform_stats: Option<Account<'info, PlatformStats>>,
+
+    // Third party submitting the renewal; paid config.crank_reward_lamports
+    // by the subscriber when set
+    #[account(mut)]
+    pub crank: Option<Signer<'info>>,
 }
 
 pub fn handler(ctx: Context<ProcessSubscription>) -> Result<()> {
     let config = &ctx.accounts.config;
     let clock = Clock::get()?;
 
-    require!(ctx.accounts.subscription.is_active, PaymentError::SubscriptionNotActive);
-
-    let time_since_last = clock.unix_timestamp - ctx.accounts.subscription.last_payment;
-    require!(time_since_last >= SECONDS_PER_MONTH, PaymentError::PaymentNotDue);
+    if !prepare_renewal(
+        &mut ctx.accounts.subscription,
+        &mut ctx.accounts.creator_vault,
+        clock.unix_timestamp,
+    )? {
+        return Ok(());
+    }
 
-    let amount = ctx.accounts.subscription.amount_per_month;
-    let fee = amount
-        .checked_mul(config.fee_basis_points as u64)
-        .ok_or(PaymentError::ArithmeticOverflow)?
-        .checked_div(10000)
-        .ok_or(PaymentError::ArithmeticOverflow)?;
+    config.check_stats_passed(&ctx.accounts.platform_stats)?;
+    let charge = RenewalCharge::new(
+        config,
+        ctx.accounts.subscription.amount_per_month,
+        ctx.accounts.crank.is_some(),
+    )?;
 
-    let creator_amount = amount.checked_sub(fee).ok_or(PaymentError::ArithmeticOverflow)?;
+    // Check the payment and the crank reward together so a renewal never
+    // fails halfway through for lack of funds
+    require!(
+        ctx.accounts.subscriber.lamports() >= charge.cost,
+        PaymentError::InsufficientFunds
+    );
 
     // Transfer fee
-    if fee > 0 {
+    if charge.fee > 0 {
         transfer(
             CpiContext::new(
                 ctx.accounts.system_program.to_account_info(),
                 Transfer {
                     from: ctx.accounts.subscriber.to_account_info(),
-                    to: ctx.accounts.fee_recipient.to_account_info(),
+                    to: ctx.accounts.treasury.to_account_info(),
                 },
             ),
-            fee,
+            charge.fee,
         )?;
     }
 
@@ -80,24 +103,135 @@ pub fn handler(ctx: Context<ProcessSubscription>) -> Result<()> {
                 to: ctx.accounts.creator_vault.to_account_info(),
             },
         ),
-        creator_amount,
+        charge.creator_amount,
     )?;
 
-    // Update vault stats after transfers are complete
-    let vault = &mut ctx.accounts.creator_vault;
+    if let Some(crank) = ctx.accounts.crank.as_ref().filter(|_| charge.crank_reward > 0) {
+        transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.subscriber.to_account_info(),
+                    to: crank.to_account_info(),
+                },
+            ),
+            charge.crank_reward,
+        )?;
+    }
+
+    record_renewal(
+        &charge,
+        &mut ctx.accounts.treasury,
+        &mut ctx.accounts.creator_vault,
+        &mut ctx.accounts.platform_stats,
+        &mut ctx.accounts.subscription,
+        clock.unix_timestamp,
+    )
+}
+
+/// How one SOL renewal is split between the treasury, the creator's vault and
+/// the crank
+pub(crate) struct RenewalCharge {
+    pub amount: u64,
+    pub fee: u64,
+    pub creator_amount: u64,
+    pub crank_reward: u64,
+    // Everything taken from the payer: amount plus crank_reward
+    pub cost: u64,
+}
+
+impl RenewalCharge {
+    pub(crate) fn new(config: &PlatformConfig, amount: u64, cranked: bool) -> Result<Self> {
+        config.check_subscription_amount(amount)?;
+        let fee = config.platform_fee(amount)?;
+        let creator_amount = amount.checked_sub(fee).ok_or(PaymentError::ArithmeticOverflow)?;
+        let cost = config.renewal_cost(amount, cranked)?;
+        Ok(Self {
+            amount,
+            fee,
+            creator_amount,
+            crank_reward: cost - amount,
+            cost,
+        })
+    }
+}
+
+/// Checks a SOL subscription can be renewed at `now`. Returns false once a
+/// subscription without auto-renew has been lapsed instead, with nothing to charge.
+pub(crate) fn prepare_renewal(
+    subscription: &mut Subscription,
+    vault: &mut CreatorVault,
+    now: i64,
+) -> Result<bool> {
+    require!(subscription.is_active, PaymentError::SubscriptionNotActive);
+    require!(subscription.mint.is_none(), PaymentError::SubscriptionMintMismatch);
+
+    require!(!subscription.disputed, PaymentError::SubscriptionDisputed);
+    require!(
+        subscription.period_ended(now, SECONDS_PER_MONTH),
+        PaymentError::PaymentNotDue
+    );
+
+    if !subscription.auto_renew {
+        lapse_subscription(subscription, vault, now)?;
+        return Ok(false);
+    }
+    Ok(true)
+}
+
+/// Books a renewal whose lamports have already moved and emits SubscriptionProcessed
+pub(crate) fn record_renewal(
+    charge: &RenewalCharge,
+    treasury: &mut Treasury,
+    vault: &mut CreatorVault,
+    platform_stats: &mut Option<Account<PlatformStats>>,
+    subscription: &mut Subscription,
+    now: i64,
+) -> Result<()> {
+    treasury.total_collected = treasury.total_collected
+        .checked_add(charge.fee)
+        .ok_or(PaymentError::ArithmeticOverflow)?;
+
     vault.total_earned = vault.total_earned
-        .checked_add(creator_amount)
+        .checked_add(charge.creator_amount)
         .ok_or(PaymentError::ArithmeticOverflow)?;
 
-    let subscription = &mut ctx.accounts.subscription;
-    subscription.last_payment = clock.unix_timestamp;
+    if let Some(stats) = platform_stats.as_mut() {
+        stats.record_subscription_payment(charge.amount)?;
+    }
+
+    let payment_count = subscription.record_renewal(now, SECONDS_PER_MONTH)?;
 
     emit!(SubscriptionProcessed {
         subscriber: subscription.subscriber,
         creator: subscription.creator,
-        amount,
-        fee,
-        timestamp: clock.unix_timestamp,
+        amount: charge.amount,
+        fee: charge.fee,
+        payment_count,
+        mint: None,
+        reference: subscription.reference,
+        crank_reward: charge.crank_reward,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+/// Ends a subscription whose subscriber turned off auto-renew, once the period
+/// they paid for is over. Nothing is charged; the account stays open.
+pub(crate) fn lapse_subscription(
+    subscription: &mut Subscription,
+    vault: &mut CreatorVault,
+    now: i64,
+) -> Result<()> {
+    subscription.deactivate(now);
+    vault.remove_subscriber()?;
+
+    emit!(SubscriptionLapsed {
+        subscriber: subscription.subscriber,
+        creator: subscription.creator,
+        payment_count: subscription.payment_count,
+        timestamp: now,
     });
 
     Ok(())
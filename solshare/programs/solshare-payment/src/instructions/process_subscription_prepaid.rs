@@ -0,0 +1,96 @@
+use anchor_lang::prelude::*;
+use crate::state::{PlatformConfig, CreatorVault, Subscription, Treasury, PlatformStats};
+use crate::error::PaymentError;
+use crate::utils::transfer_from_pda;
+use super::process_subscription::{prepare_renewal, record_renewal, RenewalCharge};
+
+// Renewal submitted by a crank without the subscriber's signature. It's paid
+// from lamports the subscriber has sent to the subscription account on top of
+// its rent, which come back to them when the subscription is closed.
+#[derive(Accounts)]
+pub struct ProcessSubscriptionPrepaid<'info> {
+    #[account(
+        seeds = [b"platform_config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, PlatformConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", creator_vault.creator.as_ref(), creator_vault.namespace_seed()],
+        bump = creator_vault.bump
+    )]
+    pub creator_vault: Account<'info, CreatorVault>,
+
+    #[account(
+        mut,
+        seeds = [b"subscription", subscriber.key().as_ref(), creator_vault.creator.as_ref(), creator_vault.namespace_seed()],
+        bump = subscription.bump,
+        has_one = subscriber
+    )]
+    pub subscription: Account<'info, Subscription>,
+
+    /// CHECK: Only locates the subscription; nothing is charged to it
+    pub subscriber: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    #[account(
+        mut,
+        seeds = [b"platform_stats"],
+        bump = platform_stats.bump
+    )]
+    pub platform_stats: Option<Account<'info, PlatformStats>>,
+
+    // Paid config.crank_reward_lamports out of the prepaid balance when set
+    #[account(mut)]
+    pub crank: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<ProcessSubscriptionPrepaid>) -> Result<()> {
+    let config = &ctx.accounts.config;
+    let clock = Clock::get()?;
+
+    if !prepare_renewal(
+        &mut ctx.accounts.subscription,
+        &mut ctx.accounts.creator_vault,
+        clock.unix_timestamp,
+    )? {
+        return Ok(());
+    }
+
+    config.check_stats_passed(&ctx.accounts.platform_stats)?;
+    let charge = RenewalCharge::new(config, ctx.accounts.subscription.amount_per_month, true)?;
+
+    let subscription_info = ctx.accounts.subscription.to_account_info();
+    let prepaid = subscription_info
+        .lamports()
+        .saturating_sub(Rent::get()?.minimum_balance(subscription_info.data_len()));
+    require!(prepaid >= charge.cost, PaymentError::InsufficientFunds);
+
+    if charge.fee > 0 {
+        transfer_from_pda(&subscription_info, &ctx.accounts.treasury.to_account_info(), charge.fee)?;
+    }
+    transfer_from_pda(
+        &subscription_info,
+        &ctx.accounts.creator_vault.to_account_info(),
+        charge.creator_amount,
+    )?;
+    if charge.crank_reward > 0 {
+        transfer_from_pda(&subscription_info, &ctx.accounts.crank.to_account_info(), charge.crank_reward)?;
+    }
+
+    record_renewal(
+        &charge,
+        &mut ctx.accounts.treasury,
+        &mut ctx.accounts.creator_vault,
+        &mut ctx.accounts.platform_stats,
+        &mut ctx.accounts.subscription,
+        clock.unix_timestamp,
+    )
+}
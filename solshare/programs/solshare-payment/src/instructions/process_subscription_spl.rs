@@ -0,0 +1,119 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::{PlatformConfig, CreatorVault, Subscription};
+use crate::error::PaymentError;
+use crate::events::SubscriptionProcessed;
+use super::process_subscription::{lapse_subscription, SECONDS_PER_MONTH};
+use super::subscribe_spl::transfer_spl_payment;
+
+#[derive(Accounts)]
+pub struct ProcessSubscriptionSpl<'info> {
+    #[account(
+        seeds = [b"platform_config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, PlatformConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", creator_vault.creator.as_ref(), creator_vault.namespace_seed()],
+        bump = creator_vault.bump
+    )]
+    pub creator_vault: Account<'info, CreatorVault>,
+
+    #[account(
+        mut,
+        seeds = [b"subscription", subscriber.key().as_ref(), creator_vault.creator.as_ref(), creator_vault.namespace_seed()],
+        bump = subscription.bump,
+        has_one = subscriber,
+        constraint = subscription.mint == Some(mint.key()) @ PaymentError::SubscriptionMintMismatch
+    )]
+    pub subscription: Account<'info, Subscription>,
+
+    pub subscriber: Signer<'info>,
+
+    #[account(
+        constraint = creator_vault.accepts_mint(&mint.key()) @ PaymentError::UnacceptedMint
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = subscriber,
+        token::token_program = token_program
+    )]
+    pub subscriber_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = creator_vault.creator,
+        token::token_program = token_program
+    )]
+    pub creator_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = config.fee_recipient,
+        token::token_program = token_program
+    )]
+    pub fee_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn handler(ctx: Context<ProcessSubscriptionSpl>) -> Result<()> {
+    let config = &ctx.accounts.config;
+    let clock = Clock::get()?;
+
+    require!(ctx.accounts.subscription.is_active, PaymentError::SubscriptionNotActive);
+
+    require!(!ctx.accounts.subscription.disputed, PaymentError::SubscriptionDisputed);
+    require!(
+        ctx.accounts.subscription.period_ended(clock.unix_timestamp, SECONDS_PER_MONTH),
+        PaymentError::PaymentNotDue
+    );
+
+    if !ctx.accounts.subscription.auto_renew {
+        lapse_subscription(
+            &mut ctx.accounts.subscription,
+            &mut ctx.accounts.creator_vault,
+            clock.unix_timestamp,
+        )?;
+        return Ok(());
+    }
+
+    let amount = ctx.accounts.subscription.amount_per_month;
+    config.check_subscription_amount(amount)?;
+    let fee = config.platform_fee(amount)?;
+
+    let creator_amount = amount.checked_sub(fee).ok_or(PaymentError::ArithmeticOverflow)?;
+
+    transfer_spl_payment(
+        &ctx.accounts.token_program,
+        &ctx.accounts.mint,
+        &ctx.accounts.subscriber_token_account,
+        &ctx.accounts.subscriber,
+        (&ctx.accounts.creator_token_account, creator_amount),
+        (&ctx.accounts.fee_token_account, fee),
+    )?;
+
+    let subscription = &mut ctx.accounts.subscription;
+    let payment_count = subscription.record_renewal(clock.unix_timestamp, SECONDS_PER_MONTH)?;
+
+    emit!(SubscriptionProcessed {
+        subscriber: subscription.subscriber,
+        creator: subscription.creator,
+        amount,
+        fee,
+        payment_count,
+        mint: subscription.mint,
+        reference: subscription.reference,
+        crank_reward: 0,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
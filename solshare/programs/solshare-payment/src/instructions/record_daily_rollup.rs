@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+use crate::state::{CreatorVault, SECONDS_PER_DAY};
+use crate::error::PaymentError;
+use crate::events::DailyEarnings;
+
+// Permissionless: anyone (typically an indexer's crank) may record the rollup
+#[derive(Accounts)]
+pub struct RecordDailyRollup<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", vault.creator.as_ref(), vault.namespace_seed()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, CreatorVault>,
+}
+
+pub fn handler(ctx: Context<RecordDailyRollup>) -> Result<()> {
+    let vault = &mut ctx.accounts.vault;
+    let clock = Clock::get()?;
+
+    let day_index = clock.unix_timestamp / SECONDS_PER_DAY;
+    require!(day_index > vault.last_rollup_day, PaymentError::RollupAlreadyRecorded);
+
+    let earned_since_last = vault.total_earned
+        .checked_sub(vault.last_rollup_earned)
+        .ok_or(PaymentError::ArithmeticOverflow)?;
+
+    vault.last_rollup_day = day_index;
+    vault.last_rollup_earned = vault.total_earned;
+
+    emit!(DailyEarnings {
+        creator: vault.creator,
+        day_index,
+        earned_since_last,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+use crate::state::{CreatorVault, Subscription};
+use crate::error::PaymentError;
+use crate::events::DisputeResolved;
+
+#[derive(Accounts)]
+pub struct ResolveDispute<'info> {
+    #[account(
+        seeds = [b"vault", creator_vault.creator.as_ref(), creator_vault.namespace_seed()],
+        bump = creator_vault.bump,
+        has_one = creator @ PaymentError::Unauthorized
+    )]
+    pub creator_vault: Account<'info, CreatorVault>,
+
+    #[account(
+        mut,
+        seeds = [b"subscription", subscription.subscriber.as_ref(), creator_vault.creator.as_ref(), creator_vault.namespace_seed()],
+        bump = subscription.bump
+    )]
+    pub subscription: Account<'info, Subscription>,
+
+    pub creator: Signer<'info>,
+}
+
+/// The creator acknowledges the dispute, letting renewals resume
+pub fn handler(ctx: Context<ResolveDispute>) -> Result<()> {
+    let subscription = &mut ctx.accounts.subscription;
+    let clock = Clock::get()?;
+
+    require!(subscription.disputed, PaymentError::SubscriptionNotDisputed);
+
+    subscription.disputed = false;
+
+    emit!(DisputeResolved {
+        subscriber: subscription.subscriber,
+        creator: subscription.creator,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+use crate::state::{CreatorVault, Subscription};
+use crate::error::PaymentError;
+use crate::events::AutoRenewUpdated;
+
+#[derive(Accounts)]
+pub struct SetAutoRenew<'info> {
+    #[account(
+        seeds = [b"vault", creator_vault.creator.as_ref(), creator_vault.namespace_seed()],
+        bump = creator_vault.bump
+    )]
+    pub creator_vault: Account<'info, CreatorVault>,
+
+    #[account(
+        mut,
+        seeds = [b"subscription", subscriber.key().as_ref(), creator_vault.creator.as_ref(), creator_vault.namespace_seed()],
+        bump = subscription.bump,
+        has_one = subscriber
+    )]
+    pub subscription: Account<'info, Subscription>,
+
+    pub subscriber: Signer<'info>,
+}
+
+/// Turning auto-renew off cancels at period end: access continues until the
+/// paid period runs out, then the next process call deactivates the subscription
+pub fn handler(ctx: Context<SetAutoRenew>, auto_renew: bool) -> Result<()> {
+    let subscription = &mut ctx.accounts.subscription;
+    require!(subscription.is_active, PaymentError::SubscriptionNotActive);
+    // Otherwise a committed subscriber could let the subscription lapse early
+    if !auto_renew {
+        require!(
+            subscription.commitment_met(Clock::get()?.unix_timestamp),
+            PaymentError::MinimumDurationNotMet
+        );
+    }
+
+    subscription.auto_renew = auto_renew;
+
+    emit!(AutoRenewUpdated {
+        subscriber: subscription.subscriber,
+        creator: subscription.creator,
+        auto_renew,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,22 @@
+use anchor_lang::prelude::*;
+use crate::state::PlatformConfig;
+use crate::error::PaymentError;
+
+#[derive(Accounts)]
+pub struct SetFeeRounding<'info> {
+    #[account(
+        mut,
+        seeds = [b"platform_config"],
+        bump = config.bump,
+        has_one = authority @ PaymentError::Unauthorized
+    )]
+    pub config: Account<'info, PlatformConfig>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SetFeeRounding>, round_fee_up: bool) -> Result<()> {
+    ctx.accounts.config.round_fee_up = round_fee_up;
+
+    Ok(())
+}
@@ -0,0 +1,22 @@
+use anchor_lang::prelude::*;
+use crate::state::PlatformConfig;
+use crate::error::PaymentError;
+
+#[derive(Accounts)]
+pub struct SetFreeWithdrawalThreshold<'info> {
+    #[account(
+        mut,
+        seeds = [b"platform_config"],
+        bump = config.bump,
+        has_one = authority @ PaymentError::Unauthorized
+    )]
+    pub config: Account<'info, PlatformConfig>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SetFreeWithdrawalThreshold>, free_withdrawal_threshold: Option<u64>) -> Result<()> {
+    ctx.accounts.config.free_withdrawal_threshold = free_withdrawal_threshold;
+
+    Ok(())
+}
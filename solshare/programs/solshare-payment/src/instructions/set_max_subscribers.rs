@@ -0,0 +1,23 @@
+use anchor_lang::prelude::*;
+use crate::state::CreatorVault;
+use crate::error::PaymentError;
+
+#[derive(Accounts)]
+pub struct SetMaxSubscribers<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", creator.key().as_ref(), vault.namespace_seed()],
+        bump = vault.bump,
+        has_one = creator @ PaymentError::Unauthorized
+    )]
+    pub vault: Account<'info, CreatorVault>,
+
+    pub creator: Signer<'info>,
+}
+
+/// Caps how many subscribers the vault accepts; 0 lifts the cap. Lowering it
+/// below the current count only turns away new subscribers.
+pub fn handler(ctx: Context<SetMaxSubscribers>, max_subscribers: u64) -> Result<()> {
+    ctx.accounts.vault.max_subscribers = max_subscribers;
+    Ok(())
+}
@@ -0,0 +1,26 @@
+use anchor_lang::prelude::*;
+use crate::state::PlatformConfig;
+use crate::error::PaymentError;
+
+#[derive(Accounts)]
+pub struct SetMaxSubscriptionAmount<'info> {
+    #[account(
+        mut,
+        seeds = [b"platform_config"],
+        bump = config.bump,
+        has_one = authority @ PaymentError::Unauthorized
+    )]
+    pub config: Account<'info, PlatformConfig>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SetMaxSubscriptionAmount>, max_amount_per_month: Option<u64>) -> Result<()> {
+    if let Some(max_amount_per_month) = max_amount_per_month {
+        require!(max_amount_per_month > 0, PaymentError::InvalidAmount);
+    }
+
+    ctx.accounts.config.max_amount_per_month = max_amount_per_month;
+
+    Ok(())
+}
@@ -0,0 +1,21 @@
+use anchor_lang::prelude::*;
+use crate::state::CreatorVault;
+use crate::error::PaymentError;
+
+#[derive(Accounts)]
+pub struct SetRequiredMint<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", creator.key().as_ref(), vault.namespace_seed()],
+        bump = vault.bump,
+        has_one = creator @ PaymentError::Unauthorized
+    )]
+    pub vault: Account<'info, CreatorVault>,
+
+    pub creator: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SetRequiredMint>, require_mint: Option<Pubkey>) -> Result<()> {
+    ctx.accounts.vault.require_mint = require_mint;
+    Ok(())
+}
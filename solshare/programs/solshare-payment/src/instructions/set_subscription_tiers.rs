@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+use crate::state::{
+    CreatorVault, SubscriptionTier, SubscriptionTiers, MAX_SUBSCRIPTION_TIERS, MAX_TIER_NAME_LEN,
+};
+use crate::error::PaymentError;
+use crate::events::SubscriptionTiersUpdated;
+
+#[derive(Accounts)]
+pub struct SetSubscriptionTiers<'info> {
+    #[account(
+        seeds = [b"vault", creator.key().as_ref(), vault.namespace_seed()],
+        bump = vault.bump,
+        has_one = creator @ PaymentError::Unauthorized
+    )]
+    pub vault: Account<'info, CreatorVault>,
+
+    #[account(
+        init_if_needed,
+        payer = creator,
+        space = 8 + SubscriptionTiers::INIT_SPACE,
+        seeds = [b"tiers", vault.key().as_ref()],
+        bump
+    )]
+    pub subscription_tiers: Account<'info, SubscriptionTiers>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Replaces the vault's tier catalog. Existing subscriptions keep the amount
+/// they signed up at; new prices only apply to new subscribers.
+pub fn handler(ctx: Context<SetSubscriptionTiers>, tiers: Vec<SubscriptionTier>) -> Result<()> {
+    require!(tiers.len() <= MAX_SUBSCRIPTION_TIERS, PaymentError::TooManyTiers);
+    for tier in &tiers {
+        require!(tier.amount_per_month > 0, PaymentError::InvalidAmount);
+        require!(tier.name.len() <= MAX_TIER_NAME_LEN, PaymentError::TierNameTooLong);
+    }
+
+    let catalog = &mut ctx.accounts.subscription_tiers;
+    catalog.vault = ctx.accounts.vault.key();
+    catalog.tiers = tiers;
+    catalog.bump = ctx.bumps.subscription_tiers;
+
+    emit!(SubscriptionTiersUpdated {
+        creator: ctx.accounts.creator.key(),
+        vault: catalog.vault,
+        tier_count: catalog.tiers.len() as u8,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+use crate::state::{CreatorVault, PlatformConfig};
+use crate::error::PaymentError;
+use crate::events::VaultVerificationChanged;
+
+#[derive(Accounts)]
+pub struct SetVaultVerified<'info> {
+    #[account(
+        seeds = [b"platform_config"],
+        bump = config.bump,
+        has_one = authority @ PaymentError::Unauthorized
+    )]
+    pub config: Account<'info, PlatformConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", vault.creator.as_ref(), vault.namespace_seed()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, CreatorVault>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SetVaultVerified>, verified: bool) -> Result<()> {
+    let vault = &mut ctx.accounts.vault;
+    vault.verified = verified;
+
+    emit!(VaultVerificationChanged {
+        vault: vault.key(),
+        creator: vault.creator,
+        verified,
+        authority: ctx.accounts.authority.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -1,6 +1,10 @@
 use anchor_lang::prelude::*;
 use anchor_lang::system_program::{transfer, Transfer};
-use crate::state::{PlatformConfig, CreatorVault, Subscription};
+use solshare_pda::{create_pda_account, rent_shortfall};
+use crate::state::{
+    PlatformConfig, CreatorVault, Subscription, Treasury, PlatformStats, SubscriptionTiers,
+    SubscriberIndex,
+};
 use crate::error::PaymentError;
 use crate::events::SubscriptionCreated;
 
@@ -12,47 +16,90 @@ pub struct Subscribe<'info> {
     )]
     pub config: Account<'info, PlatformConfig>,
     
+    // Derives the canonical bump rather than trusting the stored one, which
+    // the handler then checks against it
     #[account(
         mut,
-        seeds = [b"vault", creator_vault.creator.as_ref()],
-        bump = creator_vault.bump
+        seeds = [b"vault", creator_vault.creator.as_ref(), creator_vault.namespace_seed()],
+        bump
     )]
     pub creator_vault: Account<'info, CreatorVault>,
     
+    /// CHECK: Created in the handler once the subscriber is known to afford
+    /// both the first payment and the rent
     #[account(
-        init,
-        payer = subscriber,
-        space = 8 + Subscription::INIT_SPACE,
-        seeds = [b"subscription", subscriber.key().as_ref(), creator_vault.creator.as_ref()],
+        mut,
+        seeds = [b"subscription", subscriber.key().as_ref(), creator_vault.creator.as_ref(), creator_vault.namespace_seed()],
         bump
     )]
-    pub subscription: Account<'info, Subscription>,
+    pub subscription: UncheckedAccount<'info>,
     
     #[account(mut)]
     pub subscriber: Signer<'info>,
 
-    /// CHECK: Fee recipient — validated against platform config
-    #[account(mut, address = config.fee_recipient)]
-    pub fee_recipient: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, Treasury>,
 
     pub system_program: Program<'info, System>,
+
+    #[account(
+        mut,
+        seeds = [b"platform_stats"],
+        bump = platform_stats.bump
+    )]
+    pub platform_stats: Option<Account<'info, PlatformStats>>,
+
+    // Required when subscribing by tier_index
+    #[account(
+        seeds = [b"tiers", creator_vault.key().as_ref()],
+        bump = subscription_tiers.bump
+    )]
+    pub subscription_tiers: Option<Account<'info, SubscriptionTiers>>,
+
+    // Required once the vault keeps a subscriber index: a page with room
+    #[account(
+        mut,
+        seeds = [b"subscriber_index", creator_vault.key().as_ref(), &subscriber_index.page.to_le_bytes()],
+        bump = subscriber_index.bump
+    )]
+    pub subscriber_index: Option<Account<'info, SubscriberIndex>>,
 }
 
-pub fn handler(ctx: Context<Subscribe>, amount_per_month: u64) -> Result<()> {
+pub fn handler(
+    ctx: Context<Subscribe>,
+    amount_per_month: u64,
+    reference: Option<[u8; 16]>,
+    tier_index: Option<u8>,
+    min_duration_seconds: Option<i64>,
+) -> Result<()> {
+    ctx.accounts.creator_vault.check_bump(ctx.bumps.creator_vault)?;
     require!(amount_per_month > 0, PaymentError::InvalidAmount);
+    require!(
+        min_duration_seconds.is_none_or(|min_duration| min_duration > 0),
+        PaymentError::InvalidMinDuration
+    );
+    check_tier_amount(
+        ctx.accounts.subscription_tiers.as_ref(),
+        tier_index,
+        amount_per_month,
+    )?;
     require!(
         ctx.accounts.subscriber.key() != ctx.accounts.creator_vault.creator,
         PaymentError::CannotSubscribeToSelf
     );
 
     let config = &ctx.accounts.config;
+    config.check_subscription_amount(amount_per_month)?;
+    config.check_stats_passed(&ctx.accounts.platform_stats)?;
     let clock = Clock::get()?;
 
-    let fee = amount_per_month
-        .checked_mul(config.fee_basis_points as u64)
-        .ok_or(PaymentError::ArithmeticOverflow)?
-        .checked_div(10000)
-        .ok_or(PaymentError::ArithmeticOverflow)?;
+    create_subscription_account(&ctx, amount_per_month)?;
+
+    let fee = config.platform_fee(amount_per_month)?;
 
     let creator_amount = amount_per_month.checked_sub(fee).ok_or(PaymentError::ArithmeticOverflow)?;
 
@@ -63,11 +110,16 @@ pub fn handler(ctx: Context<Subscribe>, amount_per_month: u64) -> Result<()> {
                 ctx.accounts.system_program.to_account_info(),
                 Transfer {
                     from: ctx.accounts.subscriber.to_account_info(),
-                    to: ctx.accounts.fee_recipient.to_account_info(),
+                    to: ctx.accounts.treasury.to_account_info(),
                 },
             ),
             fee,
         )?;
+
+        let treasury = &mut ctx.accounts.treasury;
+        treasury.total_collected = treasury.total_collected
+            .checked_add(fee)
+            .ok_or(PaymentError::ArithmeticOverflow)?;
     }
 
     // Transfer first month's payment to creator vault (escrow)
@@ -87,23 +139,114 @@ pub fn handler(ctx: Context<Subscribe>, amount_per_month: u64) -> Result<()> {
     vault.total_earned = vault.total_earned
         .checked_add(creator_amount)
         .ok_or(PaymentError::ArithmeticOverflow)?;
-    vault.subscribers = vault.subscribers.checked_add(1).ok_or(PaymentError::ArithmeticOverflow)?;
+    vault.add_subscriber()?;
+
+    if let Some(stats) = ctx.accounts.platform_stats.as_mut() {
+        stats.record_subscription_payment(amount_per_month)?;
+    }
 
-    let subscription = &mut ctx.accounts.subscription;
-    subscription.subscriber = ctx.accounts.subscriber.key();
-    subscription.creator = vault.creator;
-    subscription.amount_per_month = amount_per_month;
-    subscription.last_payment = clock.unix_timestamp;
-    subscription.started_at = clock.unix_timestamp;
-    subscription.is_active = true;
-    subscription.bump = ctx.bumps.subscription;
+    let index_page = index_subscriber(
+        vault,
+        ctx.accounts.subscriber_index.as_mut(),
+        ctx.accounts.subscriber.key(),
+    )?;
+
+    let mut subscription = Subscription {
+        subscriber: ctx.accounts.subscriber.key(),
+        creator: vault.creator,
+        amount_per_month,
+        last_payment: 0,
+        started_at: clock.unix_timestamp,
+        is_active: true,
+        bump: ctx.bumps.subscription,
+        payment_count: 0,
+        mint: None,
+        auto_renew: true,
+        disputed: false,
+        reference,
+        deactivated_at: 0,
+        min_duration_seconds,
+        index_page,
+    };
+    let payment_count = subscription.record_payment(clock.unix_timestamp)?;
+    subscription.try_serialize(&mut &mut ctx.accounts.subscription.data.borrow_mut()[..])?;
 
     emit!(SubscriptionCreated {
         subscriber: subscription.subscriber,
         creator: subscription.creator,
         amount_per_month,
+        payment_count,
+        mint: None,
+        reference: subscription.reference,
         timestamp: clock.unix_timestamp,
     });
 
     Ok(())
 }
+
+/// Checks a subscription by tier against the creator's catalog. The
+/// subscriber still passes the amount they expect, so a price change landing
+/// before their transaction can't charge them more than they signed.
+pub(crate) fn check_tier_amount(
+    catalog: Option<&Account<SubscriptionTiers>>,
+    tier_index: Option<u8>,
+    amount_per_month: u64,
+) -> Result<()> {
+    if let Some(tier_index) = tier_index {
+        let catalog = catalog.ok_or(PaymentError::TierCatalogRequired)?;
+        require!(
+            catalog.amount_for(tier_index)? == amount_per_month,
+            PaymentError::TierAmountMismatch
+        );
+    }
+    Ok(())
+}
+
+/// Lists the subscriber on the vault's roster, if it keeps one, and returns
+/// the page they were added to
+pub(crate) fn index_subscriber(
+    vault: &CreatorVault,
+    subscriber_index: Option<&mut Account<SubscriberIndex>>,
+    subscriber: Pubkey,
+) -> Result<Option<u32>> {
+    if vault.subscriber_index_pages == 0 {
+        return Ok(None);
+    }
+    let index = subscriber_index.ok_or(PaymentError::SubscriberIndexRequired)?;
+    index.add(subscriber)?;
+    Ok(Some(index.page))
+}
+
+/// Creates the subscription PDA after checking the subscriber can cover the
+/// first payment as well as the rent, so they get a clear error instead of a
+/// system program failure
+fn create_subscription_account(ctx: &Context<Subscribe>, amount_per_month: u64) -> Result<()> {
+    let subscription = &ctx.accounts.subscription;
+    let subscriber = &ctx.accounts.subscriber;
+    require!(subscription.data_is_empty(), PaymentError::AlreadySubscribed);
+
+    let space = 8 + Subscription::INIT_SPACE;
+    let required = amount_per_month
+        .checked_add(rent_shortfall(subscription, space)?)
+        .ok_or(PaymentError::ArithmeticOverflow)?;
+    require!(
+        subscriber.lamports() >= required,
+        PaymentError::InsufficientFundsForSubscription
+    );
+
+    let creator = ctx.accounts.creator_vault.creator;
+    create_pda_account(
+        &subscriber.to_account_info(),
+        &subscription.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        space,
+        &crate::ID,
+        &[
+            b"subscription",
+            subscriber.key.as_ref(),
+            creator.as_ref(),
+            ctx.accounts.creator_vault.namespace_seed(),
+            &[ctx.bumps.subscription],
+        ],
+    )
+}
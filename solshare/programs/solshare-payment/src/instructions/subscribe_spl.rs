@@ -0,0 +1,193 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked};
+use crate::state::{PlatformConfig, CreatorVault, Subscription, SubscriberIndex, SubscriptionTiers};
+use crate::error::PaymentError;
+use crate::events::SubscriptionCreated;
+use super::subscribe::{check_tier_amount, index_subscriber};
+
+#[derive(Accounts)]
+pub struct SubscribeSpl<'info> {
+    #[account(
+        seeds = [b"platform_config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, PlatformConfig>,
+
+    // Derives the canonical bump rather than trusting the stored one, which
+    // the handler then checks against it
+    #[account(
+        mut,
+        seeds = [b"vault", creator_vault.creator.as_ref(), creator_vault.namespace_seed()],
+        bump
+    )]
+    pub creator_vault: Account<'info, CreatorVault>,
+
+    #[account(
+        init,
+        payer = subscriber,
+        space = 8 + Subscription::INIT_SPACE,
+        seeds = [b"subscription", subscriber.key().as_ref(), creator_vault.creator.as_ref(), creator_vault.namespace_seed()],
+        bump
+    )]
+    pub subscription: Account<'info, Subscription>,
+
+    #[account(mut)]
+    pub subscriber: Signer<'info>,
+
+    #[account(
+        constraint = creator_vault.accepts_mint(&mint.key()) @ PaymentError::UnacceptedMint
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = subscriber,
+        token::token_program = token_program
+    )]
+    pub subscriber_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = creator_vault.creator,
+        token::token_program = token_program
+    )]
+    pub creator_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    // Platform fees in SPL tokens go straight to the fee recipient's account
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = config.fee_recipient,
+        token::token_program = token_program
+    )]
+    pub fee_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+
+    // Required once the vault keeps a subscriber index: a page with room
+    #[account(
+        mut,
+        seeds = [b"subscriber_index", creator_vault.key().as_ref(), &subscriber_index.page.to_le_bytes()],
+        bump = subscriber_index.bump
+    )]
+    pub subscriber_index: Option<Account<'info, SubscriberIndex>>,
+
+    // Required when subscribing by tier_index
+    #[account(
+        seeds = [b"tiers", creator_vault.key().as_ref()],
+        bump = subscription_tiers.bump
+    )]
+    pub subscription_tiers: Option<Account<'info, SubscriptionTiers>>,
+}
+
+pub fn handler(
+    ctx: Context<SubscribeSpl>,
+    amount_per_month: u64,
+    reference: Option<[u8; 16]>,
+    tier_index: Option<u8>,
+    min_duration_seconds: Option<i64>,
+) -> Result<()> {
+    ctx.accounts.creator_vault.check_bump(ctx.bumps.creator_vault)?;
+    require!(amount_per_month > 0, PaymentError::InvalidAmount);
+    require!(
+        min_duration_seconds.is_none_or(|min_duration| min_duration > 0),
+        PaymentError::InvalidMinDuration
+    );
+    check_tier_amount(
+        ctx.accounts.subscription_tiers.as_ref(),
+        tier_index,
+        amount_per_month,
+    )?;
+    require!(
+        ctx.accounts.subscriber.key() != ctx.accounts.creator_vault.creator,
+        PaymentError::CannotSubscribeToSelf
+    );
+
+    let config = &ctx.accounts.config;
+    config.check_subscription_amount(amount_per_month)?;
+    let clock = Clock::get()?;
+
+    let fee = config.platform_fee(amount_per_month)?;
+
+    let creator_amount = amount_per_month.checked_sub(fee).ok_or(PaymentError::ArithmeticOverflow)?;
+
+    transfer_spl_payment(
+        &ctx.accounts.token_program,
+        &ctx.accounts.mint,
+        &ctx.accounts.subscriber_token_account,
+        &ctx.accounts.subscriber,
+        (&ctx.accounts.creator_token_account, creator_amount),
+        (&ctx.accounts.fee_token_account, fee),
+    )?;
+
+    // Vault earnings are tracked in lamports, so only the subscriber count changes
+    let vault = &mut ctx.accounts.creator_vault;
+    vault.add_subscriber()?;
+
+    let subscription = &mut ctx.accounts.subscription;
+    subscription.subscriber = ctx.accounts.subscriber.key();
+    subscription.creator = vault.creator;
+    subscription.amount_per_month = amount_per_month;
+    subscription.started_at = clock.unix_timestamp;
+    subscription.is_active = true;
+    subscription.bump = ctx.bumps.subscription;
+    subscription.payment_count = 0;
+    subscription.mint = Some(ctx.accounts.mint.key());
+    subscription.auto_renew = true;
+    subscription.disputed = false;
+    subscription.reference = reference;
+    subscription.deactivated_at = 0;
+    subscription.min_duration_seconds = min_duration_seconds;
+    subscription.index_page = index_subscriber(
+        vault,
+        ctx.accounts.subscriber_index.as_mut(),
+        subscription.subscriber,
+    )?;
+    let payment_count = subscription.record_payment(clock.unix_timestamp)?;
+
+    emit!(SubscriptionCreated {
+        subscriber: subscription.subscriber,
+        creator: subscription.creator,
+        amount_per_month,
+        payment_count,
+        mint: subscription.mint,
+        reference: subscription.reference,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Pulls a subscription payment from the subscriber, splitting it between the
+/// creator and the platform fee account
+pub(crate) fn transfer_spl_payment<'info>(
+    token_program: &Interface<'info, TokenInterface>,
+    mint: &InterfaceAccount<'info, Mint>,
+    from: &InterfaceAccount<'info, TokenAccount>,
+    authority: &Signer<'info>,
+    (creator_token_account, creator_amount): (&InterfaceAccount<'info, TokenAccount>, u64),
+    (fee_token_account, fee): (&InterfaceAccount<'info, TokenAccount>, u64),
+) -> Result<()> {
+    for (to, amount) in [(creator_token_account, creator_amount), (fee_token_account, fee)] {
+        if amount == 0 {
+            continue;
+        }
+        transfer_checked(
+            CpiContext::new(
+                token_program.to_account_info(),
+                TransferChecked {
+                    from: from.to_account_info(),
+                    mint: mint.to_account_info(),
+                    to: to.to_account_info(),
+                    authority: authority.to_account_info(),
+                },
+            ),
+            amount,
+            mint.decimals,
+        )?;
+    }
+    Ok(())
+}
@@ -0,0 +1,59 @@
+use anchor_lang::prelude::*;
+use crate::state::{PlatformConfig, Treasury};
+use crate::error::PaymentError;
+use crate::events::TreasurySwept;
+
+#[derive(Accounts)]
+pub struct SweepTreasury<'info> {
+    #[account(
+        seeds = [b"platform_config"],
+        bump = config.bump,
+        has_one = authority @ PaymentError::Unauthorized
+    )]
+    pub config: Account<'info, PlatformConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    /// CHECK: Fee recipient — validated against platform config
+    #[account(mut, address = config.fee_recipient)]
+    pub fee_recipient: AccountInfo<'info>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SweepTreasury>) -> Result<()> {
+    let treasury = &mut ctx.accounts.treasury;
+    let clock = Clock::get()?;
+
+    // Sweep everything above the rent-exempt minimum
+    let treasury_info = treasury.to_account_info();
+    let rent = Rent::get()?;
+    let min_balance = rent.minimum_balance(treasury_info.data_len());
+    let amount = treasury_info
+        .lamports()
+        .checked_sub(min_balance)
+        .ok_or(PaymentError::InsufficientFunds)?;
+    require!(amount > 0, PaymentError::InsufficientFunds);
+
+    let fee_recipient_info = ctx.accounts.fee_recipient.to_account_info();
+    **treasury_info.try_borrow_mut_lamports()? -= amount;
+    **fee_recipient_info.try_borrow_mut_lamports()? += amount;
+
+    treasury.total_swept = treasury.total_swept
+        .checked_add(amount)
+        .ok_or(PaymentError::ArithmeticOverflow)?;
+
+    emit!(TreasurySwept {
+        fee_recipient: fee_recipient_info.key(),
+        amount,
+        total_swept: treasury.total_swept,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,101 @@
+use anchor_lang::prelude::*;
+use crate::state::{namespace_seed, CreatorVault, PlatformConfig, PlatformStats, TipRecord, TipperStats, Treasury};
+use super::initialize_vault::init_vault;
+use super::tip_creator::{apply_tip, TipAccounts};
+
+#[derive(Accounts)]
+#[instruction(amount: u64, post: Option<Pubkey>, tip_index: u64, post_index: Option<u64>)]
+pub struct TipAndInitVault<'info> {
+    #[account(
+        seeds = [b"platform_config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, PlatformConfig>,
+
+    /// CHECK: The creator being tipped; only its key is used, as the vault seed
+    /// and owner, so the vault stays theirs whoever pays for it
+    pub creator: UncheckedAccount<'info>,
+
+    // The creator's un-namespaced vault, created here on their first tip
+    #[account(
+        init_if_needed,
+        payer = tipper,
+        space = 8 + CreatorVault::INIT_SPACE,
+        seeds = [b"vault", creator.key().as_ref(), namespace_seed(&None)],
+        bump
+    )]
+    pub creator_vault: Account<'info, CreatorVault>,
+
+    #[account(
+        init,
+        payer = tipper,
+        space = 8 + TipRecord::INIT_SPACE,
+        seeds = [b"tip", tipper.key().as_ref(), tip_index.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub tip_record: Account<'info, TipRecord>,
+
+    #[account(mut)]
+    pub tipper: Signer<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = tipper,
+        space = 8 + TipperStats::INIT_SPACE,
+        seeds = [b"tipper_stats", tipper.key().as_ref(), creator.key().as_ref()],
+        bump
+    )]
+    pub tipper_stats: Account<'info, TipperStats>,
+
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    pub system_program: Program<'info, System>,
+
+    #[account(
+        mut,
+        seeds = [b"platform_stats"],
+        bump = platform_stats.bump
+    )]
+    pub platform_stats: Option<Account<'info, PlatformStats>>,
+}
+
+pub fn handler(
+    ctx: Context<TipAndInitVault>,
+    amount: u64,
+    post: Option<Pubkey>,
+    _tip_index: u64,
+    post_index: Option<u64>,
+) -> Result<()> {
+    let accounts = &mut *ctx.accounts;
+    init_vault(
+        &mut accounts.creator_vault,
+        &accounts.config,
+        accounts.creator.key(),
+        None,
+        ctx.bumps.creator_vault,
+        accounts.platform_stats.as_mut(),
+    )?;
+
+    apply_tip(
+        TipAccounts {
+            config: &accounts.config,
+            creator_vault: &mut accounts.creator_vault,
+            tip_record: &mut accounts.tip_record,
+            tipper: &accounts.tipper,
+            tipper_stats: &mut accounts.tipper_stats,
+            treasury: &mut accounts.treasury,
+            system_program: &accounts.system_program,
+            platform_stats: accounts.platform_stats.as_mut(),
+        },
+        amount,
+        post,
+        post_index,
+        ctx.bumps.tip_record,
+        ctx.bumps.tipper_stats,
+    )
+}
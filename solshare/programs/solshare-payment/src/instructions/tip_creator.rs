@@ -1,11 +1,11 @@
 use anchor_lang::prelude::*;
 use anchor_lang::system_program::{transfer, Transfer};
-use crate::state::{PlatformConfig, CreatorVault, TipRecord};
+use crate::state::{PlatformConfig, CreatorVault, TipRecord, Treasury, PlatformStats, TipperStats};
 use crate::error::PaymentError;
-use crate::events::TipSent;
+use crate::events::{FirstSupporterRecorded, TipSent};
 
 #[derive(Accounts)]
-#[instruction(amount: u64, post: Option<Pubkey>, tip_index: u64)]
+#[instruction(amount: u64, post: Option<Pubkey>, tip_index: u64, post_index: Option<u64>)]
 pub struct TipCreator<'info> {
     #[account(
         seeds = [b"platform_config"],
@@ -15,7 +15,7 @@ pub struct TipCreator<'info> {
     
     #[account(
         mut,
-        seeds = [b"vault", creator_vault.creator.as_ref()],
+        seeds = [b"vault", creator_vault.creator.as_ref(), creator_vault.namespace_seed()],
         bump = creator_vault.bump
     )]
     pub creator_vault: Account<'info, CreatorVault>,
@@ -32,28 +32,101 @@ pub struct TipCreator<'info> {
     #[account(mut)]
     pub tipper: Signer<'info>,
 
-    /// CHECK: Fee recipient — validated against platform config
-    #[account(mut, address = config.fee_recipient)]
-    pub fee_recipient: AccountInfo<'info>,
+    #[account(
+        init_if_needed,
+        payer = tipper,
+        space = 8 + TipperStats::INIT_SPACE,
+        seeds = [b"tipper_stats", tipper.key().as_ref(), creator_vault.creator.as_ref()],
+        bump
+    )]
+    pub tipper_stats: Account<'info, TipperStats>,
+
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, Treasury>,
 
     pub system_program: Program<'info, System>,
+
+    #[account(
+        mut,
+        seeds = [b"platform_stats"],
+        bump = platform_stats.bump
+    )]
+    pub platform_stats: Option<Account<'info, PlatformStats>>,
+}
+
+pub fn handler(
+    ctx: Context<TipCreator>,
+    amount: u64,
+    post: Option<Pubkey>,
+    _tip_index: u64,
+    post_index: Option<u64>,
+) -> Result<()> {
+    let accounts = &mut *ctx.accounts;
+    apply_tip(
+        TipAccounts {
+            config: &accounts.config,
+            creator_vault: &mut accounts.creator_vault,
+            tip_record: &mut accounts.tip_record,
+            tipper: &accounts.tipper,
+            tipper_stats: &mut accounts.tipper_stats,
+            treasury: &mut accounts.treasury,
+            system_program: &accounts.system_program,
+            platform_stats: accounts.platform_stats.as_mut(),
+        },
+        amount,
+        post,
+        post_index,
+        ctx.bumps.tip_record,
+        ctx.bumps.tipper_stats,
+    )
+}
+
+/// The accounts a SOL tip touches; shared with tip_and_init_vault
+pub(crate) struct TipAccounts<'a, 'info> {
+    pub config: &'a PlatformConfig,
+    pub creator_vault: &'a mut Account<'info, CreatorVault>,
+    pub tip_record: &'a mut Account<'info, TipRecord>,
+    pub tipper: &'a Signer<'info>,
+    pub tipper_stats: &'a mut Account<'info, TipperStats>,
+    pub treasury: &'a mut Account<'info, Treasury>,
+    pub system_program: &'a Program<'info, System>,
+    pub platform_stats: Option<&'a mut Account<'info, PlatformStats>>,
 }
 
-pub fn handler(ctx: Context<TipCreator>, amount: u64, post: Option<Pubkey>, _tip_index: u64) -> Result<()> {
+/// Sends `amount` from the tipper to the creator's vault, less the platform
+/// fee, and fills in the tip record and the tipper's running totals
+pub(crate) fn apply_tip(
+    accounts: TipAccounts,
+    amount: u64,
+    post: Option<Pubkey>,
+    post_index: Option<u64>,
+    tip_record_bump: u8,
+    tipper_stats_bump: u8,
+) -> Result<()> {
+    let TipAccounts {
+        config,
+        creator_vault: vault,
+        tip_record,
+        tipper,
+        tipper_stats,
+        treasury,
+        system_program,
+        platform_stats,
+    } = accounts;
+
     require!(amount > 0, PaymentError::InvalidAmount);
-    require!(
-        ctx.accounts.tipper.key() != ctx.accounts.creator_vault.creator,
-        PaymentError::CannotTipSelf
-    );
+    require!(tipper.key() != vault.creator, PaymentError::CannotTipSelf);
+    config.check_stats_passed(&platform_stats)?;
+
+    validate_tip_post(&vault.creator, post, post_index)?;
 
-    let config = &ctx.accounts.config;
     let clock = Clock::get()?;
 
-    let fee = amount
-        .checked_mul(config.fee_basis_points as u64)
-        .ok_or(PaymentError::ArithmeticOverflow)?
-        .checked_div(10000)
-        .ok_or(PaymentError::ArithmeticOverflow)?;
+    let fee = config.platform_fee(amount)?;
 
     let creator_amount = amount.checked_sub(fee).ok_or(PaymentError::ArithmeticOverflow)?;
 
@@ -61,41 +134,58 @@ pub fn handler(ctx: Context<TipCreator>, amount: u64, post: Option<Pubkey>, _tip
     if fee > 0 {
         transfer(
             CpiContext::new(
-                ctx.accounts.system_program.to_account_info(),
+                system_program.to_account_info(),
                 Transfer {
-                    from: ctx.accounts.tipper.to_account_info(),
-                    to: ctx.accounts.fee_recipient.to_account_info(),
+                    from: tipper.to_account_info(),
+                    to: treasury.to_account_info(),
                 },
             ),
             fee,
         )?;
+
+        treasury.total_collected = treasury.total_collected
+            .checked_add(fee)
+            .ok_or(PaymentError::ArithmeticOverflow)?;
     }
 
     // Transfer to creator vault (escrow)
     transfer(
         CpiContext::new(
-            ctx.accounts.system_program.to_account_info(),
+            system_program.to_account_info(),
             Transfer {
-                from: ctx.accounts.tipper.to_account_info(),
-                to: ctx.accounts.creator_vault.to_account_info(),
+                from: tipper.to_account_info(),
+                to: vault.to_account_info(),
             },
         ),
         creator_amount,
     )?;
 
     // Update vault stats after transfers are complete
-    let vault = &mut ctx.accounts.creator_vault;
     vault.total_earned = vault.total_earned
         .checked_add(creator_amount)
         .ok_or(PaymentError::ArithmeticOverflow)?;
 
-    let tip_record = &mut ctx.accounts.tip_record;
-    tip_record.from = ctx.accounts.tipper.key();
+    if vault.record_first_supporter(tipper.key()) {
+        emit!(FirstSupporterRecorded {
+            vault: vault.key(),
+            creator: vault.creator,
+            supporter: tipper.key(),
+            timestamp: clock.unix_timestamp,
+        });
+    }
+
+    if let Some(stats) = platform_stats {
+        stats.record_tip(amount)?;
+    }
+    tipper_stats.record_tip(tipper.key(), vault.creator, amount, tipper_stats_bump)?;
+
+    tip_record.from = tipper.key();
     tip_record.to = vault.creator;
     tip_record.amount = creator_amount;
     tip_record.post = post;
     tip_record.timestamp = clock.unix_timestamp;
-    tip_record.bump = ctx.bumps.tip_record;
+    tip_record.bump = tip_record_bump;
+    tip_record.mint = None;
 
     emit!(TipSent {
         from: tip_record.from,
@@ -103,8 +193,27 @@ pub fn handler(ctx: Context<TipCreator>, amount: u64, post: Option<Pubkey>, _tip
         amount,
         fee,
         post,
+        mint: None,
         timestamp: clock.unix_timestamp,
     });
 
     Ok(())
 }
+
+/// Opt-in: supplying the post index proves the post belongs to the tipped
+/// creator by checking the PDA derivation against the social program
+pub(crate) fn validate_tip_post(
+    creator: &Pubkey,
+    post: Option<Pubkey>,
+    post_index: Option<u64>,
+) -> Result<()> {
+    if let Some(post_index) = post_index {
+        let post = post.ok_or(PaymentError::InvalidPost)?;
+        let (expected_post_pda, _) = Pubkey::find_program_address(
+            &[b"post", creator.as_ref(), &post_index.to_le_bytes()],
+            &solshare_social::ID,
+        );
+        require!(post == expected_post_pda, PaymentError::InvalidPost);
+    }
+    Ok(())
+}
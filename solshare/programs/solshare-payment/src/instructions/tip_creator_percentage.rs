@@ -0,0 +1,61 @@
+use anchor_lang::prelude::*;
+use crate::error::PaymentError;
+use super::tip_creator::{self, TipCreator};
+
+pub const MAX_TIP_BPS: u16 = 10_000;
+
+/// Tip computed on-chain from a base price so every client rounds the same
+/// way; the fractional lamport is always rounded down
+pub fn tip_amount(base_amount: u64, bps: u16) -> Result<u64> {
+    require!(bps <= MAX_TIP_BPS, PaymentError::InvalidTipPercentage);
+
+    let amount = (base_amount as u128)
+        .checked_mul(bps as u128)
+        .ok_or(PaymentError::ArithmeticOverflow)?
+        / MAX_TIP_BPS as u128;
+    u64::try_from(amount).map_err(|_| error!(PaymentError::ArithmeticOverflow))
+}
+
+// Reuses the `TipCreator` accounts: the leading arguments line up with its
+// `#[instruction]` attribute, so the tip record PDA is derived the same way
+pub fn handler(
+    ctx: Context<TipCreator>,
+    base_amount: u64,
+    post: Option<Pubkey>,
+    tip_index: u64,
+    post_index: Option<u64>,
+    bps: u16,
+) -> Result<()> {
+    let amount = tip_amount(base_amount, bps)?;
+    tip_creator::handler(ctx, amount, post, tip_index, post_index)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn common_percentages_of_a_round_price() {
+        let price = 1_000_000_000;
+        assert_eq!(tip_amount(price, 1_000).unwrap(), 100_000_000);
+        assert_eq!(tip_amount(price, 2_000).unwrap(), 200_000_000);
+        assert_eq!(tip_amount(price, 5_000).unwrap(), 500_000_000);
+        assert_eq!(tip_amount(price, MAX_TIP_BPS).unwrap(), price);
+    }
+
+    #[test]
+    fn fractional_lamports_round_down() {
+        assert_eq!(tip_amount(999, 1_000).unwrap(), 99);
+        assert_eq!(tip_amount(3, 5_000).unwrap(), 1);
+        assert_eq!(tip_amount(9, 1_000).unwrap(), 0);
+        assert_eq!(tip_amount(u64::MAX, MAX_TIP_BPS).unwrap(), u64::MAX);
+    }
+
+    #[test]
+    fn rejects_more_than_one_hundred_percent() {
+        assert_eq!(
+            tip_amount(1_000, MAX_TIP_BPS + 1).unwrap_err(),
+            PaymentError::InvalidTipPercentage.into()
+        );
+    }
+}
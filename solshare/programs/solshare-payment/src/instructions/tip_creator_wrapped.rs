@@ -0,0 +1,185 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{self, Mint, SyncNative, Token, TokenAccount};
+use anchor_spl::token::spl_token::native_mint;
+use crate::state::{PlatformConfig, CreatorVault, TipRecord, Treasury, PlatformStats, TipperStats};
+use crate::error::PaymentError;
+use crate::events::{FirstSupporterRecorded, TipSent};
+use super::tip_creator::validate_tip_post;
+
+#[derive(Accounts)]
+#[instruction(amount: u64, post: Option<Pubkey>, tip_index: u64, post_index: Option<u64>)]
+pub struct TipCreatorWrapped<'info> {
+    #[account(
+        seeds = [b"platform_config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, PlatformConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", creator_vault.creator.as_ref(), creator_vault.namespace_seed()],
+        bump = creator_vault.bump
+    )]
+    pub creator_vault: Account<'info, CreatorVault>,
+
+    #[account(
+        init,
+        payer = tipper,
+        space = 8 + TipRecord::INIT_SPACE,
+        seeds = [b"tip", tipper.key().as_ref(), tip_index.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub tip_record: Account<'info, TipRecord>,
+
+    #[account(mut)]
+    pub tipper: Signer<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = tipper,
+        space = 8 + TipperStats::INIT_SPACE,
+        seeds = [b"tipper_stats", tipper.key().as_ref(), creator_vault.creator.as_ref()],
+        bump
+    )]
+    pub tipper_stats: Account<'info, TipperStats>,
+
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    /// CHECK: The creator's wallet, only used as the wSOL account authority.
+    /// Pinning it to the vault keeps a tip for one creator from being wrapped
+    /// into another wallet's wSOL account.
+    #[account(address = creator_vault.creator @ PaymentError::InvalidCreatorAccount)]
+    pub creator: UncheckedAccount<'info>,
+
+    #[account(address = native_mint::ID)]
+    pub wsol_mint: Account<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = tipper,
+        associated_token::mint = wsol_mint,
+        associated_token::authority = creator
+    )]
+    pub creator_wsol_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+
+    #[account(
+        mut,
+        seeds = [b"platform_stats"],
+        bump = platform_stats.bump
+    )]
+    pub platform_stats: Option<Account<'info, PlatformStats>>,
+}
+
+/// Tips in SOL but credits the creator's wSOL account instead of their vault,
+/// for creators who keep all revenue in token accounts
+pub fn handler(
+    ctx: Context<TipCreatorWrapped>,
+    amount: u64,
+    post: Option<Pubkey>,
+    _tip_index: u64,
+    post_index: Option<u64>,
+) -> Result<()> {
+    require!(amount > 0, PaymentError::InvalidAmount);
+    require!(
+        ctx.accounts.tipper.key() != ctx.accounts.creator_vault.creator,
+        PaymentError::CannotTipSelf
+    );
+    validate_tip_post(&ctx.accounts.creator_vault.creator, post, post_index)?;
+
+    let config = &ctx.accounts.config;
+    config.check_stats_passed(&ctx.accounts.platform_stats)?;
+    let clock = Clock::get()?;
+
+    let fee = config.platform_fee(amount)?;
+
+    let creator_amount = amount.checked_sub(fee).ok_or(PaymentError::ArithmeticOverflow)?;
+
+    // Platform fees stay in SOL
+    if fee > 0 {
+        transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.tipper.to_account_info(),
+                    to: ctx.accounts.treasury.to_account_info(),
+                },
+            ),
+            fee,
+        )?;
+
+        let treasury = &mut ctx.accounts.treasury;
+        treasury.total_collected = treasury.total_collected
+            .checked_add(fee)
+            .ok_or(PaymentError::ArithmeticOverflow)?;
+    }
+
+    // Wrap by depositing lamports into the wSOL account and syncing its balance
+    transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.tipper.to_account_info(),
+                to: ctx.accounts.creator_wsol_account.to_account_info(),
+            },
+        ),
+        creator_amount,
+    )?;
+    token::sync_native(CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        SyncNative {
+            account: ctx.accounts.creator_wsol_account.to_account_info(),
+        },
+    ))?;
+
+    let vault = &mut ctx.accounts.creator_vault;
+    if vault.record_first_supporter(ctx.accounts.tipper.key()) {
+        emit!(FirstSupporterRecorded {
+            vault: vault.key(),
+            creator: vault.creator,
+            supporter: ctx.accounts.tipper.key(),
+            timestamp: clock.unix_timestamp,
+        });
+    }
+
+    if let Some(stats) = ctx.accounts.platform_stats.as_mut() {
+        stats.record_tip(amount)?;
+    }
+    ctx.accounts.tipper_stats.record_tip(
+        ctx.accounts.tipper.key(),
+        ctx.accounts.creator_vault.creator,
+        amount,
+        ctx.bumps.tipper_stats,
+    )?;
+
+    let tip_record = &mut ctx.accounts.tip_record;
+    tip_record.from = ctx.accounts.tipper.key();
+    tip_record.to = ctx.accounts.creator_vault.creator;
+    tip_record.amount = creator_amount;
+    tip_record.post = post;
+    tip_record.timestamp = clock.unix_timestamp;
+    tip_record.bump = ctx.bumps.tip_record;
+    tip_record.mint = Some(native_mint::ID);
+
+    emit!(TipSent {
+        from: tip_record.from,
+        to: tip_record.to,
+        amount,
+        fee,
+        post,
+        mint: tip_record.mint,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
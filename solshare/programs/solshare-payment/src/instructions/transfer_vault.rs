@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+use crate::state::CreatorVault;
+use crate::error::PaymentError;
+use crate::events::VaultTransferProposed;
+
+#[derive(Accounts)]
+pub struct TransferVault<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", creator.key().as_ref(), vault.namespace_seed()],
+        bump = vault.bump,
+        has_one = creator @ PaymentError::Unauthorized
+    )]
+    pub vault: Account<'info, CreatorVault>,
+
+    pub creator: Signer<'info>,
+}
+
+/// First step of moving a vault to another wallet: offers it to
+/// `new_creator`, who completes the move with accept_vault. None withdraws
+/// the offer; a new offer replaces the old one.
+pub fn handler(ctx: Context<TransferVault>, new_creator: Option<Pubkey>) -> Result<()> {
+    let vault = &mut ctx.accounts.vault;
+    vault.pending_creator = new_creator;
+
+    emit!(VaultTransferProposed {
+        vault: vault.key(),
+        creator: vault.creator,
+        pending_creator: new_creator,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -1,13 +1,20 @@
 use anchor_lang::prelude::*;
-use crate::state::CreatorVault;
+use crate::state::{CreatorVault, PlatformConfig, Treasury};
 use crate::error::PaymentError;
-use crate::events::Withdrawal;
+use crate::events::{StateCorrectionApplied, Withdrawal};
+use crate::utils::transfer_from_pda;
 
 #[derive(Accounts)]
 pub struct Withdraw<'info> {
+    #[account(
+        seeds = [b"platform_config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, PlatformConfig>,
+
     #[account(
         mut,
-        seeds = [b"vault", creator.key().as_ref()],
+        seeds = [b"vault", creator.key().as_ref(), vault.namespace_seed()],
         bump = vault.bump,
         has_one = creator @ PaymentError::Unauthorized
     )]
@@ -15,6 +22,13 @@ pub struct Withdraw<'info> {
 
     #[account(mut)]
     pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, Treasury>,
 }
 
 pub fn handler(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
@@ -23,26 +37,41 @@ pub fn handler(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
 
     require!(amount > 0, PaymentError::InvalidAmount);
 
-    let available = vault.total_earned
-        .checked_sub(vault.withdrawn)
-        .ok_or(PaymentError::ArithmeticOverflow)?;
+    // withdrawn should never exceed total_earned; if it does, heal the vault
+    // rather than failing every withdrawal from now on
+    if let Some(previous_withdrawn) = vault.reconcile_withdrawn() {
+        emit!(StateCorrectionApplied {
+            vault: vault.key(),
+            creator: vault.creator,
+            total_earned: vault.total_earned,
+            previous_withdrawn,
+            timestamp: clock.unix_timestamp,
+        });
+        // Failing below would roll the correction back with it, so save it
+        // even when there is nothing left to pay out
+        if vault.available() == 0 {
+            return Ok(());
+        }
+    }
+    let available = vault.available();
 
+    // Distinguish an empty vault from asking for more than it holds
+    require!(available > 0, PaymentError::NothingToWithdraw);
     require!(amount <= available, PaymentError::WithdrawalExceedsBalance);
 
-    // Verify vault PDA has enough lamports (accounting for rent-exempt minimum)
+    let fee = ctx.accounts.config.withdrawal_fee(amount)?;
+    let creator_amount = amount.checked_sub(fee).ok_or(PaymentError::ArithmeticOverflow)?;
+
+    // Pay the creator from the vault PDA, with the fee going to the treasury
     let vault_info = vault.to_account_info();
-    let rent = Rent::get()?;
-    let min_balance = rent.minimum_balance(vault_info.data_len());
-    let vault_lamports = vault_info.lamports();
-    let available_lamports = vault_lamports
-        .checked_sub(min_balance)
-        .ok_or(PaymentError::InsufficientFunds)?;
-    require!(amount <= available_lamports, PaymentError::InsufficientFunds);
-
-    // Transfer SOL from vault PDA to creator
-    let creator_info = ctx.accounts.creator.to_account_info();
-    **vault_info.try_borrow_mut_lamports()? -= amount;
-    **creator_info.try_borrow_mut_lamports()? += amount;
+    transfer_from_pda(&vault_info, &ctx.accounts.creator.to_account_info(), creator_amount)?;
+    if fee > 0 {
+        let treasury = &mut ctx.accounts.treasury;
+        transfer_from_pda(&vault_info, &treasury.to_account_info(), fee)?;
+        treasury.total_collected = treasury.total_collected
+            .checked_add(fee)
+            .ok_or(PaymentError::ArithmeticOverflow)?;
+    }
 
     vault.withdrawn = vault.withdrawn
         .checked_add(amount)
@@ -51,6 +80,7 @@ pub fn handler(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
     emit!(Withdrawal {
         creator: vault.creator,
         amount,
+        fee,
         timestamp: clock.unix_timestamp,
     });
 
@@ -2,9 +2,11 @@ pub mod error;
 pub mod events;
 pub mod instructions;
 pub mod state;
+pub mod utils;
 
 use anchor_lang::prelude::*;
 use instructions::*;
+use state::SubscriptionTier;
 
 declare_id!("8ABx4UuH35a3V6VPyg5BmjkZVn7YYJq2a7VeCjdpWuzM");
 
@@ -16,27 +18,217 @@ pub mod solshare_payment {
         initialize_platform::handler(ctx, fee_basis_points)
     }
 
-    pub fn initialize_vault(ctx: Context<InitializeVault>) -> Result<()> {
-        initialize_vault::handler(ctx)
+    pub fn initialize_platform_stats(ctx: Context<InitializePlatformStats>) -> Result<()> {
+        initialize_platform_stats::handler(ctx)
     }
 
-    pub fn tip_creator(ctx: Context<TipCreator>, amount: u64, post: Option<Pubkey>, tip_index: u64) -> Result<()> {
-        tip_creator::handler(ctx, amount, post, tip_index)
+    pub fn initialize_treasury(ctx: Context<InitializeTreasury>) -> Result<()> {
+        initialize_treasury::handler(ctx)
     }
 
-    pub fn subscribe(ctx: Context<Subscribe>, amount_per_month: u64) -> Result<()> {
-        subscribe::handler(ctx, amount_per_month)
+    pub fn set_stats_tracking(ctx: Context<SetStatsTracking>, track_stats: bool) -> Result<()> {
+        set_stats_tracking::handler(ctx, track_stats)
+    }
+
+    pub fn initialize_vault(ctx: Context<InitializeVault>, namespace: Option<[u8; 8]>) -> Result<()> {
+        initialize_vault::handler(ctx, namespace)
+    }
+
+    pub fn tip_creator(
+        ctx: Context<TipCreator>,
+        amount: u64,
+        post: Option<Pubkey>,
+        tip_index: u64,
+        post_index: Option<u64>,
+    ) -> Result<()> {
+        tip_creator::handler(ctx, amount, post, tip_index, post_index)
+    }
+
+    pub fn tip_creator_wrapped(
+        ctx: Context<TipCreatorWrapped>,
+        amount: u64,
+        post: Option<Pubkey>,
+        tip_index: u64,
+        post_index: Option<u64>,
+    ) -> Result<()> {
+        tip_creator_wrapped::handler(ctx, amount, post, tip_index, post_index)
+    }
+
+    pub fn tip_creator_percentage(
+        ctx: Context<TipCreator>,
+        base_amount: u64,
+        post: Option<Pubkey>,
+        tip_index: u64,
+        post_index: Option<u64>,
+        bps: u16,
+    ) -> Result<()> {
+        tip_creator_percentage::handler(ctx, base_amount, post, tip_index, post_index, bps)
+    }
+
+    pub fn tip_and_init_vault(
+        ctx: Context<TipAndInitVault>,
+        amount: u64,
+        post: Option<Pubkey>,
+        tip_index: u64,
+        post_index: Option<u64>,
+    ) -> Result<()> {
+        tip_and_init_vault::handler(ctx, amount, post, tip_index, post_index)
+    }
+
+    pub fn close_tip_record(ctx: Context<CloseTipRecord>) -> Result<()> {
+        close_tip_record::handler(ctx)
+    }
+
+    pub fn subscribe(
+        ctx: Context<Subscribe>,
+        amount_per_month: u64,
+        reference: Option<[u8; 16]>,
+        tier_index: Option<u8>,
+        min_duration_seconds: Option<i64>,
+    ) -> Result<()> {
+        subscribe::handler(ctx, amount_per_month, reference, tier_index, min_duration_seconds)
+    }
+
+    pub fn set_subscription_tiers(
+        ctx: Context<SetSubscriptionTiers>,
+        tiers: Vec<SubscriptionTier>,
+    ) -> Result<()> {
+        set_subscription_tiers::handler(ctx, tiers)
     }
 
     pub fn process_subscription(ctx: Context<ProcessSubscription>) -> Result<()> {
         process_subscription::handler(ctx)
     }
 
+    pub fn gift_subscription_period(ctx: Context<GiftSubscriptionPeriod>) -> Result<()> {
+        gift_subscription_period::handler(ctx)
+    }
+
+    pub fn subscribe_spl(
+        ctx: Context<SubscribeSpl>,
+        amount_per_month: u64,
+        reference: Option<[u8; 16]>,
+        tier_index: Option<u8>,
+        min_duration_seconds: Option<i64>,
+    ) -> Result<()> {
+        subscribe_spl::handler(ctx, amount_per_month, reference, tier_index, min_duration_seconds)
+    }
+
+    pub fn process_subscription_spl(ctx: Context<ProcessSubscriptionSpl>) -> Result<()> {
+        process_subscription_spl::handler(ctx)
+    }
+
+    pub fn process_subscription_prepaid(ctx: Context<ProcessSubscriptionPrepaid>) -> Result<()> {
+        process_subscription_prepaid::handler(ctx)
+    }
+
     pub fn cancel_subscription(ctx: Context<CancelSubscription>) -> Result<()> {
         cancel_subscription::handler(ctx)
     }
 
+    pub fn close_inactive_subscription(ctx: Context<CloseInactiveSubscription>) -> Result<()> {
+        close_inactive_subscription::handler(ctx)
+    }
+
+    pub fn open_subscriber_index_page(ctx: Context<OpenSubscriberIndexPage>) -> Result<()> {
+        open_subscriber_index_page::handler(ctx)
+    }
+
+    pub fn is_subscription_due(ctx: Context<IsSubscriptionDue>) -> Result<bool> {
+        is_subscription_due::handler(ctx)
+    }
+
+    pub fn next_payment_due(ctx: Context<NextPaymentDue>) -> Result<i64> {
+        next_payment_due::handler(ctx)
+    }
+
+    pub fn is_subscription_active(
+        ctx: Context<IsSubscriptionActive>,
+        subscriber: Pubkey,
+        creator: Pubkey,
+    ) -> Result<bool> {
+        is_subscription_active::handler(ctx, subscriber, creator)
+    }
+
+    pub fn set_auto_renew(ctx: Context<SetAutoRenew>, auto_renew: bool) -> Result<()> {
+        set_auto_renew::handler(ctx, auto_renew)
+    }
+
+    pub fn dispute_subscription(ctx: Context<DisputeSubscription>) -> Result<()> {
+        dispute_subscription::handler(ctx)
+    }
+
+    pub fn resolve_dispute(ctx: Context<ResolveDispute>) -> Result<()> {
+        resolve_dispute::handler(ctx)
+    }
+
     pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
         withdraw::handler(ctx, amount)
     }
+
+    pub fn set_required_mint(ctx: Context<SetRequiredMint>, require_mint: Option<Pubkey>) -> Result<()> {
+        set_required_mint::handler(ctx, require_mint)
+    }
+
+    pub fn set_max_subscribers(ctx: Context<SetMaxSubscribers>, max_subscribers: u64) -> Result<()> {
+        set_max_subscribers::handler(ctx, max_subscribers)
+    }
+
+    pub fn record_daily_rollup(ctx: Context<RecordDailyRollup>) -> Result<()> {
+        record_daily_rollup::handler(ctx)
+    }
+
+    pub fn sweep_treasury(ctx: Context<SweepTreasury>) -> Result<()> {
+        sweep_treasury::handler(ctx)
+    }
+
+    pub fn set_max_subscription_amount(
+        ctx: Context<SetMaxSubscriptionAmount>,
+        max_amount_per_month: Option<u64>,
+    ) -> Result<()> {
+        set_max_subscription_amount::handler(ctx, max_amount_per_month)
+    }
+
+    pub fn set_vault_verified(ctx: Context<SetVaultVerified>, verified: bool) -> Result<()> {
+        set_vault_verified::handler(ctx, verified)
+    }
+
+    pub fn set_free_withdrawal_threshold(
+        ctx: Context<SetFreeWithdrawalThreshold>,
+        free_withdrawal_threshold: Option<u64>,
+    ) -> Result<()> {
+        set_free_withdrawal_threshold::handler(ctx, free_withdrawal_threshold)
+    }
+
+    pub fn set_crank_reward(ctx: Context<SetCrankReward>, crank_reward_lamports: Option<u64>) -> Result<()> {
+        set_crank_reward::handler(ctx, crank_reward_lamports)
+    }
+
+    pub fn set_fee_rounding(ctx: Context<SetFeeRounding>, round_fee_up: bool) -> Result<()> {
+        set_fee_rounding::handler(ctx, round_fee_up)
+    }
+
+    pub fn transfer_vault(ctx: Context<TransferVault>, new_creator: Option<Pubkey>) -> Result<()> {
+        transfer_vault::handler(ctx, new_creator)
+    }
+
+    pub fn accept_vault(ctx: Context<AcceptVault>) -> Result<()> {
+        accept_vault::handler(ctx)
+    }
+
+    pub fn migrate_platform_config(ctx: Context<MigratePlatformConfig>) -> Result<()> {
+        migrate_platform_config::handler(ctx)
+    }
+
+    pub fn migrate_vault(ctx: Context<MigrateVault>) -> Result<()> {
+        migrate_vault::handler(ctx)
+    }
+
+    pub fn migrate_subscription(ctx: Context<MigrateSubscription>) -> Result<()> {
+        migrate_subscription::handler(ctx)
+    }
+
+    pub fn migrate_tip_record(ctx: Context<MigrateTipRecord>) -> Result<()> {
+        migrate_tip_record::handler(ctx)
+    }
 }
@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use crate::error::PaymentError;
 
 #[account]
 #[derive(InitSpace)]
@@ -7,6 +8,36 @@ pub struct PlatformConfig {
     pub fee_basis_points: u16, // 200 = 2%
     pub fee_recipient: Pubkey,
     pub bump: u8,
+    // Ceiling on amount_per_month for subscribe/process_subscription; None means no limit
+    pub max_amount_per_month: Option<u64>,
+    // Withdrawals pay fee_basis_points on the portion above this; None means withdrawals are free
+    pub free_withdrawal_threshold: Option<u64>,
+    // Lamports the subscriber pays whoever cranks a SOL renewal; None means no reward
+    pub crank_reward_lamports: Option<u64>,
+    // Round percentage fees up instead of down, so fractional lamports go to the platform
+    pub round_fee_up: bool,
+    // Set once PlatformStats exists; counted instructions then require the account
+    pub track_stats: bool,
+}
+
+// Holds platform fees until the authority sweeps them to the fee recipient
+#[account]
+#[derive(InitSpace)]
+pub struct Treasury {
+    pub total_collected: u64,
+    pub total_swept: u64,
+    pub bump: u8,
+}
+
+// Lifetime platform totals in lamports, starting from the account's creation.
+// Once PlatformConfig.track_stats is on, every counted instruction must pass it.
+#[account]
+#[derive(InitSpace)]
+pub struct PlatformStats {
+    pub total_tip_volume: u64,
+    pub total_subscription_volume: u64,
+    pub total_vaults: u64,
+    pub bump: u8,
 }
 
 #[account]
@@ -17,6 +48,24 @@ pub struct CreatorVault {
     pub withdrawn: u64,
     pub subscribers: u64,
     pub bump: u8,
+    // When set, SPL payments to this creator must use this mint
+    pub require_mint: Option<Pubkey>,
+    // Day index (unix days) of the latest DailyEarnings rollup, and total_earned at that time
+    pub last_rollup_day: i64,
+    pub last_rollup_earned: u64,
+    // Lets one creator hold separate vaults per app; None is the original,
+    // un-namespaced vault
+    pub namespace: Option<[u8; 8]>,
+    // Official-vault badge, toggled only by the platform authority
+    pub verified: bool,
+    // Wallet behind the vault's first ever tip, for early-supporter perks
+    pub first_supporter: Option<Pubkey>,
+    // Pages opened in the vault's SubscriberIndex; 0 while the roster is off
+    pub subscriber_index_pages: u32,
+    // Cap on active subscribers for exclusive memberships; 0 means unlimited
+    pub max_subscribers: u64,
+    // Wallet the creator has offered the vault to; it takes over by calling accept_vault
+    pub pending_creator: Option<Pubkey>,
 }
 
 #[account]
@@ -28,6 +77,8 @@ pub struct TipRecord {
     pub post: Option<Pubkey>,
     pub timestamp: i64,
     pub bump: u8,
+    // wSOL mint for tips credited to the creator's token account; None for vault tips
+    pub mint: Option<Pubkey>,
 }
 
 #[account]
@@ -40,8 +91,1011 @@ pub struct Subscription {
     pub started_at: i64,
     pub is_active: bool,
     pub bump: u8,
+    // Sequence number of the latest payment; the first payment (in subscribe) is 1
+    pub payment_count: u32,
+    // SPL mint the subscription is paid in; None for SOL subscriptions
+    pub mint: Option<Pubkey>,
+    // When false the subscription ends at the close of the paid period instead of renewing
+    pub auto_renew: bool,
+    // Set by the subscriber to pause charges until the creator resolves the dispute
+    pub disputed: bool,
+    // Subscriber-supplied invoice id echoed in payment events for reconciliation
+    pub reference: Option<[u8; 16]>,
+    // When the subscription was cancelled or lapsed; 0 while active
+    pub deactivated_at: i64,
+    // Opt-in commitment: the subscriber can't cancel or turn off auto-renew
+    // until this long after started_at; None means no minimum
+    pub min_duration_seconds: Option<i64>,
+    // SubscriberIndex page listing this subscriber; None when not listed
+    pub index_page: Option<u32>,
+}
+
+pub const MAX_SUBSCRIPTION_TIERS: usize = 5;
+pub const MAX_TIER_NAME_LEN: usize = 32;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct SubscriptionTier {
+    #[max_len(MAX_TIER_NAME_LEN)]
+    pub name: String,
+    pub amount_per_month: u64,
+}
+
+// Creator-managed price list for a vault; subscribers pick a tier by index
+#[account]
+#[derive(InitSpace)]
+pub struct SubscriptionTiers {
+    pub vault: Pubkey,
+    #[max_len(MAX_SUBSCRIPTION_TIERS)]
+    pub tiers: Vec<SubscriptionTier>,
+    pub bump: u8,
+}
+
+pub const SUBSCRIBERS_PER_INDEX_PAGE: usize = 32;
+
+// One page of a vault's subscriber roster, so clients can list subscribers
+// without scanning every Subscription. Pages are numbered from 0 and the next
+// one is opened once the existing pages fill up. Lapsed subscriptions stay
+// listed until their account is closed.
+#[account]
+#[derive(InitSpace)]
+pub struct SubscriberIndex {
+    pub vault: Pubkey,
+    pub page: u32,
+    #[max_len(SUBSCRIBERS_PER_INDEX_PAGE)]
+    pub subscribers: Vec<Pubkey>,
+    pub bump: u8,
+}
+
+// Running totals of one wallet's SOL tips to one creator, for leaderboards.
+// Created on the tipper's first tip; amounts are before the platform fee.
+#[account]
+#[derive(InitSpace)]
+pub struct TipperStats {
+    pub tipper: Pubkey,
+    pub creator: Pubkey,
+    pub count: u64,
+    pub total_amount: u64,
+    pub bump: u8,
 }
 
 impl PlatformConfig {
     pub const FEE_BASIS_POINTS_DEFAULT: u16 = 200; // 2%
+    /// The original layout, authority through bump, before any fields were appended
+    pub const LEGACY_SIZE: usize = 75;
+
+    /// Reads a config saved under an older, shorter layout. Every field
+    /// appended so far reads zeroed bytes as its off setting (None or false),
+    /// so padding the data out to full size is enough.
+    pub fn upgrade_layout(data: &[u8]) -> Result<Self> {
+        let size = 8 + Self::INIT_SPACE;
+        require!(
+            (Self::LEGACY_SIZE..size).contains(&data.len()),
+            PaymentError::InvalidConfigLayout
+        );
+        let mut padded = data.to_vec();
+        padded.resize(size, 0);
+        Self::try_deserialize(&mut &padded[..])
+    }
+
+    /// Platform stats may be left out only while tracking is off, so clients
+    /// can't skip the counters once the authority has turned them on
+    pub fn check_stats_passed<T>(&self, platform_stats: &Option<T>) -> Result<()> {
+        require!(
+            !self.track_stats || platform_stats.is_some(),
+            PaymentError::PlatformStatsRequired
+        );
+        Ok(())
+    }
+
+    pub fn check_subscription_amount(&self, amount_per_month: u64) -> Result<()> {
+        if let Some(max_amount_per_month) = self.max_amount_per_month {
+            require!(
+                amount_per_month <= max_amount_per_month,
+                PaymentError::SubscriptionAmountTooHigh
+            );
+        }
+        Ok(())
+    }
+
+    /// Lamports a SOL renewal takes from the subscriber: the subscription
+    /// amount plus the crank reward when a crank processes it
+    pub fn renewal_cost(&self, amount: u64, cranked: bool) -> Result<u64> {
+        let reward = if cranked { self.crank_reward_lamports.unwrap_or(0) } else { 0 };
+        amount
+            .checked_add(reward)
+            .ok_or(error!(PaymentError::ArithmeticOverflow))
+    }
+
+    /// Platform fee on a payment of `amount`
+    pub fn platform_fee(&self, amount: u64) -> Result<u64> {
+        let scaled = amount
+            .checked_mul(self.fee_basis_points as u64)
+            .ok_or(PaymentError::ArithmeticOverflow)?;
+        // At most `amount`, since fee_basis_points <= 10000
+        Ok(self.round_fee(scaled as u128) as u64)
+    }
+
+    /// Platform fee on a vault withdrawal of `amount`, charged only on the
+    /// part above the free threshold
+    pub fn withdrawal_fee(&self, amount: u64) -> Result<u64> {
+        let Some(threshold) = self.free_withdrawal_threshold else {
+            return Ok(0);
+        };
+        let scaled = (amount.saturating_sub(threshold) as u128)
+            .checked_mul(self.fee_basis_points as u128)
+            .ok_or(PaymentError::ArithmeticOverflow)?;
+        // At most the withdrawal itself, since fee_basis_points <= 10000
+        Ok(self.round_fee(scaled) as u64)
+    }
+
+    /// Divides a fee scaled by basis points back down to lamports, in the
+    /// platform's rounding direction
+    fn round_fee(&self, scaled: u128) -> u128 {
+        if self.round_fee_up {
+            scaled.div_ceil(10000)
+        } else {
+            scaled / 10000
+        }
+    }
+}
+
+pub const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+// How long a subscription must stay inactive before its account can be closed
+pub const INACTIVE_CLOSE_DELAY: i64 = 30 * SECONDS_PER_DAY;
+
+impl PlatformStats {
+    pub fn record_tip(&mut self, amount: u64) -> Result<()> {
+        self.total_tip_volume = self
+            .total_tip_volume
+            .checked_add(amount)
+            .ok_or(PaymentError::ArithmeticOverflow)?;
+        Ok(())
+    }
+
+    pub fn record_subscription_payment(&mut self, amount: u64) -> Result<()> {
+        self.total_subscription_volume = self
+            .total_subscription_volume
+            .checked_add(amount)
+            .ok_or(PaymentError::ArithmeticOverflow)?;
+        Ok(())
+    }
+
+    pub fn record_vault(&mut self) -> Result<()> {
+        self.total_vaults = self
+            .total_vaults
+            .checked_add(1)
+            .ok_or(PaymentError::ArithmeticOverflow)?;
+        Ok(())
+    }
+}
+
+/// Extra vault and subscription seed for `namespace`. The default namespace
+/// adds nothing, so un-namespaced PDAs keep their original addresses.
+pub fn namespace_seed(namespace: &Option<[u8; 8]>) -> &[u8] {
+    namespace.as_ref().map_or(&[], |namespace| namespace.as_slice())
+}
+
+impl CreatorVault {
+    /// The original layout, creator through bump, before any fields were appended
+    pub const LEGACY_SIZE: usize = 65;
+
+    /// Reads a vault saved under an older, shorter layout. Every field
+    /// appended so far reads zeroed bytes as its default (None, false or 0),
+    /// and vaults from before namespaces are un-namespaced, so padding the
+    /// data out to full size is enough.
+    pub fn upgrade_layout(data: &[u8]) -> Result<Self> {
+        let size = 8 + Self::INIT_SPACE;
+        require!(
+            (Self::LEGACY_SIZE..size).contains(&data.len()),
+            PaymentError::InvalidVaultLayout
+        );
+        let mut padded = data.to_vec();
+        padded.resize(size, 0);
+        Self::try_deserialize(&mut &padded[..])
+    }
+
+    pub fn namespace_seed(&self) -> &[u8] {
+        namespace_seed(&self.namespace)
+    }
+
+    pub fn accepts_mint(&self, mint: &Pubkey) -> bool {
+        self.require_mint.is_none_or(|required| required == *mint)
+    }
+
+    /// Records `tipper` as the first supporter if nobody has tipped yet;
+    /// returns whether they were
+    pub fn record_first_supporter(&mut self, tipper: Pubkey) -> bool {
+        if self.first_supporter.is_some() {
+            return false;
+        }
+        self.first_supporter = Some(tipper);
+        true
+    }
+
+    /// Clamps `withdrawn` back to `total_earned` if the two have desynced,
+    /// returning the old `withdrawn` when a correction was made. Without this
+    /// an overdrawn vault would never have anything withdrawable again.
+    pub fn reconcile_withdrawn(&mut self) -> Option<u64> {
+        if self.withdrawn <= self.total_earned {
+            return None;
+        }
+        let previous = self.withdrawn;
+        self.withdrawn = self.total_earned;
+        Some(previous)
+    }
+
+    pub fn available(&self) -> u64 {
+        self.total_earned.saturating_sub(self.withdrawn)
+    }
+
+    /// Fails unless the stored bump is `canonical_bump`, the one found for the
+    /// vault's seeds, so payments can't be credited to a vault whose PDA
+    /// signer seeds don't reproduce its address
+    pub fn check_bump(&self, canonical_bump: u8) -> Result<()> {
+        require!(self.bump == canonical_bump, PaymentError::VaultBumpMismatch);
+        Ok(())
+    }
+
+    /// The vault's state as carried over to `new_creator`'s vault by
+    /// accept_vault. The verified badge was granted to the old wallet and the
+    /// subscriber index pages are seeded by the old vault, so neither moves.
+    pub fn handed_over(&self, new_creator: Pubkey, bump: u8) -> CreatorVault {
+        CreatorVault {
+            creator: new_creator,
+            bump,
+            verified: false,
+            subscriber_index_pages: 0,
+            pending_creator: None,
+            ..self.clone()
+        }
+    }
+
+    /// Counts a new subscription, refusing it once the vault's cap is reached
+    pub fn add_subscriber(&mut self) -> Result<()> {
+        require!(
+            self.max_subscribers == 0 || self.subscribers < self.max_subscribers,
+            PaymentError::SubscriberLimitReached
+        );
+        self.subscribers = self
+            .subscribers
+            .checked_add(1)
+            .ok_or(PaymentError::ArithmeticOverflow)?;
+        Ok(())
+    }
+
+    /// Counts a subscription ending. Every active subscription was counted
+    /// when it started, so a zero count means the books are out of sync and
+    /// is reported rather than saturated away.
+    pub fn remove_subscriber(&mut self) -> Result<()> {
+        self.subscribers = self
+            .subscribers
+            .checked_sub(1)
+            .ok_or(PaymentError::SubscriberCountUnderflow)?;
+        Ok(())
+    }
+}
+
+impl SubscriptionTiers {
+    pub fn amount_for(&self, tier_index: u8) -> Result<u64> {
+        self.tiers
+            .get(tier_index as usize)
+            .map(|tier| tier.amount_per_month)
+            .ok_or_else(|| error!(PaymentError::InvalidTierIndex))
+    }
+}
+
+impl SubscriberIndex {
+    pub fn add(&mut self, subscriber: Pubkey) -> Result<()> {
+        require!(
+            self.subscribers.len() < SUBSCRIBERS_PER_INDEX_PAGE,
+            PaymentError::SubscriberIndexPageFull
+        );
+        self.subscribers.push(subscriber);
+        Ok(())
+    }
+
+    pub fn remove(&mut self, subscriber: &Pubkey) -> Result<()> {
+        let position = self
+            .subscribers
+            .iter()
+            .position(|listed| listed == subscriber)
+            .ok_or(PaymentError::SubscriberNotIndexed)?;
+        self.subscribers.swap_remove(position);
+        Ok(())
+    }
+}
+
+impl TipRecord {
+    /// The original layout, from through bump, before `mint` was appended
+    pub const LEGACY_SIZE: usize = 122;
+
+    /// Reads a tip record saved under the original layout. Those were all
+    /// vault tips, which is what the zeroed `mint` reads as (None), so padding
+    /// the data out to full size is enough.
+    pub fn upgrade_layout(data: &[u8]) -> Result<Self> {
+        let size = 8 + Self::INIT_SPACE;
+        require!(
+            (Self::LEGACY_SIZE..size).contains(&data.len()),
+            PaymentError::InvalidTipRecordLayout
+        );
+        let mut padded = data.to_vec();
+        padded.resize(size, 0);
+        Self::try_deserialize(&mut &padded[..])
+    }
+}
+
+impl TipperStats {
+    /// Adds a tip of `amount`; also fills in the keys, which are still zeroed
+    /// when the account was just created
+    pub fn record_tip(
+        &mut self,
+        tipper: Pubkey,
+        creator: Pubkey,
+        amount: u64,
+        bump: u8,
+    ) -> Result<()> {
+        self.tipper = tipper;
+        self.creator = creator;
+        self.bump = bump;
+        self.count = self.count.checked_add(1).ok_or(PaymentError::ArithmeticOverflow)?;
+        self.total_amount = self
+            .total_amount
+            .checked_add(amount)
+            .ok_or(PaymentError::ArithmeticOverflow)?;
+        Ok(())
+    }
+}
+
+impl Subscription {
+    /// The original layout, subscriber through bump, before any fields were appended
+    pub const LEGACY_SIZE: usize = 98;
+
+    /// Reads a subscription saved under an older, shorter layout. Most
+    /// appended fields read zeroed bytes as their default (None, false or 0);
+    /// the rest are filled in from how the original program behaved: every
+    /// subscription renewed and had been paid for at least once.
+    pub fn upgrade_layout(data: &[u8]) -> Result<Self> {
+        let size = 8 + Self::INIT_SPACE;
+        require!(
+            (Self::LEGACY_SIZE..size).contains(&data.len()),
+            PaymentError::InvalidSubscriptionLayout
+        );
+        let mut padded = data.to_vec();
+        padded.resize(size, 0);
+        let mut subscription = Self::try_deserialize(&mut &padded[..])?;
+        subscription.auto_renew = true;
+        subscription.payment_count = subscription.payment_count.max(1);
+        Ok(subscription)
+    }
+
+    /// Whether the subscriber's minimum commitment, if any, has run out
+    pub fn commitment_met(&self, now: i64) -> bool {
+        self.min_duration_seconds
+            .is_none_or(|min_duration| now >= self.started_at.saturating_add(min_duration))
+    }
+
+    pub fn deactivate(&mut self, now: i64) {
+        self.is_active = false;
+        self.deactivated_at = now;
+    }
+
+    /// Whether the subscription has been inactive long enough for its account
+    /// to be closed
+    pub fn closable(&self, now: i64) -> bool {
+        !self.is_active && now.saturating_sub(self.deactivated_at) >= INACTIVE_CLOSE_DELAY
+    }
+
+    /// Whether the paid period that started at `last_payment` has run out
+    pub fn period_ended(&self, now: i64, period: i64) -> bool {
+        now.saturating_sub(self.last_payment) >= period
+    }
+
+    /// When the period that started at `last_payment` runs out and the next
+    /// payment falls due
+    pub fn next_payment_due(&self, period: i64) -> Result<i64> {
+        self.last_payment
+            .checked_add(period)
+            .ok_or(error!(PaymentError::ArithmeticOverflow))
+    }
+
+    /// Whether the subscriber should be treated as subscribed: active, and
+    /// either renewing or still inside the period they paid for. A subscription
+    /// left to lapse stops counting when its period ends, even before a crank
+    /// marks it inactive.
+    pub fn is_current(&self, now: i64, period: i64) -> bool {
+        self.is_active && (self.auto_renew || !self.period_ended(now, period))
+    }
+
+    /// Whether a crank processing this subscription at `now` would act on it.
+    /// Disputed subscriptions are skipped since processing them fails.
+    pub fn is_due(&self, now: i64, period: i64) -> bool {
+        self.is_active && !self.disputed && self.period_ended(now, period)
+    }
+
+    /// Records a payment at `now` and returns its sequence number
+    pub fn record_payment(&mut self, now: i64) -> Result<u32> {
+        self.payment_count = self
+            .payment_count
+            .checked_add(1)
+            .ok_or(PaymentError::ArithmeticOverflow)?;
+        self.last_payment = now;
+        Ok(self.payment_count)
+    }
+
+    /// Records a recurring payment, advancing `last_payment` by one period so
+    /// billing dates stay anchored even when the crank runs late. If the
+    /// subscription is more than a period behind, the anchor resets to `now`
+    /// rather than leaving it due again immediately.
+    pub fn record_renewal(&mut self, now: i64, period: i64) -> Result<u32> {
+        let next = self
+            .last_payment
+            .checked_add(period)
+            .ok_or(PaymentError::ArithmeticOverflow)?;
+        let anchor = match next.checked_add(period) {
+            Some(following) if following > now => next,
+            _ => now,
+        };
+        self.record_payment(anchor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn active_subscription() -> Subscription {
+        Subscription {
+            subscriber: Pubkey::new_unique(),
+            creator: Pubkey::new_unique(),
+            amount_per_month: 1_000,
+            last_payment: 0,
+            started_at: 0,
+            is_active: true,
+            bump: 255,
+            payment_count: 0,
+            mint: None,
+            auto_renew: true,
+            disputed: false,
+            reference: None,
+            deactivated_at: 0,
+            min_duration_seconds: None,
+            index_page: None,
+        }
+    }
+
+    fn empty_vault() -> CreatorVault {
+        CreatorVault {
+            creator: Pubkey::new_unique(),
+            total_earned: 0,
+            withdrawn: 0,
+            subscribers: 0,
+            bump: 255,
+            require_mint: None,
+            last_rollup_day: 0,
+            last_rollup_earned: 0,
+            namespace: None,
+            verified: false,
+            first_supporter: None,
+            subscriber_index_pages: 0,
+            max_subscribers: 0,
+            pending_creator: None,
+        }
+    }
+
+    fn platform_config() -> PlatformConfig {
+        PlatformConfig {
+            authority: Pubkey::new_unique(),
+            fee_basis_points: 200,
+            fee_recipient: Pubkey::new_unique(),
+            bump: 255,
+            max_amount_per_month: None,
+            free_withdrawal_threshold: None,
+            crank_reward_lamports: None,
+            round_fee_up: false,
+            track_stats: false,
+        }
+    }
+
+    #[test]
+    fn payment_sequence_is_contiguous() {
+        let mut subscription = active_subscription();
+
+        let sequence: Vec<u32> = (0..4)
+            .map(|month| subscription.record_payment(month * 30 * 24 * 60 * 60).unwrap())
+            .collect();
+        assert_eq!(sequence, vec![1, 2, 3, 4]);
+        assert_eq!(subscription.last_payment, 3 * 30 * 24 * 60 * 60);
+    }
+
+    #[test]
+    fn late_renewals_do_not_drift() {
+        const PERIOD: i64 = 30 * 24 * 60 * 60;
+        let mut subscription = Subscription {
+            payment_count: 1,
+            ..active_subscription()
+        };
+
+        for cycle in 1..=3 {
+            let late_crank = cycle * PERIOD + 5 * 24 * 60 * 60;
+            subscription.record_renewal(late_crank, PERIOD).unwrap();
+            assert_eq!(subscription.last_payment, cycle * PERIOD);
+        }
+        assert_eq!(subscription.payment_count, 4);
+    }
+
+    #[test]
+    fn stale_renewal_resets_anchor() {
+        const PERIOD: i64 = 30 * 24 * 60 * 60;
+        let mut subscription = Subscription {
+            payment_count: 1,
+            ..active_subscription()
+        };
+
+        let now = 5 * PERIOD + 1;
+        subscription.record_renewal(now, PERIOD).unwrap();
+        assert_eq!(subscription.last_payment, now);
+    }
+
+    #[test]
+    fn period_ends_exactly_one_period_after_payment() {
+        const PERIOD: i64 = 30 * 24 * 60 * 60;
+        let subscription = Subscription {
+            last_payment: 1_000,
+            started_at: 1_000,
+            payment_count: 1,
+            auto_renew: false,
+            ..active_subscription()
+        };
+
+        assert!(!subscription.period_ended(1_000 + PERIOD - 1, PERIOD));
+        assert!(subscription.period_ended(1_000 + PERIOD, PERIOD));
+        assert_eq!(subscription.next_payment_due(PERIOD).unwrap(), 1_000 + PERIOD);
+    }
+
+    #[test]
+    fn default_namespace_keeps_original_vault_address() {
+        let creator = Pubkey::new_unique();
+        let (original, _) = Pubkey::find_program_address(&[b"vault", creator.as_ref()], &crate::ID);
+        let (default, _) = Pubkey::find_program_address(
+            &[b"vault", creator.as_ref(), namespace_seed(&None)],
+            &crate::ID,
+        );
+        let (namespaced, _) = Pubkey::find_program_address(
+            &[b"vault", creator.as_ref(), namespace_seed(&Some(*b"brand-b\0"))],
+            &crate::ID,
+        );
+
+        assert_eq!(default, original);
+        assert_ne!(namespaced, original);
+    }
+
+    #[test]
+    fn crank_reward_only_charged_when_cranked() {
+        let config = PlatformConfig {
+            crank_reward_lamports: Some(5_000),
+            ..platform_config()
+        };
+
+        assert_eq!(config.renewal_cost(1_000_000, true).unwrap(), 1_005_000);
+        assert_eq!(config.renewal_cost(1_000_000, false).unwrap(), 1_000_000);
+        assert!(config.renewal_cost(u64::MAX, true).is_err());
+
+        let unrewarded = PlatformConfig { crank_reward_lamports: None, ..config };
+        assert_eq!(unrewarded.renewal_cost(1_000_000, true).unwrap(), 1_000_000);
+    }
+
+    #[test]
+    fn withdrawal_fee_applies_only_above_threshold() {
+        let config = PlatformConfig {
+            free_withdrawal_threshold: Some(1_000_000),
+            ..platform_config()
+        };
+
+        assert_eq!(config.withdrawal_fee(1_000_000).unwrap(), 0);
+        assert_eq!(config.withdrawal_fee(1_500_000).unwrap(), 10_000);
+        assert_eq!(
+            PlatformConfig { free_withdrawal_threshold: None, ..config }
+                .withdrawal_fee(u64::MAX)
+                .unwrap(),
+            0
+        );
+    }
+
+    #[test]
+    fn fee_rounding_direction_is_configurable() {
+        let floor = PlatformConfig {
+            free_withdrawal_threshold: Some(0),
+            ..platform_config()
+        };
+        let ceiling = PlatformConfig { round_fee_up: true, ..floor };
+
+        // 2% of 1_001 lamports is 20.02
+        assert_eq!(floor.platform_fee(1_001).unwrap(), 20);
+        assert_eq!(ceiling.platform_fee(1_001).unwrap(), 21);
+        assert_eq!(floor.withdrawal_fee(1_001).unwrap(), 20);
+        assert_eq!(ceiling.withdrawal_fee(1_001).unwrap(), 21);
+
+        // Exact fees don't change
+        assert_eq!(floor.platform_fee(1_000).unwrap(), 20);
+        assert_eq!(ceiling.platform_fee(1_000).unwrap(), 20);
+        assert_eq!(ceiling.platform_fee(0).unwrap(), 0);
+        assert!(ceiling.platform_fee(u64::MAX).is_err());
+    }
+
+    #[test]
+    fn inactive_subscription_closes_after_delay() {
+        let mut subscription = Subscription {
+            payment_count: 1,
+            ..active_subscription()
+        };
+        assert!(!subscription.closable(INACTIVE_CLOSE_DELAY * 10));
+
+        subscription.deactivate(1_000);
+        assert!(!subscription.closable(1_000 + INACTIVE_CLOSE_DELAY - 1));
+        assert!(subscription.closable(1_000 + INACTIVE_CLOSE_DELAY));
+    }
+
+    #[test]
+    fn due_only_when_active_undisputed_and_period_over() {
+        const PERIOD: i64 = 30 * 24 * 60 * 60;
+        let mut subscription = Subscription {
+            last_payment: 1_000,
+            started_at: 1_000,
+            payment_count: 1,
+            ..active_subscription()
+        };
+        assert!(!subscription.is_due(1_000 + PERIOD - 1, PERIOD));
+        assert!(subscription.is_due(1_000 + PERIOD, PERIOD));
+
+        subscription.disputed = true;
+        assert!(!subscription.is_due(1_000 + PERIOD, PERIOD));
+
+        subscription.disputed = false;
+        subscription.deactivate(1_000 + PERIOD);
+        assert!(!subscription.is_due(1_000 + 2 * PERIOD, PERIOD));
+    }
+
+    #[test]
+    fn only_the_first_tipper_is_the_first_supporter() {
+        let mut vault = empty_vault();
+        let (first, second) = (Pubkey::new_unique(), Pubkey::new_unique());
+
+        assert!(vault.record_first_supporter(first));
+        assert!(!vault.record_first_supporter(second));
+        assert!(!vault.record_first_supporter(first));
+        assert_eq!(vault.first_supporter, Some(first));
+    }
+
+    #[test]
+    fn removing_from_an_empty_vault_is_reported() {
+        let mut vault = CreatorVault {
+            subscribers: 1,
+            ..empty_vault()
+        };
+
+        vault.remove_subscriber().unwrap();
+        assert_eq!(vault.subscribers, 0);
+        assert_eq!(
+            vault.remove_subscriber().unwrap_err(),
+            PaymentError::SubscriberCountUnderflow.into()
+        );
+        assert_eq!(vault.subscribers, 0);
+    }
+
+    #[test]
+    fn subscriber_cap_is_enforced_at_the_boundary() {
+        let mut vault = CreatorVault {
+            max_subscribers: 2,
+            ..empty_vault()
+        };
+
+        vault.add_subscriber().unwrap();
+        vault.add_subscriber().unwrap();
+        assert_eq!(
+            vault.add_subscriber().unwrap_err(),
+            PaymentError::SubscriberLimitReached.into()
+        );
+        assert_eq!(vault.subscribers, 2);
+
+        // A cancellation frees a place
+        vault.remove_subscriber().unwrap();
+        vault.add_subscriber().unwrap();
+
+        vault.max_subscribers = 0;
+        vault.add_subscriber().unwrap();
+        assert_eq!(vault.subscribers, 3);
+    }
+
+    #[test]
+    fn handed_over_vault_keeps_its_books_but_not_the_old_wallets_badge() {
+        let (old_creator, new_creator) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let vault = CreatorVault {
+            creator: old_creator,
+            total_earned: 5_000,
+            withdrawn: 1_000,
+            bump: 254,
+            require_mint: Some(Pubkey::new_unique()),
+            last_rollup_day: 19_000,
+            last_rollup_earned: 4_000,
+            namespace: Some(*b"brand-a\0"),
+            verified: true,
+            first_supporter: Some(Pubkey::new_unique()),
+            subscriber_index_pages: 2,
+            max_subscribers: 100,
+            pending_creator: Some(new_creator),
+            ..empty_vault()
+        };
+
+        let moved = vault.handed_over(new_creator, 253);
+        assert_eq!((moved.creator, moved.bump), (new_creator, 253));
+        assert_eq!(moved.available(), 4_000);
+        assert_eq!(moved.require_mint, vault.require_mint);
+        assert_eq!(moved.last_rollup_day, 19_000);
+        assert_eq!(moved.namespace, vault.namespace);
+        assert_eq!(moved.first_supporter, vault.first_supporter);
+        assert_eq!(moved.max_subscribers, 100);
+        assert!(!moved.verified);
+        assert_eq!(moved.subscriber_index_pages, 0);
+        assert_eq!(moved.pending_creator, None);
+    }
+
+    #[test]
+    fn only_the_canonical_vault_bump_passes() {
+        let creator = Pubkey::new_unique();
+        let (_, canonical_bump) =
+            Pubkey::find_program_address(&[b"vault", creator.as_ref()], &crate::ID);
+        let mut vault = CreatorVault {
+            creator,
+            bump: canonical_bump,
+            ..empty_vault()
+        };
+        vault.check_bump(canonical_bump).unwrap();
+
+        vault.bump = canonical_bump.wrapping_sub(1);
+        assert_eq!(
+            vault.check_bump(canonical_bump).unwrap_err(),
+            PaymentError::VaultBumpMismatch.into()
+        );
+    }
+
+    #[test]
+    fn overdrawn_vault_recovers() {
+        let mut vault = CreatorVault {
+            total_earned: 100,
+            withdrawn: 150,
+            ..empty_vault()
+        };
+        assert_eq!(vault.available(), 0);
+
+        assert_eq!(vault.reconcile_withdrawn(), Some(150));
+        assert_eq!(vault.withdrawn, 100);
+        assert_eq!(vault.reconcile_withdrawn(), None);
+
+        // New earnings are withdrawable straight away rather than paying down the gap
+        vault.total_earned += 40;
+        assert_eq!(vault.available(), 40);
+    }
+
+    #[test]
+    fn commitment_blocks_until_minimum_duration() {
+        let mut subscription = Subscription {
+            last_payment: 1_000,
+            started_at: 1_000,
+            payment_count: 1,
+            ..active_subscription()
+        };
+        assert!(subscription.commitment_met(1_000));
+
+        subscription.min_duration_seconds = Some(90 * SECONDS_PER_DAY);
+        assert!(!subscription.commitment_met(1_000 + 90 * SECONDS_PER_DAY - 1));
+        assert!(subscription.commitment_met(1_000 + 90 * SECONDS_PER_DAY));
+    }
+
+    #[test]
+    fn subscriber_index_adds_and_removes() {
+        let mut index = SubscriberIndex {
+            vault: Pubkey::new_unique(),
+            page: 0,
+            subscribers: Vec::new(),
+            bump: 255,
+        };
+        let subscribers: Vec<Pubkey> =
+            (0..SUBSCRIBERS_PER_INDEX_PAGE).map(|_| Pubkey::new_unique()).collect();
+        for subscriber in &subscribers {
+            index.add(*subscriber).unwrap();
+        }
+        assert_eq!(
+            index.add(Pubkey::new_unique()).unwrap_err(),
+            PaymentError::SubscriberIndexPageFull.into()
+        );
+
+        index.remove(&subscribers[0]).unwrap();
+        assert_eq!(index.subscribers.len(), SUBSCRIBERS_PER_INDEX_PAGE - 1);
+        assert!(!index.subscribers.contains(&subscribers[0]));
+        assert_eq!(
+            index.remove(&subscribers[0]).unwrap_err(),
+            PaymentError::SubscriberNotIndexed.into()
+        );
+
+        // The freed slot is reusable
+        index.add(subscribers[0]).unwrap();
+        assert_eq!(index.subscribers.len(), SUBSCRIBERS_PER_INDEX_PAGE);
+    }
+
+    #[test]
+    fn tipper_stats_accumulate_tips() {
+        let (tipper, creator) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mut stats = TipperStats {
+            tipper: Pubkey::default(),
+            creator: Pubkey::default(),
+            count: 0,
+            total_amount: 0,
+            bump: 0,
+        };
+
+        stats.record_tip(tipper, creator, 1_000, 254).unwrap();
+        stats.record_tip(tipper, creator, 2_500, 254).unwrap();
+        assert_eq!((stats.tipper, stats.creator, stats.bump), (tipper, creator, 254));
+        assert_eq!((stats.count, stats.total_amount), (2, 3_500));
+
+        assert_eq!(
+            stats.record_tip(tipper, creator, u64::MAX, 254).unwrap_err(),
+            PaymentError::ArithmeticOverflow.into()
+        );
+    }
+
+    #[test]
+    fn lapsing_subscription_stops_counting_at_period_end() {
+        let mut subscription = Subscription {
+            last_payment: 1_000,
+            started_at: 1_000,
+            payment_count: 1,
+            ..active_subscription()
+        };
+        let period_end = 1_000 + SECONDS_PER_DAY;
+        // A renewing subscription stays current while the crank catches up
+        assert!(subscription.is_current(period_end, SECONDS_PER_DAY));
+
+        subscription.auto_renew = false;
+        assert!(subscription.is_current(period_end - 1, SECONDS_PER_DAY));
+        assert!(!subscription.is_current(period_end, SECONDS_PER_DAY));
+
+        subscription.auto_renew = true;
+        subscription.deactivate(period_end);
+        assert!(!subscription.is_current(period_end, SECONDS_PER_DAY));
+    }
+
+    #[test]
+    fn upgrades_a_legacy_config() {
+        let legacy = PlatformConfig {
+            bump: 254,
+            ..platform_config()
+        };
+        let mut data = Vec::new();
+        legacy.try_serialize(&mut data).unwrap();
+        data.truncate(PlatformConfig::LEGACY_SIZE);
+
+        let upgraded = PlatformConfig::upgrade_layout(&data).unwrap();
+        assert_eq!(upgraded.authority, legacy.authority);
+        assert_eq!(upgraded.fee_basis_points, 200);
+        assert_eq!(upgraded.fee_recipient, legacy.fee_recipient);
+        assert_eq!(upgraded.bump, 254);
+        assert_eq!(upgraded.max_amount_per_month, None);
+        assert_eq!(upgraded.free_withdrawal_threshold, None);
+        assert_eq!(upgraded.crank_reward_lamports, None);
+        assert!(!upgraded.round_fee_up);
+        assert!(!upgraded.track_stats);
+
+        let mut current = Vec::new();
+        upgraded.try_serialize(&mut current).unwrap();
+        current.resize(8 + PlatformConfig::INIT_SPACE, 0);
+        assert!(PlatformConfig::upgrade_layout(&current).is_err());
+        assert!(PlatformConfig::upgrade_layout(&data[..PlatformConfig::LEGACY_SIZE - 1]).is_err());
+    }
+
+    #[test]
+    fn upgrades_a_legacy_vault() {
+        let legacy = CreatorVault {
+            total_earned: 5_000,
+            withdrawn: 1_000,
+            subscribers: 3,
+            bump: 254,
+            ..empty_vault()
+        };
+        let mut data = Vec::new();
+        legacy.try_serialize(&mut data).unwrap();
+        data.truncate(CreatorVault::LEGACY_SIZE);
+
+        let upgraded = CreatorVault::upgrade_layout(&data).unwrap();
+        assert_eq!(upgraded.creator, legacy.creator);
+        assert_eq!(upgraded.available(), 4_000);
+        assert_eq!(upgraded.subscribers, 3);
+        assert_eq!(upgraded.bump, 254);
+        assert_eq!(upgraded.namespace, None);
+        assert!(!upgraded.verified);
+        assert_eq!(upgraded.max_subscribers, 0);
+        assert_eq!(upgraded.pending_creator, None);
+
+        let mut current = Vec::new();
+        upgraded.try_serialize(&mut current).unwrap();
+        current.resize(8 + CreatorVault::INIT_SPACE, 0);
+        assert!(CreatorVault::upgrade_layout(&current).is_err());
+        assert!(CreatorVault::upgrade_layout(&data[..CreatorVault::LEGACY_SIZE - 1]).is_err());
+    }
+
+    #[test]
+    fn stats_account_required_only_while_tracking() {
+        let config = platform_config();
+        assert!(config.check_stats_passed(&None::<()>).is_ok());
+
+        let tracking = PlatformConfig { track_stats: true, ..config };
+        assert!(tracking.check_stats_passed(&None::<()>).is_err());
+        assert!(tracking.check_stats_passed(&Some(())).is_ok());
+    }
+
+    #[test]
+    fn upgrades_a_legacy_subscription() {
+        // A legacy subscription's appended fields are all zero bytes
+        let legacy = Subscription {
+            last_payment: 1_000,
+            started_at: 1_000,
+            payment_count: 0,
+            auto_renew: false,
+            ..active_subscription()
+        };
+        let mut data = Vec::new();
+        legacy.try_serialize(&mut data).unwrap();
+        data.truncate(Subscription::LEGACY_SIZE);
+
+        let upgraded = Subscription::upgrade_layout(&data).unwrap();
+        assert_eq!(upgraded.subscriber, legacy.subscriber);
+        assert_eq!(upgraded.creator, legacy.creator);
+        assert_eq!(upgraded.amount_per_month, 1_000);
+        assert_eq!((upgraded.last_payment, upgraded.started_at), (1_000, 1_000));
+        assert!(upgraded.is_active);
+        assert_eq!(upgraded.bump, 255);
+        assert!(upgraded.auto_renew);
+        assert_eq!(upgraded.payment_count, 1);
+        assert_eq!(upgraded.mint, None);
+        assert!(!upgraded.disputed);
+        assert_eq!(upgraded.min_duration_seconds, None);
+        assert_eq!(upgraded.index_page, None);
+
+        let mut current = Vec::new();
+        upgraded.try_serialize(&mut current).unwrap();
+        current.resize(8 + Subscription::INIT_SPACE, 0);
+        assert!(Subscription::upgrade_layout(&current).is_err());
+        assert!(Subscription::upgrade_layout(&data[..Subscription::LEGACY_SIZE - 1]).is_err());
+    }
+
+    #[test]
+    fn upgrades_a_legacy_tip_record() {
+        let legacy = TipRecord {
+            from: Pubkey::new_unique(),
+            to: Pubkey::new_unique(),
+            amount: 5_000,
+            // A post fills the original layout, leaving no byte for `mint`
+            post: Some(Pubkey::new_unique()),
+            timestamp: 1_000,
+            bump: 254,
+            mint: None,
+        };
+        let mut data = Vec::new();
+        legacy.try_serialize(&mut data).unwrap();
+        data.truncate(TipRecord::LEGACY_SIZE);
+        assert!(TipRecord::try_deserialize(&mut &data[..]).is_err());
+
+        let upgraded = TipRecord::upgrade_layout(&data).unwrap();
+        assert_eq!((upgraded.from, upgraded.to), (legacy.from, legacy.to));
+        assert_eq!(upgraded.amount, 5_000);
+        assert_eq!(upgraded.post, legacy.post);
+        assert_eq!((upgraded.timestamp, upgraded.bump), (1_000, 254));
+        assert_eq!(upgraded.mint, None);
+
+        let mut current = Vec::new();
+        upgraded.try_serialize(&mut current).unwrap();
+        current.resize(8 + TipRecord::INIT_SPACE, 0);
+        assert!(TipRecord::upgrade_layout(&current).is_err());
+        assert!(TipRecord::upgrade_layout(&data[..TipRecord::LEGACY_SIZE - 1]).is_err());
+    }
 }
@@ -0,0 +1,84 @@
+use anchor_lang::prelude::*;
+use crate::error::PaymentError;
+
+/// Moves `amount` lamports out of a program-owned account by adjusting
+/// balances directly; fails rather than leaving `from` below rent exemption
+pub fn transfer_from_pda(from: &AccountInfo, to: &AccountInfo, amount: u64) -> Result<()> {
+    transfer_from_pda_with_rent(from, to, amount, &Rent::get()?)
+}
+
+fn transfer_from_pda_with_rent(
+    from: &AccountInfo,
+    to: &AccountInfo,
+    amount: u64,
+    rent: &Rent,
+) -> Result<()> {
+    let min_balance = rent.minimum_balance(from.data_len());
+    let spendable = from
+        .lamports()
+        .checked_sub(min_balance)
+        .ok_or(PaymentError::InsufficientFunds)?;
+    require!(amount <= spendable, PaymentError::InsufficientFunds);
+
+    let to_balance = to
+        .lamports()
+        .checked_add(amount)
+        .ok_or(PaymentError::ArithmeticOverflow)?;
+
+    **from.try_borrow_mut_lamports()? -= amount;
+    **to.try_borrow_mut_lamports()? = to_balance;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DATA_LEN: usize = 64;
+
+    fn transfer(from_lamports: u64, amount: u64) -> (Result<()>, u64, u64) {
+        let rent = Rent::default();
+        let owner = crate::ID;
+        let (from_key, to_key) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let (mut from_balance, mut to_balance) = (from_lamports, 0);
+        let (mut from_data, mut to_data) = (vec![0u8; DATA_LEN], vec![]);
+        let from = AccountInfo::new(
+            &from_key, false, true, &mut from_balance, &mut from_data, &owner, false, 0,
+        );
+        let to = AccountInfo::new(
+            &to_key, false, true, &mut to_balance, &mut to_data, &owner, false, 0,
+        );
+
+        let result = transfer_from_pda_with_rent(&from, &to, amount, &rent);
+        let balances = (from.lamports(), to.lamports());
+        (result, balances.0, balances.1)
+    }
+
+    fn rent_minimum() -> u64 {
+        Rent::default().minimum_balance(DATA_LEN)
+    }
+
+    #[test]
+    fn transfers_lamports_above_rent_exemption() {
+        let (result, from, to) = transfer(rent_minimum() + 1_000, 1_000);
+        assert!(result.is_ok());
+        assert_eq!(from, rent_minimum());
+        assert_eq!(to, 1_000);
+    }
+
+    #[test]
+    fn rejects_transfer_below_rent_exemption() {
+        let (result, from, to) = transfer(rent_minimum() + 1_000, 1_001);
+        assert_eq!(result.unwrap_err(), PaymentError::InsufficientFunds.into());
+        assert_eq!(from, rent_minimum() + 1_000);
+        assert_eq!(to, 0);
+    }
+
+    #[test]
+    fn rejects_account_already_below_rent_exemption() {
+        let (result, from, _) = transfer(rent_minimum() - 1, 0);
+        assert_eq!(result.unwrap_err(), PaymentError::InsufficientFunds.into());
+        assert_eq!(from, rent_minimum() - 1);
+    }
+}
@@ -0,0 +1,174 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::program::invoke;
+use solana_keccak_hasher::hashv;
+
+pub const BUBBLEGUM_PROGRAM_ID: Pubkey = pubkey!("BGUMAp9Gq7iTEuizy4pqaxsTyUCBK68MDfK752saRPUY");
+pub const COMPRESSION_PROGRAM_ID: Pubkey = pubkey!("cmtDvXumGCrqC1Age74AVPhSRVXJMd8PJS91L8KbNCK");
+
+// Anchor discriminator of spl-account-compression's `verify_leaf`
+const VERIFY_LEAF_DISCRIMINATOR: [u8; 8] = [124, 220, 22, 223, 104, 10, 250, 224];
+// ConcurrentMerkleTreeHeader: account_type (1) + version (1) + max_buffer_size (4) + max_depth (4)
+const TREE_AUTHORITY_OFFSET: usize = 10;
+const TREE_ACCOUNT_TYPE_CONCURRENT: u8 = 1;
+const TREE_HEADER_VERSION_V1: u8 = 0;
+const LEAF_SCHEMA_V1: u8 = 1;
+
+// Mirrors of Bubblegum's `MetadataArgs` types. Field order matters: the data
+// hash is computed over their borsh serialization.
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub enum CnftTokenStandard {
+    NonFungible,
+    FungibleAsset,
+    Fungible,
+    NonFungibleEdition,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub enum CnftUseMethod {
+    Burn,
+    Multiple,
+    Single,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub struct CnftUses {
+    pub use_method: CnftUseMethod,
+    pub remaining: u64,
+    pub total: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub enum CnftTokenProgramVersion {
+    Original,
+    Token2022,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub struct CnftCollection {
+    pub verified: bool,
+    pub key: Pubkey,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub struct CnftCreator {
+    pub address: Pubkey,
+    pub verified: bool,
+    pub share: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub struct CnftMetadata {
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub seller_fee_basis_points: u16,
+    pub primary_sale_happened: bool,
+    pub is_mutable: bool,
+    pub edition_nonce: Option<u8>,
+    pub token_standard: Option<CnftTokenStandard>,
+    pub collection: Option<CnftCollection>,
+    pub uses: Option<CnftUses>,
+    pub token_program_version: CnftTokenProgramVersion,
+    pub creators: Vec<CnftCreator>,
+}
+
+impl CnftMetadata {
+    pub fn data_hash(&self) -> Result<[u8; 32]> {
+        let metadata_hash = hashv(&[&self.try_to_vec()?]);
+        Ok(hashv(&[
+            metadata_hash.as_ref(),
+            &self.seller_fee_basis_points.to_le_bytes(),
+        ])
+        .to_bytes())
+    }
+
+    pub fn creator_hash(&self) -> [u8; 32] {
+        let creator_data: Vec<Vec<u8>> = self
+            .creators
+            .iter()
+            .map(|c| [c.address.as_ref(), &[c.verified as u8], &[c.share]].concat())
+            .collect();
+        let slices: Vec<&[u8]> = creator_data.iter().map(|c| c.as_slice()).collect();
+        hashv(&slices).to_bytes()
+    }
+}
+
+pub fn asset_id(merkle_tree: &Pubkey, nonce: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"asset", merkle_tree.as_ref(), &nonce.to_le_bytes()],
+        &BUBBLEGUM_PROGRAM_ID,
+    )
+    .0
+}
+
+/// Bubblegum V1 leaf hash for an asset held by `owner`
+pub fn leaf_hash(
+    asset_id: &Pubkey,
+    owner: &Pubkey,
+    delegate: &Pubkey,
+    nonce: u64,
+    data_hash: &[u8; 32],
+    creator_hash: &[u8; 32],
+) -> [u8; 32] {
+    hashv(&[
+        &[LEAF_SCHEMA_V1],
+        asset_id.as_ref(),
+        owner.as_ref(),
+        delegate.as_ref(),
+        &nonce.to_le_bytes(),
+        data_hash,
+        creator_hash,
+    ])
+    .to_bytes()
+}
+
+/// Whether `merkle_tree` was created through Bubblegum, i.e. its authority is
+/// Bubblegum's tree config PDA. Trees created directly against account
+/// compression could contain arbitrary leaves.
+pub fn is_bubblegum_tree(merkle_tree: &AccountInfo) -> bool {
+    let data = merkle_tree.data.borrow();
+    if data.len() < TREE_AUTHORITY_OFFSET + 32
+        || data[0] != TREE_ACCOUNT_TYPE_CONCURRENT
+        || data[1] != TREE_HEADER_VERSION_V1
+    {
+        return false;
+    }
+    let (tree_config, _) =
+        Pubkey::find_program_address(&[merkle_tree.key.as_ref()], &BUBBLEGUM_PROGRAM_ID);
+    data[TREE_AUTHORITY_OFFSET..TREE_AUTHORITY_OFFSET + 32] == tree_config.to_bytes()
+}
+
+/// CPI into account compression's `verify_leaf`, which fails unless `leaf`
+/// sits at `index` in a tree whose recent root is `root`
+pub fn verify_leaf<'info>(
+    compression_program: &AccountInfo<'info>,
+    merkle_tree: &AccountInfo<'info>,
+    proof: &[AccountInfo<'info>],
+    root: [u8; 32],
+    leaf: [u8; 32],
+    index: u32,
+) -> Result<()> {
+    let mut accounts = vec![AccountMeta::new_readonly(merkle_tree.key(), false)];
+    accounts.extend(proof.iter().map(|node| AccountMeta::new_readonly(node.key(), false)));
+
+    let mut data = VERIFY_LEAF_DISCRIMINATOR.to_vec();
+    data.extend_from_slice(&root);
+    data.extend_from_slice(&leaf);
+    data.extend_from_slice(&index.to_le_bytes());
+
+    let mut account_infos = vec![merkle_tree.clone(), compression_program.clone()];
+    account_infos.extend(proof.iter().cloned());
+
+    invoke(
+        &Instruction {
+            program_id: COMPRESSION_PROGRAM_ID,
+            accounts,
+            data,
+        },
+        &account_infos,
+    )?;
+
+    Ok(())
+}
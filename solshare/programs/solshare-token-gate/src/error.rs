@@ -26,4 +26,78 @@ pub enum TokenGateError {
     TokenAccountOwnerMismatch,
     #[msg("Token account mint mismatch")]
     TokenAccountMintMismatch,
+    #[msg("Too many token requirements for a single gate")]
+    TooManyTokenRequirements,
+    #[msg("Each token requirement must use a distinct mint")]
+    DuplicateTokenRequirement,
+    #[msg("Allow list is full")]
+    AllowListFull,
+    #[msg("Wallet is already on the allow list")]
+    AlreadyAllowListed,
+    #[msg("Wallet is not on the allow list")]
+    NotAllowListed,
+    #[msg("Not enough NFTs from the required collection")]
+    InsufficientNftCount,
+    #[msg("Merkle tree is not a Bubblegum compressed NFT tree")]
+    InvalidMerkleTree,
+    #[msg("Too many gates in a single batch")]
+    BatchTooLarge,
+    #[msg("Unrecognized account in access batch")]
+    InvalidBatchAccount,
+    #[msg("Arithmetic overflow")]
+    ArithmeticOverflow,
+    #[msg("Insufficient SOL balance for access")]
+    InsufficientSolBalance,
+    #[msg("Viewer must be a system account")]
+    InvalidViewerAccount,
+    #[msg("Too many tiers for a single gate")]
+    TooManyTiers,
+    #[msg("Tier levels must be non-zero and distinct")]
+    InvalidTierLevel,
+    #[msg("Account is not a tip record from the payment program")]
+    InvalidTipRecord,
+    #[msg("Tip was not sent by the viewer to the post's creator")]
+    TipMismatch,
+    #[msg("Tip amount is below the gate minimum")]
+    InsufficientTipAmount,
+    #[msg("Tip is too old to grant access")]
+    TipExpired,
+    #[msg("Account is not the viewer's subscription to this creator")]
+    InvalidSubscription,
+    #[msg("Subscription is not active")]
+    SubscriptionInactive,
+    #[msg("Too many NFT collections for a single gate")]
+    TooManyNftCollections,
+    #[msg("Each NFT collection must be listed once")]
+    DuplicateNftCollection,
+    #[msg("Access logs can only be written for the current day")]
+    InvalidLogDay,
+    #[msg("No Metaplex metadata found for this NFT mint")]
+    NftMetadataMissing,
+    #[msg("NFT's collection has not been verified by the collection authority")]
+    NftCollectionUnverified,
+    #[msg("NFT is not from a collection this gate accepts")]
+    NftWrongCollection,
+    #[msg("Token account holds none of this NFT")]
+    NftZeroBalance,
+    #[msg("NFT is not the mint this gate requires")]
+    NftWrongMint,
+    #[msg("NFT update authority does not match this gate")]
+    NftWrongUpdateAuthority,
+    #[msg("NFT update authority is not a verified creator")]
+    NftUpdateAuthorityUnverified,
+    #[msg("Post already has access requirements")]
+    PostAlreadyGated,
+    #[msg("Post has no gate of its own; pass the creator's default gate, the post and its access PDA")]
+    GateDefaultRequired,
+    #[msg("Post is not a social program post by this creator")]
+    InvalidPostAccount,
+    #[msg("Payment program account is required to check a subscription")]
+    PaymentProgramRequired,
+    #[msg("NFT mint has decimals, so it is a fungible token rather than an NFT")]
+    NftFungibleMint,
+    #[msg("Account is not an access control in an older layout")]
+    InvalidAccessControlLayout,
+    #[msg("Each token account can only be counted once")]
+    DuplicateTokenAccount,
 }
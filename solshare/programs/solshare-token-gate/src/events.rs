@@ -1,8 +1,19 @@
 use anchor_lang::prelude::*;
+use crate::state::GateType;
+
+#[event]
+pub struct GateConfigUpdated {
+    pub admin: Pubkey,
+    pub social_program: Pubkey,
+    pub timestamp: i64,
+}
 
 #[event]
 pub struct AccessControlCreated {
     pub post: Pubkey,
+    // Index of the post in the creator's social program posts, for indexers
+    // keyed on it rather than the post address
+    pub post_index: u64,
     pub creator: Pubkey,
     pub required_token: Option<Pubkey>,
     pub minimum_balance: u64,
@@ -10,6 +21,14 @@ pub struct AccessControlCreated {
     pub timestamp: i64,
 }
 
+#[event]
+pub struct CreatorGateDefaultSet {
+    pub creator: Pubkey,
+    pub gate_type: GateType,
+    pub minimum_sol_lamports: u64,
+    pub timestamp: i64,
+}
+
 #[event]
 pub struct AccessControlUpdated {
     pub post: Pubkey,
@@ -19,6 +38,28 @@ pub struct AccessControlUpdated {
     pub timestamp: i64,
 }
 
+#[event]
+pub struct AccessControlRemoved {
+    pub post: Pubkey,
+    pub creator: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AllowListUpdated {
+    pub post: Pubkey,
+    pub wallet: Pubkey,
+    pub added: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VerifierUpdated {
+    pub post: Pubkey,
+    pub verifier: Option<Pubkey>,
+    pub timestamp: i64,
+}
+
 #[event]
 pub struct AccessVerified {
     pub user: Pubkey,
@@ -27,9 +68,51 @@ pub struct AccessVerified {
     pub timestamp: i64,
 }
 
+#[event]
+pub struct AccessGranted {
+    pub user: Pubkey,
+    pub post: Pubkey,
+    pub gate_type: GateType,
+    pub timestamp: i64,
+}
+
+// Only check_access_detailed emits this, as it reports denials in its return
+// value. check_access and the verify_* instructions deny by failing, and a
+// failed transaction keeps none of its events.
+#[event]
+pub struct AccessDenied {
+    pub user: Pubkey,
+    pub post: Pubkey,
+    pub gate_type: GateType,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AccessLogged {
+    pub viewer: Pubkey,
+    pub post: Pubkey,
+    pub day: i64,
+    pub unlock_count: u32,
+    pub timestamp: i64,
+}
+
 #[event]
 pub struct AccessRevoked {
     pub user: Pubkey,
     pub post: Pubkey,
     pub timestamp: i64,
 }
+
+#[event]
+pub struct AccessPassesRevoked {
+    pub post: Pubkey,
+    pub gate_epoch: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AccessControlMigrated {
+    pub post: Pubkey,
+    pub old_size: u32,
+    pub new_size: u32,
+}
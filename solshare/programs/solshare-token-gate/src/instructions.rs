@@ -1,9 +1,71 @@
+pub mod initialize_gate_config;
+pub mod update_gate_config;
 pub mod set_access_requirements;
+pub mod set_access_requirements_batch;
+pub mod set_sol_access_requirements;
+pub mod set_tip_access_requirements;
+pub mod set_subscription_access_requirements;
+pub mod set_specific_nft_access_requirements;
+pub mod set_update_authority_access_requirements;
 pub mod verify_token_access;
+pub mod verify_token_access_delegated;
 pub mod verify_nft_access;
+pub mod verify_cnft_access;
+pub mod verify_tip_access;
 pub mod check_access;
+pub mod verify_and_log;
+pub mod check_access_detailed;
+pub mod check_access_batch;
+pub mod has_valid_pass;
+pub mod set_pass_ttl;
+pub mod set_token_counting;
+pub mod remove_access_requirements;
+pub mod set_token_requirements;
+pub mod add_to_allowlist;
+pub mod remove_from_allowlist;
+pub mod set_minimum_nft_count;
+pub mod set_nft_collections;
+pub mod update_access_requirements;
+pub mod set_verifier;
+pub mod set_tiers;
+pub mod check_tier;
+pub mod token_access_shortfall;
+pub mod set_gate_default;
+pub mod revoke_access_pass;
+pub mod migrate_access_control;
 
+pub use initialize_gate_config::*;
+pub use update_gate_config::*;
 pub use set_access_requirements::*;
+pub use set_access_requirements_batch::*;
+pub use set_sol_access_requirements::*;
+pub use set_tip_access_requirements::*;
+pub use set_subscription_access_requirements::*;
+pub use set_specific_nft_access_requirements::*;
+pub use set_update_authority_access_requirements::*;
 pub use verify_token_access::*;
+pub use verify_token_access_delegated::*;
 pub use verify_nft_access::*;
+pub use verify_cnft_access::*;
+pub use verify_tip_access::*;
 pub use check_access::*;
+pub use verify_and_log::*;
+pub use check_access_detailed::*;
+pub use check_access_batch::*;
+pub use has_valid_pass::*;
+pub use set_pass_ttl::*;
+pub use set_token_counting::*;
+pub use remove_access_requirements::*;
+pub use set_token_requirements::*;
+pub use add_to_allowlist::*;
+pub use remove_from_allowlist::*;
+pub use set_minimum_nft_count::*;
+pub use set_nft_collections::*;
+pub use update_access_requirements::*;
+pub use set_verifier::*;
+pub use set_tiers::*;
+pub use check_tier::*;
+pub use token_access_shortfall::*;
+pub use set_gate_default::*;
+pub use revoke_access_pass::*;
+pub use migrate_access_control::*;
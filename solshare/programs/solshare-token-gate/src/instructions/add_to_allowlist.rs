@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+use crate::state::{AccessControl, MAX_ALLOW_LIST};
+use crate::error::TokenGateError;
+use crate::events::AllowListUpdated;
+
+#[derive(Accounts)]
+pub struct AddToAllowlist<'info> {
+    #[account(
+        mut,
+        seeds = [b"access", access_control.post.as_ref()],
+        bump = access_control.bump,
+        has_one = creator @ TokenGateError::Unauthorized
+    )]
+    pub access_control: Account<'info, AccessControl>,
+
+    pub creator: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<AddToAllowlist>, wallet: Pubkey) -> Result<()> {
+    let access_control = &mut ctx.accounts.access_control;
+    let clock = Clock::get()?;
+
+    require!(
+        !access_control.is_allow_listed(&wallet),
+        TokenGateError::AlreadyAllowListed
+    );
+    require!(
+        access_control.allow_list.len() < MAX_ALLOW_LIST,
+        TokenGateError::AllowListFull
+    );
+
+    access_control.allow_list.push(wallet);
+
+    emit!(AllowListUpdated {
+        post: access_control.post,
+        wallet,
+        added: true,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -1,52 +1,179 @@
 use anchor_lang::prelude::*;
-use crate::state::{AccessControl, AccessVerification, GateType};
+use solshare_payment::cpi::accounts::IsSubscriptionActive;
+use solshare_payment::program::SolsharePayment;
+use crate::state::{
+    system_account_lamports, AccessControl, AccessDenyReason, AccessVerification, CreatorGateDefault,
+};
 use crate::error::TokenGateError;
+use crate::events::AccessGranted;
+use crate::social::post_creator;
 
 #[derive(Accounts)]
 pub struct CheckAccess<'info> {
+    // None when the post has no gate of its own and the creator's default applies
     #[account(
         seeds = [b"access", access_control.post.as_ref()],
         bump = access_control.bump
     )]
-    pub access_control: Account<'info, AccessControl>,
+    pub access_control: Option<Account<'info, AccessControl>>,
     
+    // Not required for allow-listed wallets; must be for the gated post, checked in the handler
     #[account(
-        seeds = [b"verification", user.key().as_ref(), access_control.post.as_ref()],
+        seeds = [b"verification", user.key().as_ref(), verification.post.as_ref()],
         bump = verification.bump,
         has_one = user
     )]
-    pub verification: Account<'info, AccessVerification>,
+    pub verification: Option<Account<'info, AccessVerification>>,
+
+    /// CHECK: Viewer's payment subscription, validated by the payment program;
+    /// only needed for subscription gates
+    pub subscription: Option<UncheckedAccount<'info>>,
+
+    // Asked over CPI whether the subscription is active; needed with `subscription`
+    pub payment_program: Option<Program<'info, SolsharePayment>>,
     
     pub user: Signer<'info>,
+
+    // The remaining accounts are only needed when access_control is None
+    #[account(
+        seeds = [b"gate_default", gate_default.creator.as_ref()],
+        bump = gate_default.bump
+    )]
+    pub gate_default: Option<Account<'info, CreatorGateDefault>>,
+
+    /// CHECK: The social program post being viewed, validated by post_creator
+    pub post: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: The post's AccessControl PDA, which must not exist for the
+    /// default to apply
+    pub post_access_control: Option<UncheckedAccount<'info>>,
 }
 
 pub fn handler(ctx: Context<CheckAccess>) -> Result<bool> {
-    let access_control = &ctx.accounts.access_control;
-    let verification = &ctx.accounts.verification;
-    let clock = Clock::get()?;
-
-    // Explicitly verify based on gate type for defense in depth
-    // This ensures GateType::Both requires BOTH verifications, not just one
-    match access_control.gate_type {
-        GateType::Token => {
-            require!(verification.token_verified, TokenGateError::NotVerified);
+    let accounts = &ctx.accounts;
+    let inherited;
+    let access_control = match &accounts.access_control {
+        Some(access_control) => access_control,
+        None => {
+            inherited = inherit_gate_default(
+                accounts.gate_default.as_deref(),
+                accounts.post.as_deref(),
+                accounts.post_access_control.as_deref(),
+            )?;
+            &inherited
         }
-        GateType::Nft => {
-            require!(verification.nft_verified, TokenGateError::NotVerified);
-        }
-        GateType::Both => {
-            require!(verification.token_verified, TokenGateError::NotVerified);
-            require!(verification.nft_verified, TokenGateError::NotVerified);
-        }
-    }
+    };
 
-    // Check expiration if set
-    if let Some(expires_at) = verification.expires_at {
-        require!(
-            clock.unix_timestamp < expires_at,
-            TokenGateError::VerificationExpired
-        );
+    let verification = accounts.verification.as_deref();
+    if let Some(verification) = verification {
+        require_keys_eq!(verification.post, access_control.post, ErrorCode::ConstraintSeeds);
     }
 
+    enforce_access(
+        access_control,
+        &accounts.user,
+        accounts.subscription.as_deref(),
+        accounts.payment_program.as_ref().map(|program| program.as_ref()),
+        verification,
+        Clock::get()?.unix_timestamp,
+    )?;
     Ok(true)
 }
+
+/// Resolves the creator default gate for a post, which only applies if the
+/// post belongs to that creator and has no AccessControl of its own
+fn inherit_gate_default(
+    gate_default: Option<&CreatorGateDefault>,
+    post: Option<&AccountInfo>,
+    post_access_control: Option<&AccountInfo>,
+) -> Result<AccessControl> {
+    let (Some(gate_default), Some(post), Some(post_access_control)) =
+        (gate_default, post, post_access_control)
+    else {
+        return err!(TokenGateError::GateDefaultRequired);
+    };
+
+    require_keys_eq!(
+        post_creator(post, &gate_default.social_program)?,
+        gate_default.creator,
+        TokenGateError::InvalidPostAccount
+    );
+
+    let (expected, _) = Pubkey::find_program_address(&[b"access", post.key.as_ref()], &crate::ID);
+    require_keys_eq!(post_access_control.key(), expected, TokenGateError::GateDefaultRequired);
+    // A post-specific gate always takes precedence over the default
+    require!(
+        post_access_control.owner != &crate::ID,
+        TokenGateError::PostAlreadyGated
+    );
+
+    Ok(gate_default.gate_for(post.key()))
+}
+
+/// Runs the gate check for `user`, emitting AccessGranted or mapping a denial
+/// to its error. A denial rolls back any event, so check_access_detailed
+/// records those.
+pub(crate) fn enforce_access<'info>(
+    access_control: &AccessControl,
+    user: &AccountInfo,
+    subscription: Option<&AccountInfo<'info>>,
+    payment_program: Option<&AccountInfo<'info>>,
+    verification: Option<&AccessVerification>,
+    now: i64,
+) -> Result<()> {
+    let user_key = user.key();
+
+    let subscription_active = subscription
+        .map(|subscription| {
+            subscription_active(subscription, payment_program, user_key, access_control.creator)
+        })
+        .transpose()?;
+
+    let reason = access_control.evaluate_access(
+        &user_key,
+        system_account_lamports(user),
+        subscription_active,
+        verification,
+        now,
+    );
+
+    if reason == AccessDenyReason::None {
+        emit!(AccessGranted {
+            user: user_key,
+            post: access_control.post,
+            gate_type: access_control.gate_type,
+            timestamp: now,
+        });
+        return Ok(());
+    }
+
+    match reason {
+        AccessDenyReason::OutsideTimeWindow => err!(TokenGateError::VerificationExpired),
+        AccessDenyReason::InsufficientBalance { .. } => err!(TokenGateError::InsufficientSolBalance),
+        AccessDenyReason::NotSystemAccount => err!(TokenGateError::InvalidViewerAccount),
+        AccessDenyReason::SubscriptionInactive => err!(TokenGateError::SubscriptionInactive),
+        _ => err!(TokenGateError::NotVerified),
+    }
+}
+
+/// Asks the payment program whether `subscriber` holds an active subscription
+/// to `creator`, so subscription gates follow its rules rather than a copy
+pub(crate) fn subscription_active<'info>(
+    subscription: &AccountInfo<'info>,
+    payment_program: Option<&AccountInfo<'info>>,
+    subscriber: Pubkey,
+    creator: Pubkey,
+) -> Result<bool> {
+    let payment_program = payment_program.ok_or(TokenGateError::PaymentProgramRequired)?;
+    let active = solshare_payment::cpi::is_subscription_active(
+        CpiContext::new(
+            payment_program.clone(),
+            IsSubscriptionActive {
+                subscription: subscription.clone(),
+            },
+        ),
+        subscriber,
+        creator,
+    )?;
+    Ok(active.get())
+}
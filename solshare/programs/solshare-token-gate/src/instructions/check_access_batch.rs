@@ -0,0 +1,83 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::Token;
+use anchor_spl::token_interface::{Token2022, TokenAccount};
+use crate::state::{system_account_lamports, AccessControl, AccessDenyReason, AccessVerification, GateType};
+use crate::error::TokenGateError;
+use super::verify_token_access::counted_balance;
+
+// Keeps a batch well inside the compute budget and the u64 result bitmask
+pub const MAX_BATCH_GATES: usize = 32;
+
+#[derive(Accounts)]
+pub struct CheckAccessBatch<'info> {
+    pub user: Signer<'info>,
+    // Remaining accounts: `gate_count` AccessControl accounts, followed by the
+    // viewer's token accounts and/or AccessVerification PDAs in any order
+}
+
+/// Returns a bitmask where bit `i` is set if the viewer can access gate `i`
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, CheckAccessBatch<'info>>,
+    gate_count: u8,
+) -> Result<u64> {
+    let gate_count = gate_count as usize;
+    let user = ctx.accounts.user.key();
+    let user_lamports = system_account_lamports(&ctx.accounts.user);
+    let clock = Clock::get()?;
+
+    require!(gate_count <= MAX_BATCH_GATES, TokenGateError::BatchTooLarge);
+    require!(
+        ctx.remaining_accounts.len() >= gate_count,
+        TokenGateError::InvalidBatchAccount
+    );
+    let (gate_infos, viewer_infos) = ctx.remaining_accounts.split_at(gate_count);
+
+    let mut token_accounts = Vec::new();
+    let mut verifications = Vec::new();
+    for info in viewer_infos {
+        if info.owner == &Token::id() || info.owner == &Token2022::id() {
+            let token_account = TokenAccount::try_deserialize(&mut &info.data.borrow()[..])
+                .map_err(|_| error!(TokenGateError::InvalidBatchAccount))?;
+            require!(
+                token_account.owner == user,
+                TokenGateError::TokenAccountOwnerMismatch
+            );
+            token_accounts.push(token_account);
+        } else {
+            let verification = Account::<AccessVerification>::try_from(info)
+                .map_err(|_| error!(TokenGateError::InvalidBatchAccount))?;
+            require!(verification.user == user, TokenGateError::Unauthorized);
+            verifications.push(verification.into_inner());
+        }
+    }
+
+    let mut granted: u64 = 0;
+    for (i, info) in gate_infos.iter().enumerate() {
+        let access_control = Account::<AccessControl>::try_from(info)
+            .map_err(|_| error!(TokenGateError::InvalidBatchAccount))?;
+
+        let verification = verifications
+            .iter()
+            .find(|v| v.post == access_control.post);
+        let verified = access_control.evaluate_access(&user, user_lamports, None, verification, clock.unix_timestamp)
+            == AccessDenyReason::None;
+
+        // Token-only gates can be satisfied directly from the supplied balances;
+        // NFT gates need a prior verification
+        let holds_tokens = access_control.gate_type == GateType::Token && {
+            let holdings: Vec<(Pubkey, u64)> = token_accounts
+                .iter()
+                .map(|account| {
+                    (account.mint, counted_balance(account, access_control.strict_token_counting))
+                })
+                .collect();
+            access_control.token_requirements_met(&holdings)
+        };
+
+        if verified || holds_tokens {
+            granted |= 1 << i;
+        }
+    }
+
+    Ok(granted)
+}
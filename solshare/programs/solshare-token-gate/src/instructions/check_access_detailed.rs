@@ -0,0 +1,106 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+use solshare_payment::program::SolsharePayment;
+use crate::state::{system_account_lamports, AccessControl, AccessDenyReason, AccessResult, AccessVerification};
+use crate::error::TokenGateError;
+use crate::events::AccessDenied;
+use super::check_access::subscription_active;
+use super::verify_token_access::counted_balance;
+
+#[derive(Accounts)]
+pub struct CheckAccessDetailed<'info> {
+    #[account(
+        seeds = [b"access", access_control.post.as_ref()],
+        bump = access_control.bump
+    )]
+    pub access_control: Account<'info, AccessControl>,
+
+    #[account(
+        seeds = [b"verification", user.key().as_ref(), access_control.post.as_ref()],
+        bump = verification.bump,
+        has_one = user
+    )]
+    pub verification: Option<Account<'info, AccessVerification>>,
+
+    /// CHECK: Viewer's payment subscription, validated by the payment program;
+    /// only needed for subscription gates
+    pub subscription: Option<UncheckedAccount<'info>>,
+
+    // Asked over CPI whether the subscription is active; needed with `subscription`
+    pub payment_program: Option<Program<'info, SolsharePayment>>,
+
+    // Optional: lets an unverified viewer see how far they are from the token requirement
+    #[account(
+        constraint = user_token_account.owner == user.key() @ TokenGateError::TokenAccountOwnerMismatch
+    )]
+    pub user_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub user: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<CheckAccessDetailed>) -> Result<AccessResult> {
+    let access_control = &ctx.accounts.access_control;
+    let clock = Clock::get()?;
+
+    let subscription_active = ctx
+        .accounts
+        .subscription
+        .as_ref()
+        .map(|subscription| {
+            subscription_active(
+                subscription,
+                ctx.accounts.payment_program.as_ref().map(|program| program.as_ref()),
+                ctx.accounts.user.key(),
+                access_control.creator,
+            )
+        })
+        .transpose()?;
+
+    let mut reason = access_control.evaluate_access(
+        &ctx.accounts.user.key(),
+        system_account_lamports(&ctx.accounts.user),
+        subscription_active,
+        ctx.accounts.verification.as_deref(),
+        clock.unix_timestamp,
+    );
+
+    // Without a token verification, report the viewer's current holdings instead
+    let token_unverified = matches!(
+        reason,
+        AccessDenyReason::NotVerified | AccessDenyReason::TokenNotVerified
+    );
+    if token_unverified && access_control.requires_token() {
+        if let Some(token_account) = ctx.accounts.user_token_account.as_ref() {
+            // Measured the way verify_token_access would count it
+            let balance = counted_balance(token_account, access_control.strict_token_counting);
+            reason = match access_control
+                .effective_token_requirements()
+                .iter()
+                .find(|r| r.mint == token_account.mint)
+            {
+                None => AccessDenyReason::WrongMint,
+                Some(requirement) if balance < requirement.minimum_balance => {
+                    AccessDenyReason::InsufficientBalance {
+                        shortfall: requirement.minimum_balance - balance,
+                    }
+                }
+                // Holdings suffice; the viewer just hasn't called verify_token_access yet
+                Some(_) => reason,
+            };
+        }
+    }
+
+    let granted = reason == AccessDenyReason::None;
+    // The other checks fail on a denial, rolling back any event, so this is
+    // the one place denials are recorded
+    if !granted {
+        emit!(AccessDenied {
+            user: ctx.accounts.user.key(),
+            post: access_control.post,
+            gate_type: access_control.gate_type,
+            timestamp: clock.unix_timestamp,
+        });
+    }
+
+    Ok(AccessResult { granted, reason })
+}
@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+use crate::state::AccessControl;
+use crate::error::TokenGateError;
+
+#[derive(Accounts)]
+pub struct CheckTier<'info> {
+    #[account(
+        seeds = [b"access", access_control.post.as_ref()],
+        bump = access_control.bump
+    )]
+    pub access_control: Account<'info, AccessControl>,
+
+    #[account(
+        constraint = user_token_account.owner == user.key() @ TokenGateError::TokenAccountOwnerMismatch,
+        constraint = Some(user_token_account.mint) == access_control.required_token @ TokenGateError::TokenAccountMintMismatch
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub user: Signer<'info>,
+}
+
+/// Returns the highest tier level the viewer qualifies for (0 = no access)
+pub fn handler(ctx: Context<CheckTier>) -> Result<u8> {
+    Ok(ctx
+        .accounts
+        .access_control
+        .tier_for_balance(ctx.accounts.user_token_account.amount))
+}
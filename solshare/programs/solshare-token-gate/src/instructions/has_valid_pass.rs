@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+use crate::state::{AccessControl, AccessPass};
+
+#[derive(Accounts)]
+pub struct HasValidPass<'info> {
+    #[account(
+        seeds = [b"access", access_control.post.as_ref()],
+        bump = access_control.bump
+    )]
+    pub access_control: Account<'info, AccessControl>,
+
+    #[account(
+        seeds = [b"pass", access_control.post.as_ref(), viewer.key().as_ref()],
+        bump = access_pass.bump
+    )]
+    pub access_pass: Account<'info, AccessPass>,
+
+    /// CHECK: Only used as a PDA seed
+    pub viewer: UncheckedAccount<'info>,
+}
+
+/// Checks the cached pass without touching the viewer's token accounts
+pub fn handler(ctx: Context<HasValidPass>) -> Result<bool> {
+    let clock = Clock::get()?;
+    Ok(ctx
+        .accounts
+        .access_pass
+        .is_valid(&ctx.accounts.access_control, clock.unix_timestamp))
+}
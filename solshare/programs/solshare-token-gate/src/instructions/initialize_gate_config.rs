@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+use crate::state::GateConfig;
+use crate::events::GateConfigUpdated;
+
+#[derive(Accounts)]
+pub struct InitializeGateConfig<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + GateConfig::INIT_SPACE,
+        seeds = [b"gate_config"],
+        bump
+    )]
+    pub gate_config: Account<'info, GateConfig>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<InitializeGateConfig>, social_program: Pubkey) -> Result<()> {
+    let gate_config = &mut ctx.accounts.gate_config;
+    let clock = Clock::get()?;
+
+    gate_config.admin = ctx.accounts.admin.key();
+    gate_config.social_program = social_program;
+    gate_config.bump = ctx.bumps.gate_config;
+
+    emit!(GateConfigUpdated {
+        admin: gate_config.admin,
+        social_program,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use crate::state::AccessControl;
+use crate::events::AccessControlMigrated;
+
+#[derive(Accounts)]
+pub struct MigrateAccessControl<'info> {
+    /// CHECK: A gate in an older layout can't be deserialized as
+    /// AccessControl; ownership, discriminator and creator are checked in the handler
+    #[account(mut)]
+    pub access_control: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Grows a gate created under an older layout to its current size, with the
+/// creator paying the extra rent. Current gates are left untouched.
+pub fn handler(ctx: Context<MigrateAccessControl>) -> Result<()> {
+    let access_control_info = ctx.accounts.access_control.to_account_info();
+    require_keys_eq!(*access_control_info.owner, crate::ID, ErrorCode::AccountOwnedByWrongProgram);
+
+    let old_size = access_control_info.data_len();
+    let new_size = 8 + AccessControl::INIT_SPACE;
+    if old_size >= new_size {
+        return Ok(());
+    }
+
+    let access_control = AccessControl::upgrade_layout(&access_control_info.data.borrow())?;
+    require_keys_eq!(access_control.creator, ctx.accounts.creator.key(), ErrorCode::ConstraintHasOne);
+
+    let rent_needed = Rent::get()?
+        .minimum_balance(new_size)
+        .saturating_sub(access_control_info.lamports());
+    if rent_needed > 0 {
+        transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.creator.to_account_info(),
+                    to: access_control_info.clone(),
+                },
+            ),
+            rent_needed,
+        )?;
+    }
+
+    access_control_info.resize(new_size)?;
+    access_control.try_serialize(&mut &mut access_control_info.data.borrow_mut()[..])?;
+
+    emit!(AccessControlMigrated {
+        post: access_control.post,
+        old_size: old_size as u32,
+        new_size: new_size as u32,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+use crate::state::AccessControl;
+use crate::error::TokenGateError;
+use crate::events::AccessControlRemoved;
+
+#[derive(Accounts)]
+pub struct RemoveAccessRequirements<'info> {
+    #[account(
+        mut,
+        seeds = [b"access", access_control.post.as_ref()],
+        bump = access_control.bump,
+        has_one = creator @ TokenGateError::Unauthorized,
+        close = creator
+    )]
+    pub access_control: Account<'info, AccessControl>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<RemoveAccessRequirements>) -> Result<()> {
+    let access_control = &ctx.accounts.access_control;
+    let clock = Clock::get()?;
+
+    // Closing the PDA is what ungates the post: clients treat a post with no
+    // AccessControl account as open. Verifications issued against this gate are
+    // rejected by check_access if the post is gated again later.
+    emit!(AccessControlRemoved {
+        post: access_control.post,
+        creator: access_control.creator,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+use crate::state::AccessControl;
+use crate::error::TokenGateError;
+use crate::events::AllowListUpdated;
+
+#[derive(Accounts)]
+pub struct RemoveFromAllowlist<'info> {
+    #[account(
+        mut,
+        seeds = [b"access", access_control.post.as_ref()],
+        bump = access_control.bump,
+        has_one = creator @ TokenGateError::Unauthorized
+    )]
+    pub access_control: Account<'info, AccessControl>,
+
+    pub creator: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<RemoveFromAllowlist>, wallet: Pubkey) -> Result<()> {
+    let access_control = &mut ctx.accounts.access_control;
+    let clock = Clock::get()?;
+
+    let position = access_control
+        .allow_list
+        .iter()
+        .position(|w| *w == wallet)
+        .ok_or(error!(TokenGateError::NotAllowListed))?;
+    access_control.allow_list.swap_remove(position);
+
+    emit!(AllowListUpdated {
+        post: access_control.post,
+        wallet,
+        added: false,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+use crate::state::AccessControl;
+use crate::error::TokenGateError;
+use crate::events::AccessPassesRevoked;
+
+#[derive(Accounts)]
+pub struct RevokeAccessPass<'info> {
+    #[account(
+        mut,
+        seeds = [b"access", access_control.post.as_ref()],
+        bump = access_control.bump,
+        has_one = creator @ TokenGateError::Unauthorized
+    )]
+    pub access_control: Account<'info, AccessControl>,
+
+    pub creator: Signer<'info>,
+}
+
+/// Bumps the gate epoch so every outstanding pass on the post is stale and
+/// viewers must re-verify their current holdings
+pub fn handler(ctx: Context<RevokeAccessPass>) -> Result<()> {
+    let access_control = &mut ctx.accounts.access_control;
+    access_control.gate_epoch = access_control
+        .gate_epoch
+        .checked_add(1)
+        .ok_or(TokenGateError::ArithmeticOverflow)?;
+
+    emit!(AccessPassesRevoked {
+        post: access_control.post,
+        gate_epoch: access_control.gate_epoch,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
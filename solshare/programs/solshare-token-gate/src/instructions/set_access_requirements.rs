@@ -1,10 +1,8 @@
 use anchor_lang::prelude::*;
-use crate::state::{AccessControl, GateType};
+use crate::state::{AccessControl, GateConfig, GateType};
 use crate::error::TokenGateError;
 use crate::events::AccessControlCreated;
 
-const SOCIAL_PROGRAM_ID: Pubkey = pubkey!("sGLNkcQKvfTVYvhJX8KVo4RrzEZL32UTo8ruwpFEHmG");
-
 #[derive(Accounts)]
 #[instruction(post: Pubkey, required_token: Option<Pubkey>, minimum_balance: u64, required_nft_collection: Option<Pubkey>, post_index: u64)]
 pub struct SetAccessRequirements<'info> {
@@ -16,6 +14,12 @@ pub struct SetAccessRequirements<'info> {
         bump
     )]
     pub access_control: Account<'info, AccessControl>,
+
+    #[account(
+        seeds = [b"gate_config"],
+        bump = gate_config.bump
+    )]
+    pub gate_config: Account<'info, GateConfig>,
     
     #[account(mut)]
     pub creator: Signer<'info>,
@@ -31,23 +35,49 @@ pub fn handler(
     required_nft_collection: Option<Pubkey>,
     post_index: u64,
 ) -> Result<()> {
-    // Verify creator owns this post by checking the PDA derivation against the social program
-    let (expected_post_pda, _) = Pubkey::find_program_address(
-        &[
-            b"post",
-            ctx.accounts.creator.key().as_ref(),
-            &post_index.to_le_bytes(),
-        ],
-        &SOCIAL_PROGRAM_ID,
-    );
-    require!(post == expected_post_pda, TokenGateError::Unauthorized);
-
-    require!(
-        required_token.is_some() || required_nft_collection.is_some(),
-        TokenGateError::InvalidGateConfig
-    );
+    verify_post_owner(
+        &post,
+        &ctx.accounts.creator.key(),
+        post_index,
+        &ctx.accounts.gate_config.social_program,
+    )?;
 
+    let creator = ctx.accounts.creator.key();
     let access_control = &mut ctx.accounts.access_control;
+    init_token_nft_gate(
+        access_control,
+        post,
+        creator,
+        required_token,
+        minimum_balance,
+        required_nft_collection,
+        ctx.bumps.access_control,
+    )?;
+
+    emit!(AccessControlCreated {
+        post,
+        post_index,
+        creator,
+        required_token,
+        minimum_balance,
+        required_nft_collection,
+        timestamp: access_control.created_at,
+    });
+
+    Ok(())
+}
+
+/// Fills in a fresh token and/or NFT gate for `post`; shared with
+/// set_access_requirements_batch. Callers emit AccessControlCreated.
+pub(crate) fn init_token_nft_gate(
+    access_control: &mut AccessControl,
+    post: Pubkey,
+    creator: Pubkey,
+    required_token: Option<Pubkey>,
+    minimum_balance: u64,
+    required_nft_collection: Option<Pubkey>,
+    bump: u8,
+) -> Result<()> {
     let clock = Clock::get()?;
 
     let gate_type = match (required_token.is_some(), required_nft_collection.is_some()) {
@@ -57,23 +87,28 @@ pub fn handler(
         (false, false) => return Err(TokenGateError::InvalidGateConfig.into()),
     };
 
-    access_control.post = post;
-    access_control.creator = ctx.accounts.creator.key();
-    access_control.required_token = required_token;
-    access_control.minimum_balance = minimum_balance;
-    access_control.required_nft_collection = required_nft_collection;
-    access_control.gate_type = gate_type;
-    access_control.created_at = clock.unix_timestamp;
-    access_control.bump = ctx.bumps.access_control;
-
-    emit!(AccessControlCreated {
-        post,
-        creator: access_control.creator,
+    *access_control = AccessControl {
         required_token,
         minimum_balance,
         required_nft_collection,
-        timestamp: clock.unix_timestamp,
-    });
+        gate_type,
+        ..AccessControl::new(post, creator, bump, clock.unix_timestamp)
+    };
+
+    Ok(())
+}
 
+/// Verify creator owns this post by checking the PDA derivation against the social program
+pub(crate) fn verify_post_owner(
+    post: &Pubkey,
+    creator: &Pubkey,
+    post_index: u64,
+    social_program: &Pubkey,
+) -> Result<()> {
+    let (expected_post_pda, _) = Pubkey::find_program_address(
+        &[b"post", creator.as_ref(), &post_index.to_le_bytes()],
+        social_program,
+    );
+    require!(*post == expected_post_pda, TokenGateError::Unauthorized);
     Ok(())
 }
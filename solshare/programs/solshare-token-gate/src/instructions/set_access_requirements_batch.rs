@@ -0,0 +1,113 @@
+use anchor_lang::prelude::*;
+use crate::state::{AccessControl, GateConfig};
+use crate::error::TokenGateError;
+use crate::events::AccessControlCreated;
+use super::set_access_requirements::{init_token_nft_gate, verify_post_owner};
+use solshare_pda::create_pda_account;
+
+// Each post costs a PDA derivation, an ownership check and an account creation,
+// and adds a pubkey, an index and an account to the transaction
+pub const MAX_BATCH_POSTS: usize = 10;
+
+#[derive(Accounts)]
+pub struct SetAccessRequirementsBatch<'info> {
+    #[account(
+        seeds = [b"gate_config"],
+        bump = gate_config.bump
+    )]
+    pub gate_config: Account<'info, GateConfig>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    // Remaining accounts: the AccessControl PDA of each post, in the same order
+    // as `posts`, created here
+}
+
+/// Gates every post in `posts` with the same token and/or NFT requirements,
+/// as set_access_requirements would one post at a time
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, SetAccessRequirementsBatch<'info>>,
+    posts: Vec<Pubkey>,
+    post_indices: Vec<u64>,
+    required_token: Option<Pubkey>,
+    minimum_balance: u64,
+    required_nft_collection: Option<Pubkey>,
+) -> Result<()> {
+    require!(posts.len() <= MAX_BATCH_POSTS, TokenGateError::BatchTooLarge);
+    require!(
+        !posts.is_empty()
+            && posts.len() == post_indices.len()
+            && posts.len() == ctx.remaining_accounts.len(),
+        TokenGateError::InvalidBatchAccount
+    );
+
+    let creator = ctx.accounts.creator.key();
+    for ((post, post_index), access_control_info) in posts
+        .iter()
+        .zip(post_indices)
+        .zip(ctx.remaining_accounts)
+    {
+        verify_post_owner(post, &creator, post_index, &ctx.accounts.gate_config.social_program)?;
+
+        let bump = create_access_control(
+            access_control_info,
+            post,
+            &ctx.accounts.creator.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+        )?;
+
+        // The new account is zeroed, which deserializes to an empty gate
+        let mut access_control = Account::<AccessControl>::try_from_unchecked(access_control_info)?;
+        init_token_nft_gate(
+            &mut access_control,
+            *post,
+            creator,
+            required_token,
+            minimum_balance,
+            required_nft_collection,
+            bump,
+        )?;
+        access_control.exit(&crate::ID)?;
+
+        emit!(AccessControlCreated {
+            post: *post,
+            post_index,
+            creator,
+            required_token,
+            minimum_balance,
+            required_nft_collection,
+            timestamp: access_control.created_at,
+        });
+    }
+
+    Ok(())
+}
+
+/// Creates the AccessControl PDA for `post`, which only succeeds if the post
+/// isn't gated yet. The creator pays the rent.
+fn create_access_control<'info>(
+    access_control_info: &AccountInfo<'info>,
+    post: &Pubkey,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<u8> {
+    let (expected, bump) = Pubkey::find_program_address(&[b"access", post.as_ref()], &crate::ID);
+    require_keys_eq!(access_control_info.key(), expected, TokenGateError::InvalidBatchAccount);
+    require!(
+        access_control_info.owner == &System::id(),
+        TokenGateError::PostAlreadyGated
+    );
+
+    create_pda_account(
+        payer,
+        access_control_info,
+        system_program,
+        8 + AccessControl::INIT_SPACE,
+        &crate::ID,
+        &[b"access", post.as_ref(), &[bump]],
+    )?;
+
+    Ok(bump)
+}
@@ -0,0 +1,59 @@
+use anchor_lang::prelude::*;
+use crate::state::{CreatorGateDefault, GateConfig, GateType};
+use crate::error::TokenGateError;
+use crate::events::CreatorGateDefaultSet;
+
+#[derive(Accounts)]
+pub struct SetGateDefault<'info> {
+    #[account(
+        init_if_needed,
+        payer = creator,
+        space = 8 + CreatorGateDefault::INIT_SPACE,
+        seeds = [b"gate_default", creator.key().as_ref()],
+        bump
+    )]
+    pub gate_default: Account<'info, CreatorGateDefault>,
+
+    #[account(
+        seeds = [b"gate_config"],
+        bump = gate_config.bump
+    )]
+    pub gate_config: Account<'info, GateConfig>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Sets (or replaces) the gate applied to the creator's posts that have no
+/// AccessControl of their own
+pub fn handler(
+    ctx: Context<SetGateDefault>,
+    gate_type: GateType,
+    minimum_sol_lamports: u64,
+) -> Result<()> {
+    require!(
+        CreatorGateDefault::supports_gate_type(gate_type, minimum_sol_lamports),
+        TokenGateError::InvalidGateConfig
+    );
+
+    let gate_default = &mut ctx.accounts.gate_default;
+    let clock = Clock::get()?;
+
+    gate_default.creator = ctx.accounts.creator.key();
+    gate_default.social_program = ctx.accounts.gate_config.social_program;
+    gate_default.gate_type = gate_type;
+    gate_default.minimum_sol_lamports = minimum_sol_lamports;
+    gate_default.created_at = clock.unix_timestamp;
+    gate_default.bump = ctx.bumps.gate_default;
+
+    emit!(CreatorGateDefaultSet {
+        creator: gate_default.creator,
+        gate_type,
+        minimum_sol_lamports,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+use crate::state::AccessControl;
+use crate::error::TokenGateError;
+use crate::events::AccessControlUpdated;
+
+#[derive(Accounts)]
+pub struct SetMinimumNftCount<'info> {
+    #[account(
+        mut,
+        seeds = [b"access", access_control.post.as_ref()],
+        bump = access_control.bump,
+        has_one = creator @ TokenGateError::Unauthorized
+    )]
+    pub access_control: Account<'info, AccessControl>,
+
+    pub creator: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SetMinimumNftCount>, minimum_nft_count: u32) -> Result<()> {
+    let access_control = &mut ctx.accounts.access_control;
+    let clock = Clock::get()?;
+
+    require!(
+        access_control.required_nft_collection.is_some(),
+        TokenGateError::NftCollectionRequired
+    );
+    require!(minimum_nft_count > 0, TokenGateError::InvalidGateConfig);
+
+    access_control.minimum_nft_count = minimum_nft_count;
+
+    emit!(AccessControlUpdated {
+        post: access_control.post,
+        required_token: access_control.required_token,
+        minimum_balance: access_control.minimum_balance,
+        required_nft_collection: access_control.required_nft_collection,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+use crate::state::{AccessControl, MAX_NFT_COLLECTIONS};
+use crate::error::TokenGateError;
+use crate::events::AccessControlUpdated;
+
+#[derive(Accounts)]
+pub struct SetNftCollections<'info> {
+    #[account(
+        mut,
+        seeds = [b"access", access_control.post.as_ref()],
+        bump = access_control.bump,
+        has_one = creator @ TokenGateError::Unauthorized
+    )]
+    pub access_control: Account<'info, AccessControl>,
+
+    pub creator: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SetNftCollections>, collections: Vec<Pubkey>) -> Result<()> {
+    let access_control = &mut ctx.accounts.access_control;
+    let clock = Clock::get()?;
+
+    let primary = access_control
+        .required_nft_collection
+        .ok_or(error!(TokenGateError::NftCollectionRequired))?;
+    require!(
+        collections.len() <= MAX_NFT_COLLECTIONS,
+        TokenGateError::TooManyNftCollections
+    );
+    for (i, collection) in collections.iter().enumerate() {
+        require!(
+            *collection != primary && !collections[..i].contains(collection),
+            TokenGateError::DuplicateNftCollection
+        );
+    }
+
+    access_control.required_nft_collections = collections;
+
+    emit!(AccessControlUpdated {
+        post: access_control.post,
+        required_token: access_control.required_token,
+        minimum_balance: access_control.minimum_balance,
+        required_nft_collection: access_control.required_nft_collection,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
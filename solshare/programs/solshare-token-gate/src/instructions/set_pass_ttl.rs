@@ -0,0 +1,24 @@
+use anchor_lang::prelude::*;
+use crate::state::AccessControl;
+use crate::error::TokenGateError;
+
+#[derive(Accounts)]
+pub struct SetPassTtl<'info> {
+    #[account(
+        mut,
+        seeds = [b"access", access_control.post.as_ref()],
+        bump = access_control.bump,
+        has_one = creator @ TokenGateError::Unauthorized
+    )]
+    pub access_control: Account<'info, AccessControl>,
+
+    pub creator: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SetPassTtl>, pass_ttl_seconds: i64) -> Result<()> {
+    require!(pass_ttl_seconds > 0, TokenGateError::InvalidGateConfig);
+
+    ctx.accounts.access_control.pass_ttl_seconds = pass_ttl_seconds;
+
+    Ok(())
+}
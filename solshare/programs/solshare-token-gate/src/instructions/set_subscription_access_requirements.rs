@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+use crate::state::{AccessControl, GateConfig, GateType};
+use crate::events::AccessControlCreated;
+use super::set_access_requirements::verify_post_owner;
+
+#[derive(Accounts)]
+#[instruction(post: Pubkey, post_index: u64)]
+pub struct SetSubscriptionAccessRequirements<'info> {
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + AccessControl::INIT_SPACE,
+        seeds = [b"access", post.as_ref()],
+        bump
+    )]
+    pub access_control: Account<'info, AccessControl>,
+
+    #[account(
+        seeds = [b"gate_config"],
+        bump = gate_config.bump
+    )]
+    pub gate_config: Account<'info, GateConfig>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<SetSubscriptionAccessRequirements>,
+    post: Pubkey,
+    post_index: u64,
+) -> Result<()> {
+    verify_post_owner(
+        &post,
+        &ctx.accounts.creator.key(),
+        post_index,
+        &ctx.accounts.gate_config.social_program,
+    )?;
+
+    let creator = ctx.accounts.creator.key();
+    let access_control = &mut ctx.accounts.access_control;
+    let clock = Clock::get()?;
+
+    access_control.set_inner(AccessControl {
+        gate_type: GateType::Subscription,
+        ..AccessControl::new(post, creator, ctx.bumps.access_control, clock.unix_timestamp)
+    });
+
+    emit!(AccessControlCreated {
+        post,
+        post_index,
+        creator,
+        required_token: None,
+        minimum_balance: 0,
+        required_nft_collection: None,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+use crate::state::{AccessControl, TierRequirement, MAX_TIERS};
+use crate::error::TokenGateError;
+use crate::events::AccessControlUpdated;
+
+#[derive(Accounts)]
+pub struct SetTiers<'info> {
+    #[account(
+        mut,
+        seeds = [b"access", access_control.post.as_ref()],
+        bump = access_control.bump,
+        has_one = creator @ TokenGateError::Unauthorized
+    )]
+    pub access_control: Account<'info, AccessControl>,
+
+    pub creator: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SetTiers>, tiers: Vec<TierRequirement>) -> Result<()> {
+    require!(tiers.len() <= MAX_TIERS, TokenGateError::TooManyTiers);
+    for (i, tier) in tiers.iter().enumerate() {
+        require!(
+            tier.level > 0 && tiers[..i].iter().all(|t| t.level != tier.level),
+            TokenGateError::InvalidTierLevel
+        );
+    }
+
+    let access_control = &mut ctx.accounts.access_control;
+    let clock = Clock::get()?;
+
+    // Tiers are measured in the gate's primary token
+    require!(
+        access_control.required_token.is_some(),
+        TokenGateError::TokenMintRequired
+    );
+
+    access_control.tiers = tiers;
+
+    emit!(AccessControlUpdated {
+        post: access_control.post,
+        required_token: access_control.required_token,
+        minimum_balance: access_control.minimum_balance,
+        required_nft_collection: access_control.required_nft_collection,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
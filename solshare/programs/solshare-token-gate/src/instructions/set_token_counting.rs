@@ -0,0 +1,22 @@
+use anchor_lang::prelude::*;
+use crate::state::AccessControl;
+use crate::error::TokenGateError;
+
+#[derive(Accounts)]
+pub struct SetTokenCounting<'info> {
+    #[account(
+        mut,
+        seeds = [b"access", access_control.post.as_ref()],
+        bump = access_control.bump,
+        has_one = creator @ TokenGateError::Unauthorized
+    )]
+    pub access_control: Account<'info, AccessControl>,
+
+    pub creator: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SetTokenCounting>, strict: bool) -> Result<()> {
+    ctx.accounts.access_control.strict_token_counting = strict;
+
+    Ok(())
+}
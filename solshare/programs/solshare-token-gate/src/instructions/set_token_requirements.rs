@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+use crate::state::{AccessControl, GateLogic, GateType, TokenRequirement, MAX_TOKEN_REQUIREMENTS};
+use crate::error::TokenGateError;
+use crate::events::AccessControlUpdated;
+
+#[derive(Accounts)]
+pub struct SetTokenRequirements<'info> {
+    #[account(
+        mut,
+        seeds = [b"access", access_control.post.as_ref()],
+        bump = access_control.bump,
+        has_one = creator @ TokenGateError::Unauthorized
+    )]
+    pub access_control: Account<'info, AccessControl>,
+
+    pub creator: Signer<'info>,
+}
+
+pub fn handler(
+    ctx: Context<SetTokenRequirements>,
+    requirements: Vec<TokenRequirement>,
+    logic: GateLogic,
+) -> Result<()> {
+    require!(!requirements.is_empty(), TokenGateError::InvalidGateConfig);
+    require!(
+        requirements.len() <= MAX_TOKEN_REQUIREMENTS,
+        TokenGateError::TooManyTokenRequirements
+    );
+    for (i, requirement) in requirements.iter().enumerate() {
+        require!(
+            requirements[..i].iter().all(|r| r.mint != requirement.mint),
+            TokenGateError::DuplicateTokenRequirement
+        );
+    }
+
+    let access_control = &mut ctx.accounts.access_control;
+    let clock = Clock::get()?;
+
+    // The first requirement doubles as the legacy single-token fields so that
+    // clients reading required_token/minimum_balance keep working
+    access_control.required_token = Some(requirements[0].mint);
+    access_control.minimum_balance = requirements[0].minimum_balance;
+    access_control.gate_type = if access_control.required_nft_collection.is_some() {
+        GateType::Both
+    } else {
+        GateType::Token
+    };
+    access_control.token_requirements = requirements;
+    access_control.token_logic = logic;
+
+    emit!(AccessControlUpdated {
+        post: access_control.post,
+        required_token: access_control.required_token,
+        minimum_balance: access_control.minimum_balance,
+        required_nft_collection: access_control.required_nft_collection,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+use crate::state::AccessControl;
+use crate::error::TokenGateError;
+use crate::events::VerifierUpdated;
+
+#[derive(Accounts)]
+pub struct SetVerifier<'info> {
+    #[account(
+        mut,
+        seeds = [b"access", access_control.post.as_ref()],
+        bump = access_control.bump,
+        has_one = creator @ TokenGateError::Unauthorized
+    )]
+    pub access_control: Account<'info, AccessControl>,
+
+    pub creator: Signer<'info>,
+}
+
+/// Passing `None` revokes the current delegate
+pub fn handler(ctx: Context<SetVerifier>, verifier: Option<Pubkey>) -> Result<()> {
+    let access_control = &mut ctx.accounts.access_control;
+    let clock = Clock::get()?;
+
+    access_control.verifier = verifier;
+
+    emit!(VerifierUpdated {
+        post: access_control.post,
+        verifier,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+use crate::state::AccessControl;
+use crate::error::TokenGateError;
+use super::verify_token_access::counted_balance;
+
+#[derive(Accounts)]
+pub struct TokenAccessShortfall<'info> {
+    #[account(
+        seeds = [b"access", access_control.post.as_ref()],
+        bump = access_control.bump
+    )]
+    pub access_control: Account<'info, AccessControl>,
+
+    #[account(
+        constraint = user_token_account.owner == user.key() @ TokenGateError::TokenAccountOwnerMismatch
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub user: Signer<'info>,
+}
+
+/// Returns how many more tokens of the account's mint the viewer needs to
+/// meet the gate's requirement for that mint (0 = already qualifies)
+pub fn handler(ctx: Context<TokenAccessShortfall>) -> Result<u64> {
+    let access_control = &ctx.accounts.access_control;
+    let user_token_account = &ctx.accounts.user_token_account;
+
+    require!(
+        access_control.requires_token(),
+        TokenGateError::InvalidGateConfig
+    );
+    let requirement = access_control
+        .effective_token_requirements()
+        .into_iter()
+        .find(|r| r.mint == user_token_account.mint)
+        .ok_or(error!(TokenGateError::TokenAccountMintMismatch))?;
+
+    let balance = counted_balance(user_token_account, access_control.strict_token_counting);
+    Ok(requirement.minimum_balance.saturating_sub(balance))
+}
@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+use crate::state::{AccessControl, GateType};
+use crate::error::TokenGateError;
+use crate::events::AccessControlUpdated;
+
+#[derive(Accounts)]
+pub struct UpdateAccessRequirements<'info> {
+    #[account(
+        mut,
+        seeds = [b"access", access_control.post.as_ref()],
+        bump = access_control.bump,
+        has_one = creator @ TokenGateError::Unauthorized
+    )]
+    pub access_control: Account<'info, AccessControl>,
+
+    pub creator: Signer<'info>,
+}
+
+/// Changes the gate to any type its stored requirements support without
+/// recreating the PDA, e.g. `GateType::Open` until a paywall launches
+pub fn handler(ctx: Context<UpdateAccessRequirements>, gate_type: GateType) -> Result<()> {
+    let access_control = &mut ctx.accounts.access_control;
+    let clock = Clock::get()?;
+
+    require!(
+        access_control.supports_gate_type(gate_type),
+        TokenGateError::InvalidGateConfig
+    );
+
+    access_control.gate_type = gate_type;
+
+    emit!(AccessControlUpdated {
+        post: access_control.post,
+        required_token: access_control.required_token,
+        minimum_balance: access_control.minimum_balance,
+        required_nft_collection: access_control.required_nft_collection,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
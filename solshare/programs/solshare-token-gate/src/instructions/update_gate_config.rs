@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+use crate::state::GateConfig;
+use crate::error::TokenGateError;
+use crate::events::GateConfigUpdated;
+
+#[derive(Accounts)]
+pub struct UpdateGateConfig<'info> {
+    #[account(
+        mut,
+        seeds = [b"gate_config"],
+        bump = gate_config.bump,
+        has_one = admin @ TokenGateError::Unauthorized
+    )]
+    pub gate_config: Account<'info, GateConfig>,
+
+    pub admin: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<UpdateGateConfig>, social_program: Pubkey) -> Result<()> {
+    let gate_config = &mut ctx.accounts.gate_config;
+    let clock = Clock::get()?;
+
+    gate_config.social_program = social_program;
+
+    emit!(GateConfigUpdated {
+        admin: gate_config.admin,
+        social_program,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
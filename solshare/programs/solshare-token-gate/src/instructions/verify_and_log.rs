@@ -0,0 +1,86 @@
+use anchor_lang::prelude::*;
+use solshare_payment::program::SolsharePayment;
+use crate::state::{AccessControl, AccessLog, AccessVerification, SECONDS_PER_DAY};
+use crate::error::TokenGateError;
+use crate::events::AccessLogged;
+use super::check_access::enforce_access;
+
+#[derive(Accounts)]
+#[instruction(day: i64)]
+pub struct VerifyAndLog<'info> {
+    #[account(
+        seeds = [b"access", access_control.post.as_ref()],
+        bump = access_control.bump
+    )]
+    pub access_control: Account<'info, AccessControl>,
+
+    // Not required for allow-listed wallets
+    #[account(
+        seeds = [b"verification", user.key().as_ref(), access_control.post.as_ref()],
+        bump = verification.bump,
+        has_one = user
+    )]
+    pub verification: Option<Account<'info, AccessVerification>>,
+
+    /// CHECK: Viewer's payment subscription, validated by the payment program;
+    /// only needed for subscription gates
+    pub subscription: Option<UncheckedAccount<'info>>,
+
+    // Asked over CPI whether the subscription is active; needed with `subscription`
+    pub payment_program: Option<Program<'info, SolsharePayment>>,
+
+    // One log per viewer, post and day, so repeat unlocks don't add accounts
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + AccessLog::INIT_SPACE,
+        seeds = [b"log", access_control.post.as_ref(), user.key().as_ref(), &day.to_le_bytes()],
+        bump
+    )]
+    pub access_log: Account<'info, AccessLog>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// `day` is the unix day index (timestamp / 86400) the client derived the log
+/// address from; it must match the cluster clock
+pub fn handler(ctx: Context<VerifyAndLog>, day: i64) -> Result<()> {
+    let clock = Clock::get()?;
+    require!(
+        day == clock.unix_timestamp.div_euclid(SECONDS_PER_DAY),
+        TokenGateError::InvalidLogDay
+    );
+
+    let access_control = &ctx.accounts.access_control;
+    enforce_access(
+        access_control,
+        &ctx.accounts.user,
+        ctx.accounts.subscription.as_deref(),
+        ctx.accounts.payment_program.as_ref().map(|program| program.as_ref()),
+        ctx.accounts.verification.as_deref(),
+        clock.unix_timestamp,
+    )?;
+
+    let viewer = ctx.accounts.user.key();
+    let access_log = &mut ctx.accounts.access_log;
+    access_log.record(
+        access_control.post,
+        viewer,
+        day,
+        clock.unix_timestamp,
+        ctx.bumps.access_log,
+    )?;
+
+    emit!(AccessLogged {
+        viewer,
+        post: access_control.post,
+        day,
+        unlock_count: access_log.unlock_count,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,165 @@
+use anchor_lang::prelude::*;
+use crate::cnft::{self, CnftMetadata, COMPRESSION_PROGRAM_ID};
+use crate::state::{AccessControl, AccessPass, AccessVerification, GateType};
+use crate::error::TokenGateError;
+use crate::events::{AccessGranted, AccessVerified};
+
+#[derive(Accounts)]
+pub struct VerifyCnftAccess<'info> {
+    #[account(
+        seeds = [b"access", access_control.post.as_ref()],
+        bump = access_control.bump
+    )]
+    pub access_control: Account<'info, AccessControl>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + AccessVerification::INIT_SPACE,
+        seeds = [b"verification", user.key().as_ref(), access_control.post.as_ref()],
+        bump
+    )]
+    pub verification: Account<'info, AccessVerification>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + AccessPass::INIT_SPACE,
+        seeds = [b"pass", access_control.post.as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub access_pass: Account<'info, AccessPass>,
+
+    /// CHECK: Bubblegum merkle tree — owner checked here, authority checked in handler,
+    /// proof checked by account compression
+    #[account(owner = COMPRESSION_PROGRAM_ID @ TokenGateError::InvalidMerkleTree)]
+    pub merkle_tree: UncheckedAccount<'info>,
+
+    /// CHECK: spl-account-compression program, address checked
+    #[account(address = COMPRESSION_PROGRAM_ID)]
+    pub compression_program: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    // Remaining accounts: Merkle proof nodes, leaf to root, minus any canopy
+}
+
+/// Verifies the viewer owns a compressed NFT from an accepted collection; a
+/// failed proof or collection check is an error, not an AccessDenied event
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, VerifyCnftAccess<'info>>,
+    root: [u8; 32],
+    metadata: CnftMetadata,
+    leaf_delegate: Pubkey,
+    nonce: u64,
+    index: u32,
+) -> Result<()> {
+    let access_control = &ctx.accounts.access_control;
+    let clock = Clock::get()?;
+
+    // Open gates admit everyone, so there is nothing to verify
+    require!(
+        access_control.gate_type != GateType::Open,
+        TokenGateError::InvalidGateConfig
+    );
+    require!(
+        access_control.requires_nft(),
+        TokenGateError::InvalidGateConfig
+    );
+    require!(
+        access_control.required_nft_collection.is_some(),
+        TokenGateError::NftCollectionRequired
+    );
+
+    // A single proof only ever demonstrates one asset
+    require!(
+        access_control.nft_count_met(1),
+        TokenGateError::InsufficientNftCount
+    );
+
+    let collection = metadata
+        .collection
+        .as_ref()
+        .ok_or(error!(TokenGateError::InvalidNftCollection))?;
+    if !access_control.accepts_nft_collection(&collection.key) || !collection.verified {
+        return err!(TokenGateError::InvalidNftCollection);
+    }
+
+    require!(
+        cnft::is_bubblegum_tree(&ctx.accounts.merkle_tree),
+        TokenGateError::InvalidMerkleTree
+    );
+
+    // Rebuild the leaf for an asset owned by the signer; the proof only
+    // verifies if the owner and metadata (including collection) match
+    let merkle_tree_key = ctx.accounts.merkle_tree.key();
+    let asset_id = cnft::asset_id(&merkle_tree_key, nonce);
+    let leaf = cnft::leaf_hash(
+        &asset_id,
+        &ctx.accounts.user.key(),
+        &leaf_delegate,
+        nonce,
+        &metadata.data_hash()?,
+        &metadata.creator_hash(),
+    );
+
+    cnft::verify_leaf(
+        &ctx.accounts.compression_program,
+        &ctx.accounts.merkle_tree,
+        ctx.remaining_accounts,
+        root,
+        leaf,
+        index,
+    )?;
+
+    let verification = &mut ctx.accounts.verification;
+    verification.clear_if_stale(access_control);
+    verification.user = ctx.accounts.user.key();
+    verification.post = access_control.post;
+    verification.nft_verified = true;
+    verification.verified_at = clock.unix_timestamp;
+    verification.expires_at = None;
+    verification.bump = ctx.bumps.verification;
+
+    // For GateType::Both, only set verified=true if both token AND NFT are verified
+    verification.verified = match access_control.gate_type {
+        crate::state::GateType::Nft => true,
+        crate::state::GateType::Both => verification.token_verified && verification.nft_verified,
+        crate::state::GateType::Token => verification.token_verified,
+        crate::state::GateType::NativeSol => false, // Checked live by check_access
+        crate::state::GateType::TipGated => false, // Verified by verify_tip_access
+        crate::state::GateType::Subscription => false, // Checked live by check_access
+        crate::state::GateType::Open => false, // Rejected above
+        crate::state::GateType::SpecificNft => false, // Should not reach here; compressed NFTs have no single mint to match
+        crate::state::GateType::UpdateAuthority => false, // Should not reach here; compressed NFTs need required_nft_collection
+    };
+
+    ctx.accounts.access_pass.record(
+        access_control,
+        verification.user,
+        verification.verified,
+        1,
+        clock.unix_timestamp,
+        ctx.bumps.access_pass,
+    )?;
+
+    emit!(AccessVerified {
+        user: verification.user,
+        post: verification.post,
+        verification_type: "cnft".to_string(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    if verification.verified {
+        emit!(AccessGranted {
+            user: verification.user,
+            post: verification.post,
+            gate_type: access_control.gate_type,
+            timestamp: clock.unix_timestamp,
+        });
+    }
+
+    Ok(())
+}
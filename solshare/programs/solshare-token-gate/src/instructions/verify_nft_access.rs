@@ -1,9 +1,10 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::TokenAccount;
+use anchor_spl::token::{Mint, Token, TokenAccount};
 use mpl_token_metadata::accounts::Metadata;
-use crate::state::{AccessControl, AccessVerification};
+use mpl_token_metadata::types::{Collection, Creator};
+use crate::state::{AccessControl, AccessPass, AccessVerification, GateType, NftVerificationError};
 use crate::error::TokenGateError;
-use crate::events::AccessVerified;
+use crate::events::{AccessGranted, AccessVerified};
 
 #[derive(Accounts)]
 pub struct VerifyNftAccess<'info> {
@@ -21,15 +22,24 @@ pub struct VerifyNftAccess<'info> {
         bump
     )]
     pub verification: Account<'info, AccessVerification>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + AccessPass::INIT_SPACE,
+        seeds = [b"pass", access_control.post.as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub access_pass: Account<'info, AccessPass>,
     
+    // The balance is checked in the handler so an empty account reports NftZeroBalance
     #[account(
         constraint = nft_token_account.owner == user.key() @ TokenGateError::TokenAccountOwnerMismatch,
-        constraint = nft_token_account.amount == 1 @ TokenGateError::NftNotOwned
     )]
     pub nft_token_account: Account<'info, TokenAccount>,
     
-    /// CHECK: NFT mint account - verified by token account mint check
-    pub nft_mint: AccountInfo<'info>,
+    // Read for its decimals; matched against the token account in the handler
+    pub nft_mint: Account<'info, Mint>,
 
     /// CHECK: Metaplex metadata account — validated via PDA derivation and deserialization in handler
     pub nft_metadata: AccountInfo<'info>,
@@ -38,20 +48,32 @@ pub struct VerifyNftAccess<'info> {
     pub user: Signer<'info>,
     
     pub system_program: Program<'info, System>,
+    // Remaining accounts: (nft_token_account, nft_mint, nft_metadata) triples for
+    // additional NFTs counted towards minimum_nft_count
 }
 
+/// Verifies the viewer's NFT holdings. Denials are errors, so like the other
+/// verify instructions this never emits AccessDenied.
 pub fn handler(ctx: Context<VerifyNftAccess>) -> Result<()> {
     let access_control = &ctx.accounts.access_control;
     let verification = &mut ctx.accounts.verification;
     let nft_token_account = &ctx.accounts.nft_token_account;
     let clock = Clock::get()?;
 
+    // Open gates admit everyone, so there is nothing to verify
+    require!(
+        access_control.gate_type != GateType::Open,
+        TokenGateError::InvalidGateConfig
+    );
     require!(
         access_control.requires_nft(),
         TokenGateError::InvalidGateConfig
     );
+    let specific_nft = access_control.gate_type == GateType::SpecificNft;
     require!(
-        access_control.required_nft_collection.is_some(),
+        specific_nft
+            || access_control.gate_type == GateType::UpdateAuthority
+            || access_control.required_nft_collection.is_some(),
         TokenGateError::NftCollectionRequired
     );
 
@@ -61,44 +83,31 @@ pub fn handler(ctx: Context<VerifyNftAccess>) -> Result<()> {
         TokenGateError::TokenAccountMintMismatch
     );
 
-    // NFT must have amount = 1
-    require!(
-        nft_token_account.amount == 1,
-        TokenGateError::NftNotOwned
-    );
-
-    // Verify Metaplex metadata PDA derivation
-    let metadata_program_id = mpl_token_metadata::ID;
-    let (expected_metadata_key, _) = Pubkey::find_program_address(
-        &[
-            b"metadata",
-            metadata_program_id.as_ref(),
-            ctx.accounts.nft_mint.key().as_ref(),
-        ],
-        &metadata_program_id,
-    );
-    require!(
-        ctx.accounts.nft_metadata.key() == expected_metadata_key,
-        TokenGateError::InvalidNftCollection
-    );
-
-    // Deserialize metadata and verify NFT belongs to required collection
-    let metadata = Metadata::safe_deserialize(&ctx.accounts.nft_metadata.data.borrow())
-        .map_err(|_| error!(TokenGateError::InvalidNftCollection))?;
-
-    let required_collection = access_control
-        .required_nft_collection
-        .ok_or(error!(TokenGateError::NftCollectionRequired))?;
-
-    let collection = metadata
-        .collection
-        .ok_or(error!(TokenGateError::InvalidNftCollection))?;
-
-    require!(
-        collection.key == required_collection && collection.verified,
-        TokenGateError::InvalidNftCollection
-    );
+    // A specific-mint gate only needs the one token account; the metadata and
+    // any remaining accounts are ignored
+    if specific_nft {
+        verify_specific_nft(
+            nft_token_account.amount,
+            &nft_token_account.mint,
+            ctx.accounts.nft_mint.decimals,
+            access_control,
+        )?;
+    } else {
+        let held = count_collection_nfts(
+            &ctx.accounts.user.key(),
+            nft_token_account.amount,
+            &ctx.accounts.nft_mint,
+            &ctx.accounts.nft_metadata,
+            ctx.remaining_accounts,
+            access_control,
+        )?;
+        require!(
+            access_control.nft_count_met(held),
+            TokenGateError::InsufficientNftCount
+        );
+    }
 
+    verification.clear_if_stale(access_control);
     verification.user = ctx.accounts.user.key();
     verification.post = access_control.post;
     verification.nft_verified = true;
@@ -109,11 +118,26 @@ pub fn handler(ctx: Context<VerifyNftAccess>) -> Result<()> {
     // For GateType::Both, only set verified=true if both token AND NFT are verified
     // For GateType::Nft, NFT verification alone is sufficient
     verification.verified = match access_control.gate_type {
-        crate::state::GateType::Nft => true,
+        crate::state::GateType::Nft
+        | crate::state::GateType::SpecificNft
+        | crate::state::GateType::UpdateAuthority => true,
         crate::state::GateType::Both => verification.token_verified && verification.nft_verified,
         crate::state::GateType::Token => verification.token_verified, // Should not reach here due to requires_nft check
+        crate::state::GateType::NativeSol => false, // Checked live by check_access
+        crate::state::GateType::TipGated => false, // Verified by verify_tip_access
+        crate::state::GateType::Subscription => false, // Checked live by check_access
+        crate::state::GateType::Open => false, // Rejected above
     };
 
+    ctx.accounts.access_pass.record(
+        access_control,
+        verification.user,
+        verification.verified,
+        nft_token_account.amount,
+        clock.unix_timestamp,
+        ctx.bumps.access_pass,
+    )?;
+
     emit!(AccessVerified {
         user: verification.user,
         post: verification.post,
@@ -121,5 +145,379 @@ pub fn handler(ctx: Context<VerifyNftAccess>) -> Result<()> {
         timestamp: clock.unix_timestamp,
     });
 
+    if verification.verified {
+        emit!(AccessGranted {
+            user: verification.user,
+            post: verification.post,
+            gate_type: access_control.gate_type,
+            timestamp: clock.unix_timestamp,
+        });
+    }
+
     Ok(())
 }
+
+/// Counts the distinct qualifying NFTs (by collection or update authority) held by
+/// `user`: the primary NFT plus each (token_account, mint, metadata) triple in `remaining`
+fn count_collection_nfts(
+    user: &Pubkey,
+    primary_amount: u64,
+    primary_mint: &Account<Mint>,
+    primary_metadata: &AccountInfo,
+    remaining: &[AccountInfo],
+    access_control: &AccessControl,
+) -> Result<u32> {
+    verify_nft(
+        primary_amount,
+        &primary_mint.key(),
+        primary_mint.decimals,
+        primary_metadata,
+        access_control,
+    )?;
+
+    require!(
+        remaining.len() % 3 == 0,
+        TokenGateError::InvalidNftCollection
+    );
+
+    // Count distinct NFTs so the same mint supplied twice only counts once
+    let mut counted_mints = vec![primary_mint.key()];
+    for triple in remaining.chunks(3) {
+        let (token_account_info, mint_info, metadata_info) = (&triple[0], &triple[1], &triple[2]);
+        require!(
+            token_account_info.owner == &Token::id(),
+            TokenGateError::TokenAccountOwnerMismatch
+        );
+        let token_account = TokenAccount::try_deserialize(&mut &token_account_info.data.borrow()[..])
+            .map_err(|_| error!(TokenGateError::TokenAccountOwnerMismatch))?;
+        require!(
+            token_account.owner == *user,
+            TokenGateError::TokenAccountOwnerMismatch
+        );
+        require_keys_eq!(
+            mint_info.key(),
+            token_account.mint,
+            TokenGateError::TokenAccountMintMismatch
+        );
+        require!(
+            mint_info.owner == &Token::id(),
+            TokenGateError::TokenAccountMintMismatch
+        );
+        let mint = Mint::try_deserialize(&mut &mint_info.data.borrow()[..])
+            .map_err(|_| error!(TokenGateError::TokenAccountMintMismatch))?;
+        verify_nft(
+            token_account.amount,
+            &token_account.mint,
+            mint.decimals,
+            metadata_info,
+            access_control,
+        )?;
+
+        if !counted_mints.contains(&token_account.mint) {
+            counted_mints.push(token_account.mint);
+        }
+    }
+
+    Ok(counted_mints.len() as u32)
+}
+
+/// Checks that the user holds exactly one of `mint` and that it is a verified
+/// member of one of the gate's accepted collections, or for an update-authority
+/// gate, that the required authority manages it and is a verified creator
+fn verify_nft(
+    amount: u64,
+    mint: &Pubkey,
+    decimals: u8,
+    metadata: &AccountInfo,
+    access_control: &AccessControl,
+) -> Result<()> {
+    check_nft(amount, mint, decimals, metadata, access_control)
+        .map_err(|reason| error!(TokenGateError::from(reason)))?;
+
+    // More than one means a fungible token rather than an NFT
+    require!(amount == 1, TokenGateError::NftNotOwned);
+    Ok(())
+}
+
+/// Checks that the user holds exactly one of the gate's `required_nft_mint`
+fn verify_specific_nft(
+    amount: u64,
+    mint: &Pubkey,
+    decimals: u8,
+    access_control: &AccessControl,
+) -> Result<()> {
+    check_specific_nft(amount, mint, decimals, access_control)
+        .map_err(|reason| error!(TokenGateError::from(reason)))?;
+
+    require!(amount == 1, TokenGateError::NftNotOwned);
+    Ok(())
+}
+
+fn check_specific_nft(
+    amount: u64,
+    mint: &Pubkey,
+    decimals: u8,
+    access_control: &AccessControl,
+) -> std::result::Result<(), NftVerificationError> {
+    if access_control.required_nft_mint.as_ref() != Some(mint) {
+        return Err(NftVerificationError::WrongMint);
+    }
+    check_nft_supply(amount, decimals)
+}
+
+fn check_nft(
+    amount: u64,
+    mint: &Pubkey,
+    decimals: u8,
+    metadata: &AccountInfo,
+    access_control: &AccessControl,
+) -> std::result::Result<(), NftVerificationError> {
+    check_nft_supply(amount, decimals)?;
+    let metadata = load_metadata(mint, metadata)?;
+    if access_control.gate_type == GateType::UpdateAuthority {
+        check_update_authority(&metadata.update_authority, metadata.creators.as_deref(), access_control)
+    } else {
+        check_collection(metadata.collection.as_ref(), access_control)
+    }
+}
+
+/// An NFT mint has no decimals, so a fungible token can't pass as one by
+/// pointing its metadata at the collection. Checked before the metadata is read.
+fn check_nft_supply(amount: u64, decimals: u8) -> std::result::Result<(), NftVerificationError> {
+    if amount == 0 {
+        return Err(NftVerificationError::ZeroBalance);
+    }
+    if decimals != 0 {
+        return Err(NftVerificationError::FungibleMint);
+    }
+    Ok(())
+}
+
+/// Reads the Metaplex metadata for `mint`, which must live at its metadata PDA
+fn load_metadata(
+    mint: &Pubkey,
+    metadata: &AccountInfo,
+) -> std::result::Result<Metadata, NftVerificationError> {
+    let metadata_program_id = mpl_token_metadata::ID;
+    let (expected_metadata_key, _) = Pubkey::find_program_address(
+        &[
+            b"metadata",
+            metadata_program_id.as_ref(),
+            mint.as_ref(),
+        ],
+        &metadata_program_id,
+    );
+    if metadata.key() != expected_metadata_key {
+        return Err(NftVerificationError::NoMetadata);
+    }
+
+    Metadata::safe_deserialize(&metadata.data.borrow())
+        .map_err(|_| NftVerificationError::NoMetadata)
+}
+
+fn check_collection(
+    collection: Option<&Collection>,
+    access_control: &AccessControl,
+) -> std::result::Result<(), NftVerificationError> {
+    let collection = collection.ok_or(NftVerificationError::WrongCollection)?;
+    if !access_control.accepts_nft_collection(&collection.key) {
+        return Err(NftVerificationError::WrongCollection);
+    }
+    if !collection.verified {
+        return Err(NftVerificationError::CollectionUnverified);
+    }
+    Ok(())
+}
+
+/// Legacy NFTs are grouped by update authority; requiring it to also be a
+/// verified creator stops anyone minting lookalikes under a transferred authority
+fn check_update_authority(
+    update_authority: &Pubkey,
+    creators: Option<&[Creator]>,
+    access_control: &AccessControl,
+) -> std::result::Result<(), NftVerificationError> {
+    if access_control.required_update_authority.as_ref() != Some(update_authority) {
+        return Err(NftVerificationError::WrongUpdateAuthority);
+    }
+    let verified_creator = creators
+        .unwrap_or_default()
+        .iter()
+        .any(|creator| creator.address == *update_authority && creator.verified);
+    if !verified_creator {
+        return Err(NftVerificationError::UpdateAuthorityUnverified);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn nft_gate() -> AccessControl {
+        AccessControl {
+            gate_type: GateType::Nft,
+            required_nft_collection: Some(Pubkey::new_unique()),
+            ..AccessControl::new(Pubkey::new_unique(), Pubkey::new_unique(), 255, 0)
+        }
+    }
+
+    fn check_with_metadata_key(
+        amount: u64,
+        mint: &Pubkey,
+        decimals: u8,
+        metadata_key: Pubkey,
+    ) -> std::result::Result<(), NftVerificationError> {
+        let mut lamports = 0;
+        let mut data = Vec::new();
+        let owner = mpl_token_metadata::ID;
+        let metadata = AccountInfo::new(
+            &metadata_key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &owner,
+            false,
+            0,
+        );
+        check_nft(amount, mint, decimals, &metadata, &nft_gate())
+    }
+
+    #[test]
+    fn empty_token_account_is_zero_balance() {
+        let mint = Pubkey::new_unique();
+        let (metadata_key, _) = Metadata::find_pda(&mint);
+        assert_eq!(
+            check_with_metadata_key(0, &mint, 0, metadata_key),
+            Err(NftVerificationError::ZeroBalance)
+        );
+    }
+
+    #[test]
+    fn mint_with_decimals_is_fungible_before_metadata_is_read() {
+        let mint = Pubkey::new_unique();
+        let (metadata_key, _) = Metadata::find_pda(&mint);
+        // One base unit of a 6-decimal token is rejected without ever reaching
+        // the metadata, whatever collection it claims
+        assert_eq!(
+            check_with_metadata_key(1, &mint, 6, metadata_key),
+            Err(NftVerificationError::FungibleMint)
+        );
+
+        let required = Pubkey::new_unique();
+        let gate = AccessControl {
+            gate_type: GateType::SpecificNft,
+            required_nft_collection: None,
+            required_nft_mint: Some(required),
+            ..nft_gate()
+        };
+        assert_eq!(
+            check_specific_nft(1, &required, 9, &gate),
+            Err(NftVerificationError::FungibleMint)
+        );
+    }
+
+    #[test]
+    fn missing_or_misplaced_metadata_is_no_metadata() {
+        let mint = Pubkey::new_unique();
+        let (metadata_key, _) = Metadata::find_pda(&mint);
+        // Right address, nothing written there
+        assert_eq!(
+            check_with_metadata_key(1, &mint, 0, metadata_key),
+            Err(NftVerificationError::NoMetadata)
+        );
+        // Some other account passed as the metadata
+        assert_eq!(
+            check_with_metadata_key(1, &mint, 0, Pubkey::new_unique()),
+            Err(NftVerificationError::NoMetadata)
+        );
+    }
+
+    #[test]
+    fn unlisted_or_missing_collection_is_wrong_collection() {
+        let gate = nft_gate();
+        let other = Collection { verified: true, key: Pubkey::new_unique() };
+        assert_eq!(check_collection(None, &gate), Err(NftVerificationError::WrongCollection));
+        assert_eq!(
+            check_collection(Some(&other), &gate),
+            Err(NftVerificationError::WrongCollection)
+        );
+    }
+
+    #[test]
+    fn unverified_membership_is_collection_unverified() {
+        let gate = nft_gate();
+        let key = gate.required_nft_collection.unwrap();
+        assert_eq!(
+            check_collection(Some(&Collection { verified: false, key }), &gate),
+            Err(NftVerificationError::CollectionUnverified)
+        );
+        assert_eq!(check_collection(Some(&Collection { verified: true, key }), &gate), Ok(()));
+    }
+
+    #[test]
+    fn specific_nft_gate_requires_the_exact_mint() {
+        let required = Pubkey::new_unique();
+        let gate = AccessControl {
+            gate_type: GateType::SpecificNft,
+            required_nft_collection: None,
+            required_nft_mint: Some(required),
+            ..nft_gate()
+        };
+        assert_eq!(check_specific_nft(1, &required, 0, &gate), Ok(()));
+        assert_eq!(check_specific_nft(0, &required, 0, &gate), Err(NftVerificationError::ZeroBalance));
+        // Another mint, even from the same collection, doesn't qualify
+        assert_eq!(
+            check_specific_nft(1, &Pubkey::new_unique(), 0, &gate),
+            Err(NftVerificationError::WrongMint)
+        );
+    }
+
+    #[test]
+    fn update_authority_gate_requires_a_verified_matching_authority() {
+        let authority = Pubkey::new_unique();
+        let gate = AccessControl {
+            gate_type: GateType::UpdateAuthority,
+            required_nft_collection: None,
+            required_update_authority: Some(authority),
+            ..nft_gate()
+        };
+        let creator = |address, verified| Creator { address, verified, share: 100 };
+
+        assert_eq!(
+            check_update_authority(&authority, Some(&[creator(authority, true)]), &gate),
+            Ok(())
+        );
+        assert_eq!(
+            check_update_authority(&Pubkey::new_unique(), Some(&[creator(authority, true)]), &gate),
+            Err(NftVerificationError::WrongUpdateAuthority)
+        );
+        // The authority matches but never signed as a creator
+        assert_eq!(
+            check_update_authority(&authority, Some(&[creator(authority, false)]), &gate),
+            Err(NftVerificationError::UpdateAuthorityUnverified)
+        );
+        assert_eq!(
+            check_update_authority(&authority, None, &gate),
+            Err(NftVerificationError::UpdateAuthorityUnverified)
+        );
+    }
+
+    #[test]
+    fn each_reason_has_its_own_error_code() {
+        let codes = [
+            NftVerificationError::NoMetadata,
+            NftVerificationError::CollectionUnverified,
+            NftVerificationError::WrongCollection,
+            NftVerificationError::ZeroBalance,
+            NftVerificationError::WrongMint,
+            NftVerificationError::WrongUpdateAuthority,
+            NftVerificationError::UpdateAuthorityUnverified,
+            NftVerificationError::FungibleMint,
+        ]
+        .map(|reason| u32::from(TokenGateError::from(reason)));
+        for (i, code) in codes.iter().enumerate() {
+            assert!(!codes[i + 1..].contains(code));
+        }
+    }
+}
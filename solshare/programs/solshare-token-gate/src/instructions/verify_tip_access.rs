@@ -0,0 +1,110 @@
+use anchor_lang::prelude::*;
+use crate::state::{AccessControl, AccessPass, AccessVerification, GateType};
+use crate::error::TokenGateError;
+use crate::events::{AccessGranted, AccessVerified};
+use crate::payment::TipReceipt;
+
+#[derive(Accounts)]
+pub struct VerifyTipAccess<'info> {
+    #[account(
+        seeds = [b"access", access_control.post.as_ref()],
+        bump = access_control.bump
+    )]
+    pub access_control: Account<'info, AccessControl>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + AccessVerification::INIT_SPACE,
+        seeds = [b"verification", user.key().as_ref(), access_control.post.as_ref()],
+        bump
+    )]
+    pub verification: Account<'info, AccessVerification>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + AccessPass::INIT_SPACE,
+        seeds = [b"pass", access_control.post.as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub access_pass: Account<'info, AccessPass>,
+
+    /// CHECK: Owner and layout are validated by TipReceipt::load
+    pub tip_record: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Verifies the viewer from their tip record. A missing or short tip fails the
+/// instruction instead of emitting AccessDenied.
+pub fn handler(ctx: Context<VerifyTipAccess>) -> Result<()> {
+    let access_control = &ctx.accounts.access_control;
+    let user = ctx.accounts.user.key();
+    let clock = Clock::get()?;
+
+    require!(
+        access_control.gate_type == GateType::TipGated,
+        TokenGateError::InvalidGateConfig
+    );
+
+    let tip = TipReceipt::load(&ctx.accounts.tip_record)?;
+    require!(
+        tip.from == user && tip.to == access_control.creator,
+        TokenGateError::TipMismatch
+    );
+    // Tips without a post count toward any of the creator's tip-gated posts
+    require!(
+        tip.post.is_none_or(|post| post == access_control.post),
+        TokenGateError::TipMismatch
+    );
+
+    if tip.amount < access_control.minimum_tip_lamports {
+        return err!(TokenGateError::InsufficientTipAmount);
+    }
+
+    // Access lasts one pass TTL from the tip, so a receipt can't be replayed indefinitely
+    let expires_at = tip
+        .timestamp
+        .checked_add(access_control.pass_ttl())
+        .ok_or(TokenGateError::ArithmeticOverflow)?;
+    require!(clock.unix_timestamp < expires_at, TokenGateError::TipExpired);
+
+    let verification = &mut ctx.accounts.verification;
+    verification.user = user;
+    verification.post = access_control.post;
+    verification.verified = true;
+    verification.verified_at = clock.unix_timestamp;
+    verification.expires_at = Some(expires_at);
+    verification.bump = ctx.bumps.verification;
+
+    let access_pass = &mut ctx.accounts.access_pass;
+    access_pass.record(
+        access_control,
+        user,
+        true,
+        tip.amount,
+        clock.unix_timestamp,
+        ctx.bumps.access_pass,
+    )?;
+    access_pass.verified_until = access_pass.verified_until.min(expires_at);
+
+    emit!(AccessVerified {
+        user,
+        post: access_control.post,
+        verification_type: "tip".to_string(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    emit!(AccessGranted {
+        user,
+        post: access_control.post,
+        gate_type: access_control.gate_type,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -1,8 +1,9 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::TokenAccount;
-use crate::state::{AccessControl, AccessVerification};
+use anchor_spl::token::Token;
+use anchor_spl::token_interface::{Token2022, TokenAccount};
+use crate::state::{AccessControl, AccessPass, AccessVerification, GateType};
 use crate::error::TokenGateError;
-use crate::events::AccessVerified;
+use crate::events::{AccessGranted, AccessVerified};
 
 #[derive(Accounts)]
 pub struct VerifyTokenAccess<'info> {
@@ -20,25 +21,61 @@ pub struct VerifyTokenAccess<'info> {
         bump
     )]
     pub verification: Account<'info, AccessVerification>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + AccessPass::INIT_SPACE,
+        seeds = [b"pass", access_control.post.as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub access_pass: Account<'info, AccessPass>,
     
+    // SPL Token or Token-2022 account; the layout is detected from the owning program
     #[account(
-        constraint = user_token_account.owner == user.key() @ TokenGateError::TokenAccountOwnerMismatch,
-        constraint = Some(user_token_account.mint) == access_control.required_token @ TokenGateError::TokenAccountMintMismatch
+        constraint = user_token_account.owner == user.key() @ TokenGateError::TokenAccountOwnerMismatch
     )]
-    pub user_token_account: Account<'info, TokenAccount>,
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
     
     #[account(mut)]
     pub user: Signer<'info>,
     
     pub system_program: Program<'info, System>,
+    // Remaining accounts: additional user token accounts for multi-token gates
 }
 
 pub fn handler(ctx: Context<VerifyTokenAccess>) -> Result<()> {
-    let access_control = &ctx.accounts.access_control;
-    let verification = &mut ctx.accounts.verification;
-    let user_token_account = &ctx.accounts.user_token_account;
+    let accounts = &mut *ctx.accounts;
+    apply_token_verification(
+        &accounts.access_control,
+        &mut accounts.verification,
+        &mut accounts.access_pass,
+        accounts.user.key(),
+        &accounts.user_token_account,
+        ctx.remaining_accounts,
+        (ctx.bumps.verification, ctx.bumps.access_pass),
+    )
+}
+
+/// Checks `user`'s token holdings against the gate and records the result in
+/// their verification and pass; shared with the delegated verifier path.
+/// Holdings that fall short fail with InsufficientTokenBalance, not AccessDenied.
+pub(crate) fn apply_token_verification(
+    access_control: &AccessControl,
+    verification: &mut AccessVerification,
+    access_pass: &mut AccessPass,
+    user: Pubkey,
+    user_token_account: &InterfaceAccount<TokenAccount>,
+    remaining_accounts: &[AccountInfo],
+    (verification_bump, pass_bump): (u8, u8),
+) -> Result<()> {
     let clock = Clock::get()?;
 
+    // Open gates admit everyone, so there is nothing to verify
+    require!(
+        access_control.gate_type != GateType::Open,
+        TokenGateError::InvalidGateConfig
+    );
     require!(
         access_control.requires_token(),
         TokenGateError::InvalidGateConfig
@@ -48,17 +85,55 @@ pub fn handler(ctx: Context<VerifyTokenAccess>) -> Result<()> {
         TokenGateError::TokenMintRequired
     );
 
+    let requirements = access_control.effective_token_requirements();
     require!(
-        user_token_account.amount >= access_control.minimum_balance,
-        TokenGateError::InsufficientTokenBalance
+        requirements.iter().any(|r| r.mint == user_token_account.mint),
+        TokenGateError::TokenAccountMintMismatch
     );
 
-    verification.user = ctx.accounts.user.key();
+    // Balances are summed per mint, so each account may only appear once
+    let strict = access_control.strict_token_counting;
+    let mut counted = vec![user_token_account.key()];
+    let mut holdings = Vec::new();
+    add_holding(
+        &mut holdings,
+        user_token_account.mint,
+        counted_balance(user_token_account, strict),
+    )?;
+    for account_info in remaining_accounts.iter() {
+        require!(
+            !counted.contains(account_info.key),
+            TokenGateError::DuplicateTokenAccount
+        );
+        counted.push(account_info.key());
+        require!(
+            account_info.owner == &Token::id() || account_info.owner == &Token2022::id(),
+            TokenGateError::TokenAccountOwnerMismatch
+        );
+        let token_account = TokenAccount::try_deserialize(&mut &account_info.data.borrow()[..])
+            .map_err(|_| error!(TokenGateError::TokenAccountOwnerMismatch))?;
+        require!(
+            token_account.owner == user,
+            TokenGateError::TokenAccountOwnerMismatch
+        );
+        add_holding(
+            &mut holdings,
+            token_account.mint,
+            counted_balance(&token_account, strict),
+        )?;
+    }
+
+    if !access_control.token_requirements_met(&holdings) {
+        return err!(TokenGateError::InsufficientTokenBalance);
+    }
+
+    verification.clear_if_stale(access_control);
+    verification.user = user;
     verification.post = access_control.post;
     verification.token_verified = true;
     verification.verified_at = clock.unix_timestamp;
     verification.expires_at = None; // Token access doesn't expire by default
-    verification.bump = ctx.bumps.verification;
+    verification.bump = verification_bump;
 
     // For GateType::Both, only set verified=true if both token AND NFT are verified
     // For GateType::Token, token verification alone is sufficient
@@ -66,8 +141,23 @@ pub fn handler(ctx: Context<VerifyTokenAccess>) -> Result<()> {
         crate::state::GateType::Token => true,
         crate::state::GateType::Both => verification.token_verified && verification.nft_verified,
         crate::state::GateType::Nft => verification.nft_verified, // Should not reach here due to requires_token check
+        crate::state::GateType::NativeSol => false, // Checked live by check_access
+        crate::state::GateType::TipGated => false, // Verified by verify_tip_access
+        crate::state::GateType::Subscription => false, // Checked live by check_access
+        crate::state::GateType::Open => false, // Rejected above
+        crate::state::GateType::SpecificNft => verification.nft_verified, // Should not reach here due to requires_token check
+        crate::state::GateType::UpdateAuthority => verification.nft_verified, // Should not reach here due to requires_token check
     };
 
+    access_pass.record(
+        access_control,
+        verification.user,
+        verification.verified,
+        holdings[0].1,
+        clock.unix_timestamp,
+        pass_bump,
+    )?;
+
     emit!(AccessVerified {
         user: verification.user,
         post: verification.post,
@@ -75,5 +165,39 @@ pub fn handler(ctx: Context<VerifyTokenAccess>) -> Result<()> {
         timestamp: clock.unix_timestamp,
     });
 
+    if verification.verified {
+        emit!(AccessGranted {
+            user: verification.user,
+            post: verification.post,
+            gate_type: access_control.gate_type,
+            timestamp: clock.unix_timestamp,
+        });
+    }
+
     Ok(())
 }
+
+/// Adds `amount` to the running total for `mint`
+fn add_holding(holdings: &mut Vec<(Pubkey, u64)>, mint: Pubkey, amount: u64) -> Result<()> {
+    match holdings.iter_mut().find(|(held, _)| *held == mint) {
+        Some((_, total)) => {
+            *total = total
+                .checked_add(amount)
+                .ok_or(TokenGateError::ArithmeticOverflow)?;
+        }
+        None => holdings.push((mint, amount)),
+    }
+    Ok(())
+}
+
+/// Balance of `account` that counts toward a gate. Frozen accounts count as
+/// empty; in strict mode tokens delegated to someone else don't count either.
+pub(crate) fn counted_balance(account: &TokenAccount, strict: bool) -> u64 {
+    if account.is_frozen() {
+        0
+    } else if strict {
+        account.amount.saturating_sub(account.delegated_amount)
+    } else {
+        account.amount
+    }
+}
@@ -0,0 +1,61 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+use crate::state::{AccessControl, AccessPass, AccessVerification};
+use crate::error::TokenGateError;
+use super::verify_token_access::apply_token_verification;
+
+#[derive(Accounts)]
+pub struct VerifyTokenAccessDelegated<'info> {
+    #[account(
+        seeds = [b"access", access_control.post.as_ref()],
+        bump = access_control.bump,
+        constraint = access_control.verifier == Some(verifier.key()) @ TokenGateError::Unauthorized
+    )]
+    pub access_control: Account<'info, AccessControl>,
+
+    #[account(
+        init_if_needed,
+        payer = verifier,
+        space = 8 + AccessVerification::INIT_SPACE,
+        seeds = [b"verification", user.key().as_ref(), access_control.post.as_ref()],
+        bump
+    )]
+    pub verification: Account<'info, AccessVerification>,
+
+    #[account(
+        init_if_needed,
+        payer = verifier,
+        space = 8 + AccessPass::INIT_SPACE,
+        seeds = [b"pass", access_control.post.as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub access_pass: Account<'info, AccessPass>,
+
+    #[account(
+        constraint = user_token_account.owner == user.key() @ TokenGateError::TokenAccountOwnerMismatch
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: Viewer being verified; their holdings are checked via user_token_account
+    pub user: UncheckedAccount<'info>,
+
+    // The gate's delegate pays for and signs the verification
+    #[account(mut)]
+    pub verifier: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    // Remaining accounts: additional user token accounts for multi-token gates
+}
+
+pub fn handler(ctx: Context<VerifyTokenAccessDelegated>) -> Result<()> {
+    let accounts = &mut *ctx.accounts;
+    apply_token_verification(
+        &accounts.access_control,
+        &mut accounts.verification,
+        &mut accounts.access_pass,
+        accounts.user.key(),
+        &accounts.user_token_account,
+        ctx.remaining_accounts,
+        (ctx.bumps.verification, ctx.bumps.access_pass),
+    )
+}
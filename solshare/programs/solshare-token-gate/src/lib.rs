@@ -1,10 +1,15 @@
+pub mod cnft;
 pub mod error;
 pub mod events;
 pub mod instructions;
 pub mod state;
+pub mod payment;
+pub mod social;
 
 use anchor_lang::prelude::*;
 use instructions::*;
+use cnft::CnftMetadata;
+use state::{AccessResult, GateLogic, GateType, TierRequirement, TokenRequirement};
 
 declare_id!("EeK73A5QDEmBCGVntKgRNYEtPRbKCkHAi2yfBLuMTQAz");
 
@@ -12,6 +17,14 @@ declare_id!("EeK73A5QDEmBCGVntKgRNYEtPRbKCkHAi2yfBLuMTQAz");
 pub mod solshare_token_gate {
     use super::*;
 
+    pub fn initialize_gate_config(ctx: Context<InitializeGateConfig>, social_program: Pubkey) -> Result<()> {
+        initialize_gate_config::handler(ctx, social_program)
+    }
+
+    pub fn update_gate_config(ctx: Context<UpdateGateConfig>, social_program: Pubkey) -> Result<()> {
+        update_gate_config::handler(ctx, social_program)
+    }
+
     pub fn set_access_requirements(
         ctx: Context<SetAccessRequirements>,
         post: Pubkey,
@@ -23,15 +36,187 @@ pub mod solshare_token_gate {
         set_access_requirements::handler(ctx, post, required_token, minimum_balance, required_nft_collection, post_index)
     }
 
+    pub fn set_access_requirements_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SetAccessRequirementsBatch<'info>>,
+        posts: Vec<Pubkey>,
+        post_indices: Vec<u64>,
+        required_token: Option<Pubkey>,
+        minimum_balance: u64,
+        required_nft_collection: Option<Pubkey>,
+    ) -> Result<()> {
+        set_access_requirements_batch::handler(
+            ctx,
+            posts,
+            post_indices,
+            required_token,
+            minimum_balance,
+            required_nft_collection,
+        )
+    }
+
+    pub fn set_sol_access_requirements(
+        ctx: Context<SetSolAccessRequirements>,
+        post: Pubkey,
+        minimum_sol_lamports: u64,
+        post_index: u64,
+    ) -> Result<()> {
+        set_sol_access_requirements::handler(ctx, post, minimum_sol_lamports, post_index)
+    }
+
+    pub fn set_tip_access_requirements(
+        ctx: Context<SetTipAccessRequirements>,
+        post: Pubkey,
+        minimum_tip_lamports: u64,
+        post_index: u64,
+    ) -> Result<()> {
+        set_tip_access_requirements::handler(ctx, post, minimum_tip_lamports, post_index)
+    }
+
+    pub fn set_subscription_access_requirements(
+        ctx: Context<SetSubscriptionAccessRequirements>,
+        post: Pubkey,
+        post_index: u64,
+    ) -> Result<()> {
+        set_subscription_access_requirements::handler(ctx, post, post_index)
+    }
+
+    pub fn set_specific_nft_access_requirements(
+        ctx: Context<SetSpecificNftAccessRequirements>,
+        post: Pubkey,
+        required_nft_mint: Pubkey,
+        post_index: u64,
+    ) -> Result<()> {
+        set_specific_nft_access_requirements::handler(ctx, post, required_nft_mint, post_index)
+    }
+
+    pub fn set_update_authority_access_requirements(
+        ctx: Context<SetUpdateAuthorityAccessRequirements>,
+        post: Pubkey,
+        required_update_authority: Pubkey,
+        post_index: u64,
+    ) -> Result<()> {
+        set_update_authority_access_requirements::handler(ctx, post, required_update_authority, post_index)
+    }
+
     pub fn verify_token_access(ctx: Context<VerifyTokenAccess>) -> Result<()> {
         verify_token_access::handler(ctx)
     }
 
+    pub fn verify_token_access_delegated(ctx: Context<VerifyTokenAccessDelegated>) -> Result<()> {
+        verify_token_access_delegated::handler(ctx)
+    }
+
     pub fn verify_nft_access(ctx: Context<VerifyNftAccess>) -> Result<()> {
         verify_nft_access::handler(ctx)
     }
 
+    pub fn verify_cnft_access<'info>(
+        ctx: Context<'_, '_, 'info, 'info, VerifyCnftAccess<'info>>,
+        root: [u8; 32],
+        metadata: CnftMetadata,
+        leaf_delegate: Pubkey,
+        nonce: u64,
+        index: u32,
+    ) -> Result<()> {
+        verify_cnft_access::handler(ctx, root, metadata, leaf_delegate, nonce, index)
+    }
+
+    pub fn verify_tip_access(ctx: Context<VerifyTipAccess>) -> Result<()> {
+        verify_tip_access::handler(ctx)
+    }
+
     pub fn check_access(ctx: Context<CheckAccess>) -> Result<bool> {
         check_access::handler(ctx)
     }
+
+    pub fn verify_and_log(ctx: Context<VerifyAndLog>, day: i64) -> Result<()> {
+        verify_and_log::handler(ctx, day)
+    }
+
+    pub fn check_access_detailed(ctx: Context<CheckAccessDetailed>) -> Result<AccessResult> {
+        check_access_detailed::handler(ctx)
+    }
+
+    pub fn check_access_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CheckAccessBatch<'info>>,
+        gate_count: u8,
+    ) -> Result<u64> {
+        check_access_batch::handler(ctx, gate_count)
+    }
+
+    pub fn has_valid_pass(ctx: Context<HasValidPass>) -> Result<bool> {
+        has_valid_pass::handler(ctx)
+    }
+
+    pub fn set_pass_ttl(ctx: Context<SetPassTtl>, pass_ttl_seconds: i64) -> Result<()> {
+        set_pass_ttl::handler(ctx, pass_ttl_seconds)
+    }
+
+    pub fn set_token_counting(ctx: Context<SetTokenCounting>, strict: bool) -> Result<()> {
+        set_token_counting::handler(ctx, strict)
+    }
+
+    pub fn remove_access_requirements(ctx: Context<RemoveAccessRequirements>) -> Result<()> {
+        remove_access_requirements::handler(ctx)
+    }
+
+    pub fn set_token_requirements(
+        ctx: Context<SetTokenRequirements>,
+        requirements: Vec<TokenRequirement>,
+        logic: GateLogic,
+    ) -> Result<()> {
+        set_token_requirements::handler(ctx, requirements, logic)
+    }
+
+    pub fn add_to_allowlist(ctx: Context<AddToAllowlist>, wallet: Pubkey) -> Result<()> {
+        add_to_allowlist::handler(ctx, wallet)
+    }
+
+    pub fn remove_from_allowlist(ctx: Context<RemoveFromAllowlist>, wallet: Pubkey) -> Result<()> {
+        remove_from_allowlist::handler(ctx, wallet)
+    }
+
+    pub fn set_minimum_nft_count(ctx: Context<SetMinimumNftCount>, minimum_nft_count: u32) -> Result<()> {
+        set_minimum_nft_count::handler(ctx, minimum_nft_count)
+    }
+
+    pub fn update_access_requirements(ctx: Context<UpdateAccessRequirements>, gate_type: GateType) -> Result<()> {
+        update_access_requirements::handler(ctx, gate_type)
+    }
+
+    pub fn set_nft_collections(ctx: Context<SetNftCollections>, collections: Vec<Pubkey>) -> Result<()> {
+        set_nft_collections::handler(ctx, collections)
+    }
+
+    pub fn set_verifier(ctx: Context<SetVerifier>, verifier: Option<Pubkey>) -> Result<()> {
+        set_verifier::handler(ctx, verifier)
+    }
+
+    pub fn set_tiers(ctx: Context<SetTiers>, tiers: Vec<TierRequirement>) -> Result<()> {
+        set_tiers::handler(ctx, tiers)
+    }
+
+    pub fn check_tier(ctx: Context<CheckTier>) -> Result<u8> {
+        check_tier::handler(ctx)
+    }
+
+    pub fn token_access_shortfall(ctx: Context<TokenAccessShortfall>) -> Result<u64> {
+        token_access_shortfall::handler(ctx)
+    }
+
+    pub fn set_gate_default(
+        ctx: Context<SetGateDefault>,
+        gate_type: GateType,
+        minimum_sol_lamports: u64,
+    ) -> Result<()> {
+        set_gate_default::handler(ctx, gate_type, minimum_sol_lamports)
+    }
+
+    pub fn revoke_access_pass(ctx: Context<RevokeAccessPass>) -> Result<()> {
+        revoke_access_pass::handler(ctx)
+    }
+
+    pub fn migrate_access_control(ctx: Context<MigrateAccessControl>) -> Result<()> {
+        migrate_access_control::handler(ctx)
+    }
 }
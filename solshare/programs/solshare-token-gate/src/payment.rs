@@ -0,0 +1,83 @@
+use anchor_lang::prelude::*;
+use solshare_payment::state::TipRecord;
+use crate::error::TokenGateError;
+
+/// Mirror of the payment program's `TipRecord`; field order matters
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub struct TipReceipt {
+    pub from: Pubkey,
+    pub to: Pubkey,
+    pub amount: u64,
+    pub post: Option<Pubkey>,
+    pub timestamp: i64,
+    pub bump: u8,
+    // Missing from records made before it was added, which read as None
+    pub mint: Option<Pubkey>,
+}
+
+impl TipReceipt {
+    /// Reads a tip record owned by the payment program
+    pub fn load(tip_record: &AccountInfo) -> Result<Self> {
+        require_keys_eq!(*tip_record.owner, solshare_payment::ID, TokenGateError::InvalidTipRecord);
+        Self::from_data(&tip_record.data.borrow())
+    }
+
+    /// Parses tip record data, padding records from before `mint` was
+    /// appended so the missing byte reads as None
+    pub fn from_data(data: &[u8]) -> Result<Self> {
+        require!(
+            data.starts_with(TipRecord::DISCRIMINATOR),
+            TokenGateError::InvalidTipRecord
+        );
+        let mut padded = data[TipRecord::DISCRIMINATOR.len()..].to_vec();
+        if padded.len() < Self::INIT_SPACE {
+            padded.resize(Self::INIT_SPACE, 0);
+        }
+        Self::deserialize(&mut &padded[..])
+            .map_err(|_| error!(TokenGateError::InvalidTipRecord))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn receipt() -> TipReceipt {
+        TipReceipt {
+            from: Pubkey::new_unique(),
+            to: Pubkey::new_unique(),
+            amount: 5_000,
+            post: Some(Pubkey::new_unique()),
+            timestamp: 1_000,
+            bump: 254,
+            mint: None,
+        }
+    }
+
+    fn account_data(receipt: &TipReceipt) -> Vec<u8> {
+        let mut data = TipRecord::DISCRIMINATOR.to_vec();
+        receipt.serialize(&mut data).unwrap();
+        data
+    }
+
+    #[test]
+    fn reads_a_legacy_tip_record_without_a_mint() {
+        let receipt = receipt();
+        let mut data = account_data(&receipt);
+        // The original layout ends at bump
+        data.pop();
+        assert_eq!(data.len(), 122);
+
+        assert!(TipReceipt::from_data(&data).unwrap() == receipt);
+    }
+
+    #[test]
+    fn reads_a_current_tip_record() {
+        let receipt = TipReceipt { mint: Some(Pubkey::new_unique()), ..receipt() };
+        assert!(TipReceipt::from_data(&account_data(&receipt)).unwrap() == receipt);
+
+        let mut other = account_data(&receipt);
+        other[0] ^= 1;
+        assert!(TipReceipt::from_data(&other).is_err());
+    }
+}
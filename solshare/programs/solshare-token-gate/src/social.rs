@@ -0,0 +1,19 @@
+use anchor_lang::prelude::*;
+use crate::error::TokenGateError;
+
+// Anchor account discriminator of the social program's Post
+const POST_DISCRIMINATOR: [u8; 8] = [8, 147, 90, 186, 185, 56, 192, 150];
+
+/// Reads the creator of a Post owned by `social_program`; the creator is the
+/// Post's first field
+pub fn post_creator(post: &AccountInfo, social_program: &Pubkey) -> Result<Pubkey> {
+    require_keys_eq!(*post.owner, *social_program, TokenGateError::InvalidPostAccount);
+
+    let data = post.data.borrow();
+    require!(
+        data.starts_with(&POST_DISCRIMINATOR),
+        TokenGateError::InvalidPostAccount
+    );
+    Pubkey::deserialize(&mut &data[POST_DISCRIMINATOR.len()..])
+        .map_err(|_| error!(TokenGateError::InvalidPostAccount))
+}
@@ -1,10 +1,103 @@
 use anchor_lang::prelude::*;
+use crate::error::TokenGateError;
+
+pub const MAX_TOKEN_REQUIREMENTS: usize = 4;
+pub const MAX_ALLOW_LIST: usize = 32;
+pub const MAX_TIERS: usize = 5;
+pub const MAX_NFT_COLLECTIONS: usize = 4;
+pub const DEFAULT_PASS_TTL_SECONDS: i64 = 60 * 60;
+pub const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
 pub enum GateType {
     Token,
     Nft,
-    Both,
+    Both, // minimum_balance of required_token AND an NFT from the collection, verified separately
+    NativeSol,
+    TipGated,
+    Subscription,
+    Open, // Registered but not enforced; everyone has access
+    SpecificNft, // Exactly one of required_nft_mint, e.g. a one-of-one pass
+    UpdateAuthority, // An NFT whose update authority is required_update_authority, for pre-collection projects
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum GateLogic {
+    All, // Every token requirement must be met
+    Any, // At least one token requirement must be met
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub struct TokenRequirement {
+    pub mint: Pubkey,
+    pub minimum_balance: u64,
+}
+
+/// Why a viewer was denied access; `None` when access is granted
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum AccessDenyReason {
+    None,
+    NotVerified,
+    TokenNotVerified,
+    NftNotVerified,
+    OutsideTimeWindow,
+    WrongMint,
+    InsufficientBalance { shortfall: u64 },
+    NotSystemAccount,
+    SubscriptionInactive,
+}
+
+/// Why an NFT failed `verify_nft_access`, so clients can tell the holder what
+/// to fix; each variant surfaces as its own `TokenGateError`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum NftVerificationError {
+    NoMetadata,
+    CollectionUnverified,
+    WrongCollection,
+    ZeroBalance,
+    WrongMint,
+    WrongUpdateAuthority,
+    UpdateAuthorityUnverified,
+    FungibleMint,
+}
+
+impl From<NftVerificationError> for TokenGateError {
+    fn from(reason: NftVerificationError) -> Self {
+        match reason {
+            NftVerificationError::NoMetadata => TokenGateError::NftMetadataMissing,
+            NftVerificationError::CollectionUnverified => TokenGateError::NftCollectionUnverified,
+            NftVerificationError::WrongCollection => TokenGateError::NftWrongCollection,
+            NftVerificationError::ZeroBalance => TokenGateError::NftZeroBalance,
+            NftVerificationError::WrongMint => TokenGateError::NftWrongMint,
+            NftVerificationError::WrongUpdateAuthority => TokenGateError::NftWrongUpdateAuthority,
+            NftVerificationError::UpdateAuthorityUnverified => {
+                TokenGateError::NftUpdateAuthorityUnverified
+            }
+            NftVerificationError::FungibleMint => TokenGateError::NftFungibleMint,
+        }
+    }
+}
+
+/// Returned by `check_access_detailed`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct AccessResult {
+    pub granted: bool,
+    pub reason: AccessDenyReason,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub struct TierRequirement {
+    pub level: u8,
+    pub min_balance: u64,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct GateConfig {
+    pub admin: Pubkey,
+    // Social program whose post PDAs gates are validated against
+    pub social_program: Pubkey,
+    pub bump: u8,
 }
 
 #[account]
@@ -18,6 +111,73 @@ pub struct AccessControl {
     pub gate_type: GateType,
     pub created_at: i64,
     pub bump: u8,
+    // Multi-token gates; empty means the single required_token/minimum_balance pair applies
+    #[max_len(MAX_TOKEN_REQUIREMENTS)]
+    pub token_requirements: Vec<TokenRequirement>,
+    pub token_logic: GateLogic,
+    // Wallets granted access regardless of holdings
+    #[max_len(MAX_ALLOW_LIST)]
+    pub allow_list: Vec<Pubkey>,
+    // Distinct NFTs from the collection the viewer must hold; 0 is treated as 1
+    pub minimum_nft_count: u32,
+    // How long an AccessPass stays valid; 0 uses DEFAULT_PASS_TTL_SECONDS
+    pub pass_ttl_seconds: i64,
+    // Lamports the viewer's wallet must hold for GateType::NativeSol
+    pub minimum_sol_lamports: u64,
+    // Delegate allowed to verify on behalf of viewers (e.g. a rendering service)
+    pub verifier: Option<Pubkey>,
+    // Access levels by balance of required_token; the highest satisfied level wins
+    #[max_len(MAX_TIERS)]
+    pub tiers: Vec<TierRequirement>,
+    // Smallest tip (net of platform fee) that unlocks a GateType::TipGated post
+    pub minimum_tip_lamports: u64,
+    // Count token balances net of delegated amounts; frozen accounts never count
+    pub strict_token_counting: bool,
+    // Further collections accepted alongside required_nft_collection; an NFT
+    // from any of them qualifies
+    #[max_len(MAX_NFT_COLLECTIONS)]
+    pub required_nft_collections: Vec<Pubkey>,
+    // The single NFT mint a GateType::SpecificNft post requires
+    pub required_nft_mint: Option<Pubkey>,
+    // Bumped by the creator to invalidate every outstanding AccessPass
+    pub gate_epoch: u32,
+    // Update authority (and verified creator) a GateType::UpdateAuthority NFT must have
+    pub required_update_authority: Option<Pubkey>,
+}
+
+/// A creator's fallback gate, applied by check_access to any of their posts
+/// that has no AccessControl of its own
+#[account]
+#[derive(InitSpace)]
+pub struct CreatorGateDefault {
+    pub creator: Pubkey,
+    // Copied from GateConfig so check_access can validate the post without it
+    pub social_program: Pubkey,
+    // Only gates check_access evaluates live (NativeSol, Subscription); others
+    // need a per-post verification
+    pub gate_type: GateType,
+    pub minimum_sol_lamports: u64,
+    pub created_at: i64,
+    pub bump: u8,
+}
+
+impl CreatorGateDefault {
+    pub fn supports_gate_type(gate_type: GateType, minimum_sol_lamports: u64) -> bool {
+        match gate_type {
+            GateType::NativeSol => minimum_sol_lamports > 0,
+            GateType::Subscription => true,
+            _ => false,
+        }
+    }
+
+    /// The gate `post` inherits, in the form check_access evaluates
+    pub fn gate_for(&self, post: Pubkey) -> AccessControl {
+        AccessControl {
+            gate_type: self.gate_type,
+            minimum_sol_lamports: self.minimum_sol_lamports,
+            ..AccessControl::new(post, self.creator, 0, self.created_at)
+        }
+    }
 }
 
 #[account]
@@ -33,12 +193,501 @@ pub struct AccessVerification {
     pub bump: u8,
 }
 
+impl AccessVerification {
+    /// Drops token/NFT results recorded against a previous (removed) gate on the
+    /// same post, so verifying one half of a `Both` gate can't revive the other
+    pub fn clear_if_stale(&mut self, access_control: &AccessControl) {
+        if self.verified_at < access_control.created_at {
+            self.verified = false;
+            self.token_verified = false;
+            self.nft_verified = false;
+        }
+    }
+}
+
+/// Lamports held by a wallet, or `None` if the account isn't a system account
+pub fn system_account_lamports(account: &AccountInfo) -> Option<u64> {
+    (account.owner == &System::id()).then(|| account.lamports())
+}
+
+/// A viewer's unlocks of a post during one UTC day, written by `verify_and_log`
+#[account]
+#[derive(InitSpace)]
+pub struct AccessLog {
+    pub post: Pubkey,
+    pub viewer: Pubkey,
+    pub day: i64,
+    pub first_unlocked_at: i64,
+    pub last_unlocked_at: i64,
+    pub unlock_count: u32,
+    pub bump: u8,
+}
+
+impl AccessLog {
+    /// Records an unlock at `now`; the first unlock of the day fills in the log
+    pub fn record(&mut self, post: Pubkey, viewer: Pubkey, day: i64, now: i64, bump: u8) -> Result<()> {
+        if self.unlock_count == 0 {
+            self.post = post;
+            self.viewer = viewer;
+            self.day = day;
+            self.first_unlocked_at = now;
+            self.bump = bump;
+        }
+        self.last_unlocked_at = now;
+        self.unlock_count = self
+            .unlock_count
+            .checked_add(1)
+            .ok_or(TokenGateError::ArithmeticOverflow)?;
+        Ok(())
+    }
+}
+
+/// Cached result of a successful verification, checked by `has_valid_pass`
+#[account]
+#[derive(InitSpace)]
+pub struct AccessPass {
+    pub post: Pubkey,
+    pub viewer: Pubkey,
+    pub issued_at: i64,
+    pub verified_until: i64,
+    pub bump: u8,
+    // Balance counted at the last successful verification
+    pub verified_balance: u64,
+    // AccessControl::gate_epoch the pass was issued under
+    pub gate_epoch: u32,
+}
+
+impl AccessPass {
+    /// Records a verification; the pass is only extended once the viewer has full access
+    pub fn record(
+        &mut self,
+        access_control: &AccessControl,
+        viewer: Pubkey,
+        granted: bool,
+        verified_balance: u64,
+        now: i64,
+        bump: u8,
+    ) -> Result<()> {
+        self.post = access_control.post;
+        self.viewer = viewer;
+        self.bump = bump;
+        if granted {
+            self.issued_at = now;
+            self.verified_until = now
+                .checked_add(access_control.pass_ttl())
+                .ok_or(TokenGateError::ArithmeticOverflow)?;
+            self.verified_balance = verified_balance;
+            self.gate_epoch = access_control.gate_epoch;
+        }
+        Ok(())
+    }
+
+    pub fn is_valid(&self, access_control: &AccessControl, now: i64) -> bool {
+        // Passes issued for a previous (removed) gate on the same post don't count
+        // and neither do passes from before the creator's last revocation
+        self.issued_at >= access_control.created_at
+            && self.gate_epoch == access_control.gate_epoch
+            && now < self.verified_until
+    }
+}
+
 impl AccessControl {
+    /// An Open gate on `post` with every requirement empty; setters fill in
+    /// their gate type and requirements over it
+    pub fn new(post: Pubkey, creator: Pubkey, bump: u8, now: i64) -> Self {
+        Self {
+            post,
+            creator,
+            required_token: None,
+            minimum_balance: 0,
+            required_nft_collection: None,
+            gate_type: GateType::Open,
+            created_at: now,
+            bump,
+            token_requirements: Vec::new(),
+            token_logic: GateLogic::All,
+            allow_list: Vec::new(),
+            minimum_nft_count: 1,
+            pass_ttl_seconds: DEFAULT_PASS_TTL_SECONDS,
+            minimum_sol_lamports: 0,
+            verifier: None,
+            tiers: Vec::new(),
+            minimum_tip_lamports: 0,
+            strict_token_counting: false,
+            required_nft_collections: Vec::new(),
+            required_nft_mint: None,
+            gate_epoch: 0,
+            required_update_authority: None,
+        }
+    }
+
+    /// The original layout, post through bump, before any fields were appended
+    pub const LEGACY_SIZE: usize = 156;
+
+    /// Reads a gate saved under an older, shorter layout. Every field appended
+    /// so far reads zeroed bytes as its default (empty, None, 0 or
+    /// GateLogic::All), and a zero minimum_nft_count or pass_ttl_seconds
+    /// already means the default, so padding the data out to full size is enough.
+    pub fn upgrade_layout(data: &[u8]) -> Result<Self> {
+        let size = 8 + Self::INIT_SPACE;
+        require!(
+            (Self::LEGACY_SIZE..size).contains(&data.len()),
+            TokenGateError::InvalidAccessControlLayout
+        );
+        let mut padded = data.to_vec();
+        padded.resize(size, 0);
+        Self::try_deserialize(&mut &padded[..])
+    }
+
+    pub fn pass_ttl(&self) -> i64 {
+        if self.pass_ttl_seconds > 0 {
+            self.pass_ttl_seconds
+        } else {
+            DEFAULT_PASS_TTL_SECONDS
+        }
+    }
+
     pub fn requires_token(&self) -> bool {
         matches!(self.gate_type, GateType::Token | GateType::Both)
     }
 
     pub fn requires_nft(&self) -> bool {
-        matches!(self.gate_type, GateType::Nft | GateType::Both)
+        matches!(
+            self.gate_type,
+            GateType::Nft | GateType::Both | GateType::SpecificNft | GateType::UpdateAuthority
+        )
+    }
+
+    pub fn required_nft_count(&self) -> u32 {
+        self.minimum_nft_count.max(1)
+    }
+
+    pub fn nft_count_met(&self, held: u32) -> bool {
+        held >= self.required_nft_count()
+    }
+
+    /// Whether an NFT from `collection` counts toward this gate
+    pub fn accepts_nft_collection(&self, collection: &Pubkey) -> bool {
+        self.required_nft_collection.as_ref() == Some(collection)
+            || self.required_nft_collections.contains(collection)
+    }
+
+    /// Whether the stored requirements are enough to enforce `gate_type`
+    pub fn supports_gate_type(&self, gate_type: GateType) -> bool {
+        match gate_type {
+            GateType::Token => self.required_token.is_some() && self.required_nft_collection.is_none(),
+            GateType::Nft => self.required_token.is_none() && self.required_nft_collection.is_some(),
+            GateType::Both => self.required_token.is_some() && self.required_nft_collection.is_some(),
+            GateType::NativeSol => self.minimum_sol_lamports > 0,
+            GateType::TipGated => self.minimum_tip_lamports > 0,
+            GateType::SpecificNft => self.required_nft_mint.is_some(),
+            GateType::UpdateAuthority => self.required_update_authority.is_some(),
+            GateType::Subscription | GateType::Open => true,
+        }
+    }
+
+    /// Highest tier level a balance of `required_token` satisfies, 0 for none
+    pub fn tier_for_balance(&self, balance: u64) -> u8 {
+        self.tiers
+            .iter()
+            .filter(|tier| balance >= tier.min_balance)
+            .map(|tier| tier.level)
+            .max()
+            .unwrap_or(0)
+    }
+
+    pub fn is_allow_listed(&self, wallet: &Pubkey) -> bool {
+        self.allow_list.contains(wallet)
+    }
+
+    /// Evaluates a viewer's stored verification (or, for SOL and subscription
+    /// gates, their wallet balance and subscription state) against this gate
+    pub fn evaluate_access(
+        &self,
+        user: &Pubkey,
+        user_lamports: Option<u64>,
+        subscription_active: Option<bool>,
+        verification: Option<&AccessVerification>,
+        now: i64,
+    ) -> AccessDenyReason {
+        // Open gates admit everyone; allow-listed wallets bypass every other check
+        if self.gate_type == GateType::Open || self.is_allow_listed(user) {
+            return AccessDenyReason::None;
+        }
+
+        // SOL gates are checked live against the wallet; no verification step
+        if self.gate_type == GateType::NativeSol {
+            return match user_lamports {
+                None => AccessDenyReason::NotSystemAccount,
+                Some(lamports) if lamports < self.minimum_sol_lamports => {
+                    AccessDenyReason::InsufficientBalance {
+                        shortfall: self.minimum_sol_lamports - lamports,
+                    }
+                }
+                Some(_) => AccessDenyReason::None,
+            };
+        }
+
+        // Subscription gates are checked live against the payment program
+        if self.gate_type == GateType::Subscription {
+            return match subscription_active {
+                None => AccessDenyReason::NotVerified,
+                Some(false) => AccessDenyReason::SubscriptionInactive,
+                Some(true) => AccessDenyReason::None,
+            };
+        }
+
+        let Some(verification) = verification else {
+            return AccessDenyReason::NotVerified;
+        };
+
+        // Tip gates are only satisfied by a verified tip receipt
+        if self.gate_type == GateType::TipGated && !verification.verified {
+            return AccessDenyReason::NotVerified;
+        }
+
+        // Explicitly verify based on gate type for defense in depth
+        // This ensures GateType::Both requires BOTH verifications, not just one
+        if self.requires_token() && !verification.token_verified {
+            return AccessDenyReason::TokenNotVerified;
+        }
+        if self.requires_nft() && !verification.nft_verified {
+            return AccessDenyReason::NftNotVerified;
+        }
+
+        // A verification issued before the current gate was created belongs to a
+        // previous (removed) gate on the same post
+        if verification.verified_at < self.created_at {
+            return AccessDenyReason::NotVerified;
+        }
+
+        if let Some(expires_at) = verification.expires_at {
+            if now >= expires_at {
+                return AccessDenyReason::OutsideTimeWindow;
+            }
+        }
+
+        AccessDenyReason::None
+    }
+
+    /// Token requirements in effect, falling back to the single-token configuration
+    pub fn effective_token_requirements(&self) -> Vec<TokenRequirement> {
+        if !self.token_requirements.is_empty() {
+            return self.token_requirements.clone();
+        }
+        self.required_token
+            .map(|mint| TokenRequirement {
+                mint,
+                minimum_balance: self.minimum_balance,
+            })
+            .into_iter()
+            .collect()
+    }
+
+    /// Evaluates the token requirements against the viewer's (mint, amount) holdings
+    pub fn token_requirements_met(&self, holdings: &[(Pubkey, u64)]) -> bool {
+        let requirements = self.effective_token_requirements();
+        if requirements.is_empty() {
+            return false;
+        }
+
+        let is_met = |requirement: &TokenRequirement| {
+            holdings
+                .iter()
+                .any(|(mint, amount)| *mint == requirement.mint && *amount >= requirement.minimum_balance)
+        };
+
+        match self.token_logic {
+            GateLogic::All => requirements.iter().all(is_met),
+            GateLogic::Any => requirements.iter().any(is_met),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn nft_gate(minimum_nft_count: u32) -> AccessControl {
+        AccessControl {
+            gate_type: GateType::Nft,
+            required_nft_collection: Some(Pubkey::new_unique()),
+            minimum_nft_count,
+            ..AccessControl::new(Pubkey::new_unique(), Pubkey::new_unique(), 255, 0)
+        }
+    }
+
+    #[test]
+    fn accepts_any_listed_collection() {
+        let mut gate = nft_gate(1);
+        let primary = gate.required_nft_collection.unwrap();
+        let partner = Pubkey::new_unique();
+        assert!(gate.accepts_nft_collection(&primary));
+        assert!(!gate.accepts_nft_collection(&partner));
+
+        gate.required_nft_collections.push(partner);
+        assert!(gate.accepts_nft_collection(&partner));
+        assert!(!gate.accepts_nft_collection(&Pubkey::new_unique()));
+    }
+
+    #[test]
+    fn open_gate_grants_everyone() {
+        let mut gate = nft_gate(1);
+        assert_eq!(
+            gate.evaluate_access(&Pubkey::new_unique(), None, None, None, 0),
+            AccessDenyReason::NotVerified
+        );
+
+        gate.gate_type = GateType::Open;
+        assert_eq!(
+            gate.evaluate_access(&Pubkey::new_unique(), None, None, None, 0),
+            AccessDenyReason::None
+        );
+    }
+
+    #[test]
+    fn nft_count_threshold() {
+        let gate = nft_gate(3);
+        assert!(!gate.nft_count_met(2));
+        assert!(gate.nft_count_met(3));
+        assert!(gate.nft_count_met(4));
+    }
+
+    #[test]
+    fn nft_count_defaults_to_one() {
+        let gate = nft_gate(0);
+        assert!(!gate.nft_count_met(0));
+        assert!(gate.nft_count_met(1));
+    }
+
+    fn verification(token_verified: bool, nft_verified: bool, verified_at: i64) -> AccessVerification {
+        AccessVerification {
+            user: Pubkey::new_unique(),
+            post: Pubkey::new_unique(),
+            verified: token_verified && nft_verified,
+            token_verified,
+            nft_verified,
+            verified_at,
+            expires_at: None,
+            bump: 255,
+        }
+    }
+
+    #[test]
+    fn both_gate_requires_token_and_nft() {
+        let mut gate = nft_gate(1);
+        gate.gate_type = GateType::Both;
+        gate.required_token = Some(Pubkey::new_unique());
+        gate.minimum_balance = 50;
+        let user = Pubkey::new_unique();
+
+        assert_eq!(
+            gate.evaluate_access(&user, None, None, Some(&verification(true, false, 0)), 0),
+            AccessDenyReason::NftNotVerified
+        );
+        assert_eq!(
+            gate.evaluate_access(&user, None, None, Some(&verification(false, true, 0)), 0),
+            AccessDenyReason::TokenNotVerified
+        );
+        assert_eq!(
+            gate.evaluate_access(&user, None, None, Some(&verification(true, true, 0)), 0),
+            AccessDenyReason::None
+        );
+    }
+
+    #[test]
+    fn stale_half_is_cleared_for_a_new_gate() {
+        let mut gate = nft_gate(1);
+        gate.gate_type = GateType::Both;
+        gate.created_at = 100;
+
+        // Token verified against a gate that was since removed and recreated
+        let mut stale = verification(true, false, 50);
+        stale.clear_if_stale(&gate);
+        assert!(!stale.token_verified);
+
+        let mut current = verification(true, false, 150);
+        current.clear_if_stale(&gate);
+        assert!(current.token_verified);
+    }
+
+    #[test]
+    fn bumping_gate_epoch_invalidates_passes() {
+        let mut gate = nft_gate(1);
+        let mut pass = AccessPass {
+            post: gate.post,
+            viewer: Pubkey::new_unique(),
+            issued_at: 0,
+            verified_until: 0,
+            bump: 255,
+            verified_balance: 0,
+            gate_epoch: 0,
+        };
+        pass.record(&gate, pass.viewer, true, 1, 10, 255).unwrap();
+        assert!(pass.is_valid(&gate, 20));
+        assert_eq!(pass.verified_balance, 1);
+
+        gate.gate_epoch += 1;
+        assert!(!pass.is_valid(&gate, 20));
+
+        // Re-verifying under the new epoch restores the pass
+        pass.record(&gate, pass.viewer, true, 1, 30, 255).unwrap();
+        assert!(pass.is_valid(&gate, 40));
+    }
+
+    #[test]
+    fn post_inherits_the_creator_default_gate() {
+        assert!(!CreatorGateDefault::supports_gate_type(GateType::Token, 0));
+        assert!(!CreatorGateDefault::supports_gate_type(GateType::NativeSol, 0));
+        assert!(CreatorGateDefault::supports_gate_type(GateType::NativeSol, 1_000));
+
+        let default = CreatorGateDefault {
+            creator: Pubkey::new_unique(),
+            social_program: Pubkey::new_unique(),
+            gate_type: GateType::NativeSol,
+            minimum_sol_lamports: 1_000,
+            created_at: 0,
+            bump: 255,
+        };
+        let post = Pubkey::new_unique();
+        let gate = default.gate_for(post);
+        assert!(gate.post == post && gate.creator == default.creator);
+
+        let viewer = Pubkey::new_unique();
+        assert_eq!(
+            gate.evaluate_access(&viewer, Some(999), None, None, 0),
+            AccessDenyReason::InsufficientBalance { shortfall: 1 }
+        );
+        assert_eq!(
+            gate.evaluate_access(&viewer, Some(1_000), None, None, 0),
+            AccessDenyReason::None
+        );
+    }
+
+    #[test]
+    fn upgrades_a_legacy_gate() {
+        // A legacy gate's appended fields are all zero bytes
+        let legacy = AccessControl { pass_ttl_seconds: 0, ..nft_gate(0) };
+        let mut data = Vec::new();
+        legacy.try_serialize(&mut data).unwrap();
+        data.truncate(AccessControl::LEGACY_SIZE);
+
+        let upgraded = AccessControl::upgrade_layout(&data).unwrap();
+        assert_eq!(upgraded.post, legacy.post);
+        assert_eq!(upgraded.creator, legacy.creator);
+        assert_eq!(upgraded.required_nft_collection, legacy.required_nft_collection);
+        assert!(upgraded.gate_type == GateType::Nft);
+        assert_eq!(upgraded.bump, 255);
+        assert!(upgraded.token_requirements.is_empty() && upgraded.allow_list.is_empty());
+        assert!(upgraded.token_logic == GateLogic::All);
+        assert_eq!(upgraded.required_nft_count(), 1);
+        assert_eq!(upgraded.pass_ttl(), DEFAULT_PASS_TTL_SECONDS);
+        assert_eq!((upgraded.verifier, upgraded.gate_epoch), (None, 0));
+
+        let mut current = Vec::new();
+        upgraded.try_serialize(&mut current).unwrap();
+        current.resize(8 + AccessControl::INIT_SPACE, 0);
+        assert!(AccessControl::upgrade_layout(&current).is_err());
+        assert!(AccessControl::upgrade_layout(&data[..AccessControl::LEGACY_SIZE - 1]).is_err());
     }
 }
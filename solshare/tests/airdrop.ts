@@ -16,6 +16,7 @@ import {
   mintTo,
   getAssociatedTokenAddressSync,
   getAccount,
+  freezeAccount,
 } from "@solana/spl-token";
 import * as crypto from "crypto";
 
@@ -143,6 +144,30 @@ describe("solshare-airdrop", () => {
     );
   });
 
+  function recipientReceiptPda(campaign: PublicKey, recipient: PublicKey) {
+    const [pda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("receipt"), campaign.toBuffer(), recipient.toBuffer()],
+      program.programId
+    );
+    return pda;
+  }
+
+  // distribute_batch takes each recipient as (wallet, token account, receipt PDA)
+  function recipientAccounts(
+    campaign: PublicKey,
+    recipients: [PublicKey, PublicKey][]
+  ) {
+    return recipients.flatMap(([wallet, ata]) => [
+      { pubkey: wallet, isWritable: false, isSigner: false },
+      { pubkey: ata, isWritable: true, isSigner: false },
+      {
+        pubkey: recipientReceiptPda(campaign, wallet),
+        isWritable: true,
+        isSigner: false,
+      },
+    ]);
+  }
+
   describe("Campaign Creation", () => {
     it("creates a campaign with total_recipients", async () => {
       await program.methods
@@ -150,7 +175,8 @@ describe("solshare-airdrop", () => {
           campaignId,
           AMOUNT_PER_RECIPIENT,
           TOTAL_RECIPIENTS,
-          crankAuthority.publicKey
+          crankAuthority.publicKey,
+          null
         )
         .accounts({
           creator: creator.publicKey,
@@ -188,19 +214,47 @@ describe("solshare-airdrop", () => {
       assert.deepEqual(campaignState.crankAuthority, crankAuthority.publicKey);
       assert.equal(campaignState.bump, campaignBump);
     });
-  });
 
-  describe("Campaign Funding", () => {
-    it("funds a campaign (transfers tokens to escrow, status -> Funded)", async () => {
-      const fundAmount = new anchor.BN(
-        AMOUNT_PER_RECIPIENT.toNumber() * TOTAL_RECIPIENTS
-      );
+    it("rejects reusing a campaign id for the same creator", async () => {
+      try {
+        await program.methods
+          .createCampaign(
+            campaignId,
+            AMOUNT_PER_RECIPIENT.muln(2),
+            1,
+            crankAuthority.publicKey,
+            null
+          )
+          .accounts({
+            creator: creator.publicKey,
+            campaign: campaignPda,
+            tokenMint: tokenMint,
+            escrowAta: escrowAta,
+            systemProgram: SystemProgram.programId,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          })
+          .signers([creator])
+          .rpc();
+        assert.fail("Should have failed - campaign id already used");
+      } catch (e: any) {
+        expect(e.message).to.include("CampaignIdAlreadyUsed");
+      }
 
-      const creatorAtaBefore = await getAccount(provider.connection, creatorAta);
-      const escrowAtaBefore = await getAccount(provider.connection, escrowAta);
+      // The original campaign is left untouched
+      const campaignState = await program.account.campaignState.fetch(campaignPda);
+      assert.equal(
+        campaignState.amountPerRecipient.toNumber(),
+        AMOUNT_PER_RECIPIENT.toNumber()
+      );
+      assert.equal(campaignState.totalRecipients, TOTAL_RECIPIENTS);
+    });
+  });
 
-      await program.methods
-        .fundCampaign(fundAmount)
+  describe("Campaign Funding", () => {
+    function fundCampaign(amount: anchor.BN) {
+      return program.methods
+        .fundCampaign(amount)
         .accounts({
           creator: creator.publicKey,
           campaign: campaignPda,
@@ -210,6 +264,32 @@ describe("solshare-airdrop", () => {
         })
         .signers([creator])
         .rpc();
+    }
+
+    async function campaignFundedEvent(sig: string) {
+      const tx = await provider.connection.getTransaction(sig, {
+        commitment: "confirmed",
+        maxSupportedTransactionVersion: 0,
+      });
+      const parser = new anchor.EventParser(
+        program.programId,
+        new anchor.BorshCoder(program.idl)
+      );
+      return [...parser.parseLogs(tx!.meta!.logMessages!)].find(
+        (e) => e.name === "campaignFunded"
+      )!;
+    }
+
+    it("funds a campaign (transfers tokens to escrow, status -> Funded)", async () => {
+      // One recipient short; the top-up below covers the rest
+      const fundAmount = new anchor.BN(
+        AMOUNT_PER_RECIPIENT.toNumber() * (TOTAL_RECIPIENTS - 1)
+      );
+
+      const creatorAtaBefore = await getAccount(provider.connection, creatorAta);
+      const escrowAtaBefore = await getAccount(provider.connection, escrowAta);
+
+      await fundCampaign(fundAmount);
 
       const creatorAtaAfter = await getAccount(provider.connection, creatorAta);
       const escrowAtaAfter = await getAccount(provider.connection, escrowAta);
@@ -226,27 +306,26 @@ describe("solshare-airdrop", () => {
       const campaignState = await program.account.campaignState.fetch(campaignPda);
       assert.deepEqual(campaignState.status, { funded: {} });
       assert.equal(campaignState.totalAmount.toNumber(), fundAmount.toNumber());
+      assert.equal(campaignState.fundingEventsCount, 1);
     });
 
-    it("fails to fund non-Draft campaign", async () => {
-      const fundAmount = new anchor.BN(1_000_000);
+    it("tops up a Funded campaign and reports the running total", async () => {
+      const sig = await fundCampaign(AMOUNT_PER_RECIPIENT);
+      await provider.connection.confirmTransaction(sig, "confirmed");
 
-      try {
-        await program.methods
-          .fundCampaign(fundAmount)
-          .accounts({
-            creator: creator.publicKey,
-            campaign: campaignPda,
-            creatorAta: creatorAta,
-            escrowAta: escrowAta,
-            tokenProgram: TOKEN_PROGRAM_ID,
-          })
-          .signers([creator])
-          .rpc();
-        assert.fail("Should have failed - campaign is not in Draft status");
-      } catch (e: any) {
-        expect(e.message).to.include("InvalidStatus");
-      }
+      const fullAmount = AMOUNT_PER_RECIPIENT.toNumber() * TOTAL_RECIPIENTS;
+      const campaignState = await program.account.campaignState.fetch(campaignPda);
+      assert.deepEqual(campaignState.status, { funded: {} });
+      assert.equal(campaignState.totalAmount.toNumber(), fullAmount);
+      assert.equal(campaignState.fundingEventsCount, 2);
+
+      const escrowAccount = await getAccount(provider.connection, escrowAta);
+      assert.equal(Number(escrowAccount.amount), fullAmount);
+
+      const event = await campaignFundedEvent(sig);
+      assert.equal(event.data.amount.toNumber(), AMOUNT_PER_RECIPIENT.toNumber());
+      assert.equal(event.data.totalAmount.toNumber(), fullAmount);
+      assert.equal(event.data.fundingEventsCount, 2);
     });
   });
 
@@ -258,16 +337,16 @@ describe("solshare-airdrop", () => {
       );
 
       await program.methods
-        .distributeBatch(1)
+        .distributeBatch(0, 1)
         .accounts({
           crankAuthority: crankAuthority.publicKey,
           campaign: campaignPda,
           escrowAta: escrowAta,
+          crankAta: null,
           tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
         })
-        .remainingAccounts([
-          { pubkey: recipient1Ata, isWritable: true, isSigner: false },
-        ])
+        .remainingAccounts(recipientAccounts(campaignPda, [[recipient1.publicKey, recipient1Ata]]))
         .signers([crankAuthority])
         .rpc();
 
@@ -291,20 +370,43 @@ describe("solshare-airdrop", () => {
       assert.deepEqual(campaignState.status, { processing: {} });
     });
 
+    it("fails to top up a campaign once distribution has started", async () => {
+      try {
+        await program.methods
+          .fundCampaign(new anchor.BN(1_000_000))
+          .accounts({
+            creator: creator.publicKey,
+            campaign: campaignPda,
+            creatorAta: creatorAta,
+            escrowAta: escrowAta,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([creator])
+          .rpc();
+        assert.fail("Should have failed - campaign is Processing");
+      } catch (e: any) {
+        expect(e.message).to.include("InvalidStatus");
+      }
+    });
+
     it("auto-completes when distributed_count >= total_recipients (status -> Completed)", async () => {
       // Distribute to remaining 2 recipients in one batch
       await program.methods
-        .distributeBatch(2)
+        .distributeBatch(1, 2)
         .accounts({
           crankAuthority: crankAuthority.publicKey,
           campaign: campaignPda,
           escrowAta: escrowAta,
+          crankAta: null,
           tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
         })
-        .remainingAccounts([
-          { pubkey: recipient2Ata, isWritable: true, isSigner: false },
-          { pubkey: recipient3Ata, isWritable: true, isSigner: false },
-        ])
+        .remainingAccounts(
+          recipientAccounts(campaignPda, [
+            [recipient2.publicKey, recipient2Ata],
+            [recipient3.publicKey, recipient3Ata],
+          ])
+        )
         .signers([crankAuthority])
         .rpc();
 
@@ -348,7 +450,8 @@ describe("solshare-airdrop", () => {
           secCampaignId,
           AMOUNT_PER_RECIPIENT,
           1,
-          crankAuthority.publicKey
+          crankAuthority.publicKey,
+          null
         )
         .accounts({
           creator: creator.publicKey,
@@ -379,16 +482,16 @@ describe("solshare-airdrop", () => {
     it("fails when recipient ATA has wrong mint (InvalidRecipientMint)", async () => {
       try {
         await program.methods
-          .distributeBatch(1)
+          .distributeBatch(0, 1)
           .accounts({
             crankAuthority: crankAuthority.publicKey,
             campaign: secCampaignPda,
             escrowAta: secEscrowAta,
+            crankAta: null,
             tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
           })
-          .remainingAccounts([
-            { pubkey: wrongMintRecipientAta, isWritable: true, isSigner: false },
-          ])
+          .remainingAccounts(recipientAccounts(secCampaignPda, [[recipient1.publicKey, wrongMintRecipientAta]]))
           .signers([crankAuthority])
           .rpc();
         assert.fail("Should have failed - wrong mint on recipient ATA");
@@ -396,6 +499,31 @@ describe("solshare-airdrop", () => {
         expect(e.message).to.include("InvalidRecipientMint");
       }
     });
+
+    it("fails when a right-mint ATA belongs to someone else (RecipientOwnerMismatch)", async () => {
+      try {
+        // recipient2's allocation routed into recipient1's token account
+        await program.methods
+          .distributeBatch(0, 1)
+          .accounts({
+            crankAuthority: crankAuthority.publicKey,
+            campaign: secCampaignPda,
+            escrowAta: secEscrowAta,
+            crankAta: null,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+          })
+          .remainingAccounts(recipientAccounts(secCampaignPda, [[recipient2.publicKey, recipient1Ata]]))
+          .signers([crankAuthority])
+          .rpc();
+        assert.fail("Should have failed - recipient ATA owned by someone else");
+      } catch (e: any) {
+        expect(e.message).to.include("RecipientOwnerMismatch");
+      }
+
+      const state = await program.account.campaignState.fetch(secCampaignPda);
+      assert.equal(state.distributionCursor, 0);
+    });
   });
 
   describe("Security: Crank Authority", () => {
@@ -428,7 +556,8 @@ describe("solshare-airdrop", () => {
           crankCampaignId,
           AMOUNT_PER_RECIPIENT,
           1,
-          crankAuthority.publicKey
+          crankAuthority.publicKey,
+          null
         )
         .accounts({
           creator: creator.publicKey,
@@ -459,16 +588,16 @@ describe("solshare-airdrop", () => {
     it("fails when non-crank-authority tries to distribute (UnauthorizedCrank)", async () => {
       try {
         await program.methods
-          .distributeBatch(1)
+          .distributeBatch(0, 1)
           .accounts({
             crankAuthority: nonCrankAuthority.publicKey,
             campaign: crankCampaignPda,
             escrowAta: crankEscrowAta,
+            crankAta: null,
             tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
           })
-          .remainingAccounts([
-            { pubkey: recipient1Ata, isWritable: true, isSigner: false },
-          ])
+          .remainingAccounts(recipientAccounts(crankCampaignPda, [[recipient1.publicKey, recipient1Ata]]))
           .signers([nonCrankAuthority])
           .rpc();
         assert.fail("Should have failed - unauthorized crank authority");
@@ -507,7 +636,8 @@ describe("solshare-airdrop", () => {
           refundCampaignId,
           AMOUNT_PER_RECIPIENT,
           2,
-          crankAuthority.publicKey
+          crankAuthority.publicKey,
+          null
         )
         .accounts({
           creator: creator.publicKey,
@@ -550,6 +680,7 @@ describe("solshare-airdrop", () => {
           creator: creator.publicKey,
           campaign: refundCampaignPda,
           creatorAta: creatorAta,
+          refundDestinationAta: null,
           escrowAta: refundEscrowAta,
           tokenProgram: TOKEN_PROGRAM_ID,
         })
@@ -580,6 +711,10 @@ describe("solshare-airdrop", () => {
       // The escrow should be empty (all tokens distributed)
       const escrowAccount = await getAccount(provider.connection, escrowAta);
       assert.equal(Number(escrowAccount.amount), 0);
+      const escrowRent = await provider.connection.getBalance(escrowAta);
+      const creatorLamportsBefore = await provider.connection.getBalance(
+        creator.publicKey
+      );
 
       await program.methods
         .refund()
@@ -587,6 +722,7 @@ describe("solshare-airdrop", () => {
           creator: creator.publicKey,
           campaign: campaignPda,
           creatorAta: creatorAta,
+          refundDestinationAta: null,
           escrowAta: escrowAta,
           tokenProgram: TOKEN_PROGRAM_ID,
         })
@@ -595,32 +731,2132 @@ describe("solshare-airdrop", () => {
 
       const campaignAfter = await program.account.campaignState.fetch(campaignPda);
       assert.deepEqual(campaignAfter.status, { cancelled: {} });
+
+      // Nothing was left to refund, but the escrow's rent still comes back
+      assert.isNull(await provider.connection.getAccountInfo(escrowAta));
+      const creatorLamportsAfter = await provider.connection.getBalance(
+        creator.publicKey
+      );
+      assert.equal(creatorLamportsAfter - creatorLamportsBefore, escrowRent);
+    });
+
+    it("treats a second refund of a cancelled campaign as a no-op", async () => {
+      // refundCampaignPda was cancelled above and its escrow ATA closed
+      const escrowInfo = await provider.connection.getAccountInfo(refundEscrowAta);
+      assert.isNull(escrowInfo);
+      const creatorAtaBefore = await getAccount(provider.connection, creatorAta);
+
+      await program.methods
+        .refund()
+        .accounts({
+          creator: creator.publicKey,
+          campaign: refundCampaignPda,
+          creatorAta: creatorAta,
+          refundDestinationAta: null,
+          escrowAta: refundEscrowAta,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([creator])
+        .rpc();
+
+      const creatorAtaAfter = await getAccount(provider.connection, creatorAta);
+      assert.equal(
+        Number(creatorAtaAfter.amount),
+        Number(creatorAtaBefore.amount)
+      );
+
+      const campaignState = await program.account.campaignState.fetch(
+        refundCampaignPda
+      );
+      assert.deepEqual(campaignState.status, { cancelled: {} });
     });
+  });
+
+  // ---------------------------------------------------------------------------
+  // Refund: Frozen Destination
+  // ---------------------------------------------------------------------------
+  describe("Refund: Frozen Destination", () => {
+    it("fails to refund into a frozen creator ATA", async () => {
+      // Mint with a freeze authority so the creator's ATA can be frozen
+      const freezableMint = await createMint(
+        provider.connection,
+        creator,
+        creator.publicKey,
+        creator.publicKey,
+        DECIMALS
+      );
+      const frozenAta = await createAssociatedTokenAccount(
+        provider.connection,
+        creator,
+        freezableMint,
+        creator.publicKey
+      );
+      await mintTo(
+        provider.connection,
+        creator,
+        freezableMint,
+        frozenAta,
+        creator,
+        AMOUNT_PER_RECIPIENT.toNumber()
+      );
+
+      const frozenCampaignId = Array.from(crypto.randomBytes(16));
+      const [frozenCampaignPda] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("campaign"),
+          creator.publicKey.toBuffer(),
+          Buffer.from(frozenCampaignId),
+        ],
+        program.programId
+      );
+      const frozenEscrowAta = getAssociatedTokenAddressSync(
+        freezableMint,
+        frozenCampaignPda,
+        true
+      );
+
+      await program.methods
+        .createCampaign(
+          frozenCampaignId,
+          AMOUNT_PER_RECIPIENT,
+          1,
+          crankAuthority.publicKey,
+          null
+        )
+        .accounts({
+          creator: creator.publicKey,
+          campaign: frozenCampaignPda,
+          tokenMint: freezableMint,
+          escrowAta: frozenEscrowAta,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        })
+        .signers([creator])
+        .rpc();
+
+      await program.methods
+        .fundCampaign(AMOUNT_PER_RECIPIENT)
+        .accounts({
+          creator: creator.publicKey,
+          campaign: frozenCampaignPda,
+          creatorAta: frozenAta,
+          escrowAta: frozenEscrowAta,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([creator])
+        .rpc();
+
+      await freezeAccount(
+        provider.connection,
+        creator,
+        frozenAta,
+        freezableMint,
+        creator
+      );
 
-    it("fails to refund cancelled campaign — no double-refund (AlreadyCompleted)", async () => {
-      // After the previous refund, campaignPda is now Cancelled.
-      // A second refund must be rejected.
       try {
         await program.methods
           .refund()
           .accounts({
             creator: creator.publicKey,
-            campaign: campaignPda,
-            creatorAta: creatorAta,
-            // escrowAta was closed above, but Anchor will fail to
-            // deserialize it before the constraint even fires.
-            // We still pass it to exercise the constraint path.
-            escrowAta: escrowAta,
+            campaign: frozenCampaignPda,
+            creatorAta: frozenAta,
+            refundDestinationAta: null,
+            escrowAta: frozenEscrowAta,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([creator])
+          .rpc();
+        assert.fail("Should have failed - destination ATA is frozen");
+      } catch (e: any) {
+        expect(e.message).to.include("FrozenDestination");
+      }
+
+      // The campaign stays refundable once the ATA is thawed
+      const campaignState = await program.account.campaignState.fetch(
+        frozenCampaignPda
+      );
+      assert.deepEqual(campaignState.status, { funded: {} });
+    });
+  });
+
+  // ---------------------------------------------------------------------------
+  // Campaign Progress
+  // ---------------------------------------------------------------------------
+  describe("Campaign Progress", () => {
+    it("reports progress without fetching the campaign account", async () => {
+      const progress = await program.methods
+        .getCampaignProgress()
+        .accounts({ campaign: campaignPda })
+        .view();
+
+      const campaignState = await program.account.campaignState.fetch(campaignPda);
+      assert.equal(progress.distributedCount, TOTAL_RECIPIENTS);
+      assert.equal(progress.totalRecipients, campaignState.totalRecipients);
+      assert.equal(progress.distributionCursor, TOTAL_RECIPIENTS);
+      assert.equal(
+        progress.distributedAmount.toNumber(),
+        campaignState.distributedAmount.toNumber()
+      );
+      assert.equal(
+        progress.totalAmount.toNumber(),
+        campaignState.totalAmount.toNumber()
+      );
+      assert.deepEqual(progress.status, campaignState.status);
+    });
+  });
+
+  // ---------------------------------------------------------------------------
+  // Refund Deadline
+  // ---------------------------------------------------------------------------
+  describe("Refund Deadline", () => {
+    // Creates a two-recipient campaign stuck in Processing after one payout
+    async function stuckCampaign(deadline: number) {
+      const id = Array.from(crypto.randomBytes(16));
+      const [campaign] = PublicKey.findProgramAddressSync(
+        [Buffer.from("campaign"), creator.publicKey.toBuffer(), Buffer.from(id)],
+        program.programId
+      );
+      const escrow = getAssociatedTokenAddressSync(tokenMint, campaign, true);
+
+      await program.methods
+        .createCampaign(
+          id,
+          AMOUNT_PER_RECIPIENT,
+          2,
+          crankAuthority.publicKey,
+          new anchor.BN(deadline)
+        )
+        .accounts({
+          creator: creator.publicKey,
+          campaign,
+          tokenMint: tokenMint,
+          escrowAta: escrow,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        })
+        .signers([creator])
+        .rpc();
+
+      await program.methods
+        .fundCampaign(new anchor.BN(AMOUNT_PER_RECIPIENT.toNumber() * 2))
+        .accounts({
+          creator: creator.publicKey,
+          campaign,
+          creatorAta: creatorAta,
+          escrowAta: escrow,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([creator])
+        .rpc();
+
+      await program.methods
+        .distributeBatch(0, 1)
+        .accounts({
+          crankAuthority: crankAuthority.publicKey,
+          campaign,
+          escrowAta: escrow,
+          crankAta: null,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .remainingAccounts(recipientAccounts(campaign, [[recipient1.publicKey, recipient1Ata]]))
+        .signers([crankAuthority])
+        .rpc();
+
+      const state = await program.account.campaignState.fetch(campaign);
+      assert.deepEqual(state.status, { processing: {} });
+      return { campaign, escrow };
+    }
+
+    function refund(campaign: PublicKey, escrow: PublicKey) {
+      return program.methods
+        .refund()
+        .accounts({
+          creator: creator.publicKey,
+          campaign,
+          creatorAta: creatorAta,
+          refundDestinationAta: null,
+          escrowAta: escrow,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([creator])
+        .rpc();
+    }
+
+    it("rejects a deadline in the past", async () => {
+      const id = Array.from(crypto.randomBytes(16));
+      const [campaign] = PublicKey.findProgramAddressSync(
+        [Buffer.from("campaign"), creator.publicKey.toBuffer(), Buffer.from(id)],
+        program.programId
+      );
+      try {
+        await program.methods
+          .createCampaign(
+            id,
+            AMOUNT_PER_RECIPIENT,
+            1,
+            crankAuthority.publicKey,
+            new anchor.BN(1)
+          )
+          .accounts({
+            creator: creator.publicKey,
+            campaign,
+            tokenMint: tokenMint,
+            escrowAta: getAssociatedTokenAddressSync(tokenMint, campaign, true),
+            systemProgram: SystemProgram.programId,
             tokenProgram: TOKEN_PROGRAM_ID,
+            associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
           })
           .signers([creator])
           .rpc();
-        assert.fail("Should have failed - campaign is already cancelled");
+        assert.fail("Should have failed - deadline already passed");
       } catch (e: any) {
-        // Could fail with AlreadyCompleted (constraint) or account deserialization
-        // error (escrow ATA closed). Either way the tx must not succeed.
-        expect(e).to.not.be.null;
+        expect(e.message).to.include("InvalidDeadline");
       }
     });
+
+    it("refunds mid-distribution without waiting for the deadline", async () => {
+      const { campaign, escrow } = await stuckCampaign(
+        Math.floor(Date.now() / 1000) + 3600
+      );
+      await refund(campaign, escrow);
+
+      const state = await program.account.campaignState.fetch(campaign);
+      assert.deepEqual(state.status, { cancelled: {} });
+    });
+
+    it("refunds a stuck Processing campaign once the deadline passes", async () => {
+      const { campaign, escrow } = await stuckCampaign(
+        Math.floor(Date.now() / 1000) + 3
+      );
+      await new Promise((resolve) => setTimeout(resolve, 5000));
+
+      const creatorAtaBefore = await getAccount(provider.connection, creatorAta);
+      await refund(campaign, escrow);
+
+      const creatorAtaAfter = await getAccount(provider.connection, creatorAta);
+      assert.equal(
+        Number(creatorAtaAfter.amount) - Number(creatorAtaBefore.amount),
+        AMOUNT_PER_RECIPIENT.toNumber()
+      );
+      const state = await program.account.campaignState.fetch(campaign);
+      assert.deepEqual(state.status, { cancelled: {} });
+    });
+  });
+
+  // ---------------------------------------------------------------------------
+  // Security: Escrow Authority
+  // ---------------------------------------------------------------------------
+  describe("Security: Escrow Authority", () => {
+    it("rejects a substituted escrow account the campaign doesn't own", async () => {
+      const id = Array.from(crypto.randomBytes(16));
+      const [campaign] = PublicKey.findProgramAddressSync(
+        [Buffer.from("campaign"), creator.publicKey.toBuffer(), Buffer.from(id)],
+        program.programId
+      );
+      const escrow = getAssociatedTokenAddressSync(tokenMint, campaign, true);
+
+      await program.methods
+        .createCampaign(id, AMOUNT_PER_RECIPIENT, 1, crankAuthority.publicKey, null)
+        .accounts({
+          creator: creator.publicKey,
+          campaign,
+          tokenMint: tokenMint,
+          escrowAta: escrow,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        })
+        .signers([creator])
+        .rpc();
+
+      await program.methods
+        .fundCampaign(AMOUNT_PER_RECIPIENT)
+        .accounts({
+          creator: creator.publicKey,
+          campaign,
+          creatorAta: creatorAta,
+          escrowAta: escrow,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([creator])
+        .rpc();
+
+      // The creator's own ATA holds the same mint but isn't controlled by the campaign
+      try {
+        await program.methods
+          .distributeBatch(0, 1)
+          .accounts({
+            crankAuthority: crankAuthority.publicKey,
+            campaign,
+            escrowAta: creatorAta,
+            crankAta: null,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+          })
+          .remainingAccounts(recipientAccounts(campaign, [[recipient1.publicKey, recipient1Ata]]))
+          .signers([crankAuthority])
+          .rpc();
+        assert.fail("Should have failed - escrow is not the campaign's");
+      } catch (e: any) {
+        expect(e.message).to.match(/ConstraintRaw|InvalidEscrowAuthority/);
+      }
+
+      const state = await program.account.campaignState.fetch(campaign);
+      assert.equal(state.distributedCount, 0);
+    });
+  });
+
+  // ---------------------------------------------------------------------------
+  // Resumable Distribution
+  // ---------------------------------------------------------------------------
+  describe("Resumable Distribution", () => {
+    const id = Array.from(crypto.randomBytes(16));
+    let campaign: PublicKey;
+    let escrow: PublicKey;
+
+    before(async () => {
+      [campaign] = PublicKey.findProgramAddressSync(
+        [Buffer.from("campaign"), creator.publicKey.toBuffer(), Buffer.from(id)],
+        program.programId
+      );
+      escrow = getAssociatedTokenAddressSync(tokenMint, campaign, true);
+
+      // Earlier suites use up the creator's initial supply
+      await mintTo(
+        provider.connection,
+        creator,
+        tokenMint,
+        creatorAta,
+        creator,
+        AMOUNT_PER_RECIPIENT.toNumber() * 3
+      );
+
+      await program.methods
+        .createCampaign(id, AMOUNT_PER_RECIPIENT, 3, crankAuthority.publicKey, null)
+        .accounts({
+          creator: creator.publicKey,
+          campaign,
+          tokenMint: tokenMint,
+          escrowAta: escrow,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        })
+        .signers([creator])
+        .rpc();
+
+      await program.methods
+        .fundCampaign(new anchor.BN(AMOUNT_PER_RECIPIENT.toNumber() * 3))
+        .accounts({
+          creator: creator.publicKey,
+          campaign,
+          creatorAta: creatorAta,
+          escrowAta: escrow,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([creator])
+        .rpc();
+    });
+
+    function distribute(startIndex: number, recipients: [PublicKey, PublicKey][]) {
+      return program.methods
+        .distributeBatch(startIndex, recipients.length)
+        .accounts({
+          crankAuthority: crankAuthority.publicKey,
+          campaign,
+          escrowAta: escrow,
+          crankAta: null,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .remainingAccounts(recipientAccounts(campaign, recipients))
+        .signers([crankAuthority])
+        .rpc();
+    }
+
+    it("resumes from the persisted cursor after an interruption", async () => {
+      const recipient1Before = await getAccount(provider.connection, recipient1Ata);
+
+      await distribute(0, [
+        [recipient1.publicKey, recipient1Ata],
+        [recipient2.publicKey, recipient2Ata],
+      ]);
+
+      // The crank restarts and replays the batch it already sent
+      try {
+        await distribute(0, [
+          [recipient1.publicKey, recipient1Ata],
+          [recipient2.publicKey, recipient2Ata],
+        ]);
+        assert.fail("Should have failed - batch already distributed");
+      } catch (e: any) {
+        expect(e.message).to.include("DistributionCursorMismatch");
+      }
+
+      const progress = await program.methods
+        .getCampaignProgress()
+        .accounts({ campaign })
+        .view();
+      assert.equal(progress.distributionCursor, 2);
+
+      await distribute(progress.distributionCursor, [
+        [recipient3.publicKey, recipient3Ata],
+      ]);
+
+      const state = await program.account.campaignState.fetch(campaign);
+      assert.equal(state.distributionCursor, 3);
+      assert.deepEqual(state.status, { completed: {} });
+
+      // The replayed batch paid nobody twice
+      const recipient1After = await getAccount(provider.connection, recipient1Ata);
+      assert.equal(
+        Number(recipient1After.amount) - Number(recipient1Before.amount),
+        AMOUNT_PER_RECIPIENT.toNumber()
+      );
+    });
+  });
+
+  // ---------------------------------------------------------------------------
+  // Merkle Claims
+  // ---------------------------------------------------------------------------
+  describe("Merkle Claims", () => {
+    const id = Array.from(crypto.randomBytes(16));
+    const allocations = [
+      { index: 0, claimant: recipient1, amount: 2_000_000 },
+      { index: 1, claimant: recipient2, amount: 1_000_000 },
+    ];
+    let campaign: PublicKey;
+    let escrow: PublicKey;
+    let leaves: Buffer[];
+
+    const sha256 = (...parts: Buffer[]) =>
+      crypto.createHash("sha256").update(Buffer.concat(parts)).digest();
+
+    function leaf(index: number, claimant: PublicKey, amount: number) {
+      const indexBytes = Buffer.alloc(4);
+      indexBytes.writeUInt32LE(index);
+      const amountBytes = Buffer.alloc(8);
+      amountBytes.writeBigUInt64LE(BigInt(amount));
+      return sha256(
+        Buffer.from([0]),
+        indexBytes,
+        claimant.toBuffer(),
+        amountBytes
+      );
+    }
+
+    function node(a: Buffer, b: Buffer) {
+      const [left, right] = Buffer.compare(a, b) <= 0 ? [a, b] : [b, a];
+      return sha256(Buffer.from([1]), left, right);
+    }
+
+    function claimStatusPda(index: number) {
+      const indexBytes = Buffer.alloc(4);
+      indexBytes.writeUInt32LE(index);
+      const [pda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("claim"), campaign.toBuffer(), indexBytes],
+        program.programId
+      );
+      return pda;
+    }
+
+    function claim(
+      claimant: Keypair,
+      claimantAta: PublicKey,
+      index: number,
+      amount: number,
+      proof: Buffer[]
+    ) {
+      return program.methods
+        .claim(
+          index,
+          new anchor.BN(amount),
+          proof.map((p) => Array.from(p))
+        )
+        .accounts({
+          claimant: claimant.publicKey,
+          campaign,
+          claimStatus: claimStatusPda(index),
+          escrowAta: escrow,
+          claimantAta,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([claimant])
+        .rpc();
+    }
+
+    before(async () => {
+      [campaign] = PublicKey.findProgramAddressSync(
+        [Buffer.from("campaign"), creator.publicKey.toBuffer(), Buffer.from(id)],
+        program.programId
+      );
+      escrow = getAssociatedTokenAddressSync(tokenMint, campaign, true);
+
+      await mintTo(
+        provider.connection,
+        creator,
+        tokenMint,
+        creatorAta,
+        creator,
+        3_000_000
+      );
+
+      await program.methods
+        .createCampaign(id, AMOUNT_PER_RECIPIENT, 2, crankAuthority.publicKey, null)
+        .accounts({
+          creator: creator.publicKey,
+          campaign,
+          tokenMint: tokenMint,
+          escrowAta: escrow,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        })
+        .signers([creator])
+        .rpc();
+
+      await program.methods
+        .fundCampaign(new anchor.BN(3_000_000))
+        .accounts({
+          creator: creator.publicKey,
+          campaign,
+          creatorAta: creatorAta,
+          escrowAta: escrow,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([creator])
+        .rpc();
+
+      leaves = allocations.map((a) =>
+        leaf(a.index, a.claimant.publicKey, a.amount)
+      );
+      const root = node(leaves[0], leaves[1]);
+
+      await program.methods
+        .setMerkleRoot(Array.from(root), 2)
+        .accounts({ creator: creator.publicKey, campaign })
+        .signers([creator])
+        .rpc();
+    });
+
+    function remainingEligible() {
+      return program.methods.remainingEligible().accounts({ campaign }).view();
+    }
+
+    it("rejects more eligible leaves than declared recipients", async () => {
+      try {
+        await program.methods
+          .setMerkleRoot(Array.from(node(leaves[0], leaves[1])), 3)
+          .accounts({ creator: creator.publicKey, campaign })
+          .signers([creator])
+          .rpc();
+        assert.fail("Should have failed - more leaves than recipients");
+      } catch (e: any) {
+        expect(e.message).to.include("InvalidRecipientCount");
+      }
+    });
+
+    it("starts with every allowlist spot open", async () => {
+      assert.equal(await remainingEligible(), 2);
+    });
+
+    it("keeps the crank from distributing a Merkle campaign", async () => {
+      try {
+        await program.methods
+          .distributeBatch(0, 1)
+          .accounts({
+            crankAuthority: crankAuthority.publicKey,
+            campaign,
+            escrowAta: escrow,
+            crankAta: null,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+          })
+          .remainingAccounts(recipientAccounts(campaign, [[recipient1.publicKey, recipient1Ata]]))
+          .signers([crankAuthority])
+          .rpc();
+        assert.fail("Should have failed - campaign pays out through claims");
+      } catch (e: any) {
+        expect(e.message).to.include("MerkleCampaign");
+      }
+    });
+
+    it("pays a recipient exactly their allocation", async () => {
+      const before = await getAccount(provider.connection, recipient1Ata);
+
+      await claim(recipient1, recipient1Ata, 0, 2_000_000, [leaves[1]]);
+
+      const after = await getAccount(provider.connection, recipient1Ata);
+      assert.equal(Number(after.amount) - Number(before.amount), 2_000_000);
+
+      const status = await program.account.claimStatus.fetch(claimStatusPda(0));
+      assert.equal(status.claimed, true);
+      assert.equal(status.amount.toNumber(), 2_000_000);
+
+      const state = await program.account.campaignState.fetch(campaign);
+      assert.equal(state.eligibleCount, 2);
+      assert.equal(state.claimedCount, 1);
+      assert.equal(await remainingEligible(), 1);
+    });
+
+    it("rejects replaying a claim (AlreadyClaimed)", async () => {
+      try {
+        await claim(recipient1, recipient1Ata, 0, 2_000_000, [leaves[1]]);
+        assert.fail("Should have failed - allocation already claimed");
+      } catch (e: any) {
+        expect(e.message).to.include("AlreadyClaimed");
+      }
+    });
+
+    it("rejects a claim for more than the leaf encodes (InvalidProof)", async () => {
+      try {
+        await claim(recipient2, recipient2Ata, 1, 2_000_000, [leaves[0]]);
+        assert.fail("Should have failed - amount doesn't match the leaf");
+      } catch (e: any) {
+        expect(e.message).to.include("InvalidProof");
+      }
+    });
+
+    it("doesn't count rejected claims against the allowlist", async () => {
+      assert.equal(await remainingEligible(), 1);
+    });
+
+    it("completes the campaign once every leaf is claimed", async () => {
+      await claim(recipient2, recipient2Ata, 1, 1_000_000, [leaves[0]]);
+
+      const state = await program.account.campaignState.fetch(campaign);
+      assert.equal(state.distributedAmount.toNumber(), 3_000_000);
+      assert.equal(state.claimedCount, 2);
+      assert.deepEqual(state.status, { completed: {} });
+      assert.equal(await remainingEligible(), 0);
+    });
+  });
+
+  // ---------------------------------------------------------------------------
+  // Refund Destination Override
+  // ---------------------------------------------------------------------------
+  describe("Refund Destination Override", () => {
+    const treasury = Keypair.generate();
+    const id = Array.from(crypto.randomBytes(16));
+    let campaign: PublicKey;
+    let escrow: PublicKey;
+    let treasuryAta: PublicKey;
+
+    before(async () => {
+      [campaign] = PublicKey.findProgramAddressSync(
+        [Buffer.from("campaign"), creator.publicKey.toBuffer(), Buffer.from(id)],
+        program.programId
+      );
+      escrow = getAssociatedTokenAddressSync(tokenMint, campaign, true);
+      treasuryAta = await createAssociatedTokenAccount(
+        provider.connection,
+        creator,
+        tokenMint,
+        treasury.publicKey
+      );
+
+      await mintTo(
+        provider.connection,
+        creator,
+        tokenMint,
+        creatorAta,
+        creator,
+        AMOUNT_PER_RECIPIENT.toNumber() * 2
+      );
+
+      await program.methods
+        .createCampaign(id, AMOUNT_PER_RECIPIENT, 2, crankAuthority.publicKey, null)
+        .accounts({
+          creator: creator.publicKey,
+          campaign,
+          tokenMint: tokenMint,
+          escrowAta: escrow,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        })
+        .signers([creator])
+        .rpc();
+
+      await program.methods
+        .fundCampaign(new anchor.BN(AMOUNT_PER_RECIPIENT.toNumber() * 2))
+        .accounts({
+          creator: creator.publicKey,
+          campaign,
+          creatorAta: creatorAta,
+          escrowAta: escrow,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([creator])
+        .rpc();
+    });
+
+    it("rejects a destination with the wrong mint", async () => {
+      try {
+        await program.methods
+          .refund()
+          .accounts({
+            creator: creator.publicKey,
+            campaign,
+            creatorAta: creatorAta,
+            refundDestinationAta: wrongMintRecipientAta,
+            escrowAta: escrow,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([creator])
+          .rpc();
+        assert.fail("Should have failed - destination mint mismatch");
+      } catch (e: any) {
+        expect(e.message).to.include("InvalidRecipientMint");
+      }
+    });
+
+    it("refunds to a non-creator-owned ATA of the campaign mint", async () => {
+      const creatorBefore = await getAccount(provider.connection, creatorAta);
+
+      await program.methods
+        .refund()
+        .accounts({
+          creator: creator.publicKey,
+          campaign,
+          creatorAta: creatorAta,
+          refundDestinationAta: treasuryAta,
+          escrowAta: escrow,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([creator])
+        .rpc();
+
+      const treasuryAfter = await getAccount(provider.connection, treasuryAta);
+      assert.equal(
+        Number(treasuryAfter.amount),
+        AMOUNT_PER_RECIPIENT.toNumber() * 2
+      );
+      const creatorAfter = await getAccount(provider.connection, creatorAta);
+      assert.equal(creatorAfter.amount, creatorBefore.amount);
+
+      const state = await program.account.campaignState.fetch(campaign);
+      assert.deepEqual(state.status, { cancelled: {} });
+    });
+  });
+
+  // ---------------------------------------------------------------------------
+  // Completion Guard
+  // ---------------------------------------------------------------------------
+  describe("Completion Guard", () => {
+    function campaignAccounts(id: number[]) {
+      const [campaign] = PublicKey.findProgramAddressSync(
+        [Buffer.from("campaign"), creator.publicKey.toBuffer(), Buffer.from(id)],
+        program.programId
+      );
+      const escrow = getAssociatedTokenAddressSync(tokenMint, campaign, true);
+      return { campaign, escrow };
+    }
+
+    it("rejects a campaign with no recipients (InvalidRecipientCount)", async () => {
+      const id = Array.from(crypto.randomBytes(16));
+      const { campaign, escrow } = campaignAccounts(id);
+
+      try {
+        await program.methods
+          .createCampaign(id, AMOUNT_PER_RECIPIENT, 0, crankAuthority.publicKey, null)
+          .accounts({
+            creator: creator.publicKey,
+            campaign,
+            tokenMint: tokenMint,
+            escrowAta: escrow,
+            systemProgram: SystemProgram.programId,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          })
+          .signers([creator])
+          .rpc();
+        assert.fail("Should have failed - zero recipients");
+      } catch (e: any) {
+        expect(e.message).to.include("InvalidRecipientCount");
+      }
+    });
+
+    it("keeps an under-distributed campaign Processing", async () => {
+      const id = Array.from(crypto.randomBytes(16));
+      const { campaign, escrow } = campaignAccounts(id);
+
+      // Funded for three payouts but declared for two
+      await mintTo(
+        provider.connection,
+        creator,
+        tokenMint,
+        creatorAta,
+        creator,
+        AMOUNT_PER_RECIPIENT.toNumber() * 3
+      );
+      await program.methods
+        .createCampaign(id, AMOUNT_PER_RECIPIENT, 2, crankAuthority.publicKey, null)
+        .accounts({
+          creator: creator.publicKey,
+          campaign,
+          tokenMint: tokenMint,
+          escrowAta: escrow,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        })
+        .signers([creator])
+        .rpc();
+      await program.methods
+        .fundCampaign(new anchor.BN(AMOUNT_PER_RECIPIENT.toNumber() * 3))
+        .accounts({
+          creator: creator.publicKey,
+          campaign,
+          creatorAta: creatorAta,
+          escrowAta: escrow,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([creator])
+        .rpc();
+
+      const distribute = (startIndex: number, recipients: [PublicKey, PublicKey][]) =>
+        program.methods
+          .distributeBatch(startIndex, recipients.length)
+          .accounts({
+            crankAuthority: crankAuthority.publicKey,
+            campaign,
+            escrowAta: escrow,
+            crankAta: null,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+          })
+          .remainingAccounts(recipientAccounts(campaign, recipients))
+          .signers([crankAuthority])
+          .rpc();
+
+      await distribute(0, [[recipient1.publicKey, recipient1Ata]]);
+      let state = await program.account.campaignState.fetch(campaign);
+      assert.deepEqual(state.status, { processing: {} });
+
+      // Paying past the declared count is rejected rather than completing
+      try {
+        await distribute(1, [
+          [recipient2.publicKey, recipient2Ata],
+          [recipient3.publicKey, recipient3Ata],
+        ]);
+        assert.fail("Should have failed - more payouts than declared");
+      } catch (e: any) {
+        expect(e.message).to.include("RecipientCountExceeded");
+      }
+      state = await program.account.campaignState.fetch(campaign);
+      assert.equal(state.distributedCount, 1);
+      assert.deepEqual(state.status, { processing: {} });
+    });
+  });
+
+  // ---------------------------------------------------------------------------
+  // Crank Fees
+  // ---------------------------------------------------------------------------
+  describe("Crank Fees", () => {
+    const CRANK_FEE = 100_000;
+
+    async function feeCampaign(fundAmount: number) {
+      const id = Array.from(crypto.randomBytes(16));
+      const [campaign] = PublicKey.findProgramAddressSync(
+        [Buffer.from("campaign"), creator.publicKey.toBuffer(), Buffer.from(id)],
+        program.programId
+      );
+      const escrow = getAssociatedTokenAddressSync(tokenMint, campaign, true);
+
+      await mintTo(
+        provider.connection,
+        creator,
+        tokenMint,
+        creatorAta,
+        creator,
+        fundAmount
+      );
+      await program.methods
+        .createCampaign(id, AMOUNT_PER_RECIPIENT, 2, crankAuthority.publicKey, null)
+        .accounts({
+          creator: creator.publicKey,
+          campaign,
+          tokenMint: tokenMint,
+          escrowAta: escrow,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        })
+        .signers([creator])
+        .rpc();
+      await program.methods
+        .setCrankFee(new anchor.BN(CRANK_FEE))
+        .accounts({ creator: creator.publicKey, campaign })
+        .signers([creator])
+        .rpc();
+      await program.methods
+        .fundCampaign(new anchor.BN(fundAmount))
+        .accounts({
+          creator: creator.publicKey,
+          campaign,
+          creatorAta: creatorAta,
+          escrowAta: escrow,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([creator])
+        .rpc();
+
+      return { campaign, escrow };
+    }
+
+    function distributeWithFee(campaign: PublicKey, escrow: PublicKey) {
+      return program.methods
+        .distributeBatch(0, 2)
+        .accounts({
+          crankAuthority: crankAuthority.publicKey,
+          campaign,
+          escrowAta: escrow,
+          crankAta: crankAuthorityAta,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .remainingAccounts(
+          recipientAccounts(campaign, [
+            [recipient1.publicKey, recipient1Ata],
+            [recipient2.publicKey, recipient2Ata],
+          ])
+        )
+        .signers([crankAuthority])
+        .rpc();
+    }
+
+    let crankAuthorityAta: PublicKey;
+
+    before(async () => {
+      crankAuthorityAta = await createAssociatedTokenAccount(
+        provider.connection,
+        creator,
+        tokenMint,
+        crankAuthority.publicKey
+      );
+    });
+
+    it("rejects a batch whose fees would exceed the escrow", async () => {
+      // Covers the payouts but not the crank fee on top
+      const { campaign, escrow } = await feeCampaign(
+        AMOUNT_PER_RECIPIENT.toNumber() * 2
+      );
+
+      try {
+        await distributeWithFee(campaign, escrow);
+        assert.fail("Should have failed - fees exceed escrow");
+      } catch (e: any) {
+        expect(e.message).to.include("InsufficientFunds");
+      }
+    });
+
+    it("pays the crank its fee for each distributed recipient", async () => {
+      const { campaign, escrow } = await feeCampaign(
+        (AMOUNT_PER_RECIPIENT.toNumber() + CRANK_FEE) * 2
+      );
+      const crankBefore = await getAccount(provider.connection, crankAuthorityAta);
+
+      await distributeWithFee(campaign, escrow);
+
+      const crankAfter = await getAccount(provider.connection, crankAuthorityAta);
+      assert.equal(
+        Number(crankAfter.amount) - Number(crankBefore.amount),
+        CRANK_FEE * 2
+      );
+
+      const state = await program.account.campaignState.fetch(campaign);
+      assert.equal(state.crankFeesPaid.toNumber(), CRANK_FEE * 2);
+      assert.deepEqual(state.status, { completed: {} });
+
+      const escrowAccount = await getAccount(provider.connection, escrow);
+      assert.equal(Number(escrowAccount.amount), 0);
+    });
+  });
+
+  // ---------------------------------------------------------------------------
+  // Rescue Refund
+  // ---------------------------------------------------------------------------
+  describe("Rescue Refund", () => {
+    const admin = Keypair.generate();
+
+    async function rescueCampaign(deadline: anchor.BN | null) {
+      const id = Array.from(crypto.randomBytes(16));
+      const [campaign] = PublicKey.findProgramAddressSync(
+        [Buffer.from("campaign"), creator.publicKey.toBuffer(), Buffer.from(id)],
+        program.programId
+      );
+      const escrow = getAssociatedTokenAddressSync(tokenMint, campaign, true);
+
+      await program.methods
+        .createCampaign(id, AMOUNT_PER_RECIPIENT, 2, crankAuthority.publicKey, deadline)
+        .accounts({
+          creator: creator.publicKey,
+          campaign,
+          tokenMint: tokenMint,
+          escrowAta: escrow,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        })
+        .signers([creator])
+        .rpc();
+
+      return { campaign, escrow };
+    }
+
+    function rescue(signer: Keypair, campaign: PublicKey, escrow: PublicKey) {
+      return program.methods
+        .rescueRefund()
+        .accounts({
+          adminAuthority: signer.publicKey,
+          campaign,
+          rescueAta: recipient1Ata,
+          escrowAta: escrow,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([signer])
+        .rpc();
+    }
+
+    it("rejects a rescue path on a campaign without a deadline", async () => {
+      const { campaign } = await rescueCampaign(null);
+
+      try {
+        await program.methods
+          .setRescue(admin.publicKey, recipient1Ata)
+          .accounts({ creator: creator.publicKey, campaign })
+          .signers([creator])
+          .rpc();
+        assert.fail("Should have failed - no deadline");
+      } catch (e: any) {
+        expect(e.message).to.include("RescueRequiresDeadline");
+      }
+    });
+
+    describe("with a registered admin", () => {
+      let campaign: PublicKey;
+      let escrow: PublicKey;
+
+      before(async () => {
+        const deadline = new anchor.BN(Math.floor(Date.now() / 1000) + 3600);
+        ({ campaign, escrow } = await rescueCampaign(deadline));
+
+        await program.methods
+          .setRescue(admin.publicKey, recipient1Ata)
+          .accounts({ creator: creator.publicKey, campaign })
+          .signers([creator])
+          .rpc();
+
+        const fundAmount = AMOUNT_PER_RECIPIENT.toNumber() * 2;
+        await mintTo(
+          provider.connection,
+          creator,
+          tokenMint,
+          creatorAta,
+          creator,
+          fundAmount
+        );
+        await program.methods
+          .fundCampaign(new anchor.BN(fundAmount))
+          .accounts({
+            creator: creator.publicKey,
+            campaign,
+            creatorAta: creatorAta,
+            escrowAta: escrow,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([creator])
+          .rpc();
+      });
+
+      it("stores the admin and rescue destination", async () => {
+        const state = await program.account.campaignState.fetch(campaign);
+        assert.ok(state.adminAuthority.equals(admin.publicKey));
+        assert.ok(state.rescueDestination.equals(recipient1Ata));
+      });
+
+      it("rejects a rescue signed by anyone but the admin", async () => {
+        try {
+          await rescue(nonCrankAuthority, campaign, escrow);
+          assert.fail("Should have failed - not the admin");
+        } catch (e: any) {
+          expect(e.message).to.include("UnauthorizedAdmin");
+        }
+      });
+
+      it("rejects a rescue before the timeout has passed", async () => {
+        try {
+          await rescue(admin, campaign, escrow);
+          assert.fail("Should have failed - rescue window not open");
+        } catch (e: any) {
+          expect(e.message).to.include("RescueNotAvailable");
+        }
+
+        const escrowAccount = await getAccount(provider.connection, escrow);
+        assert.equal(
+          Number(escrowAccount.amount),
+          AMOUNT_PER_RECIPIENT.toNumber() * 2
+        );
+      });
+
+      it("rejects changing the rescue path once funded", async () => {
+        try {
+          await program.methods
+            .setRescue(creator.publicKey, creatorAta)
+            .accounts({ creator: creator.publicKey, campaign })
+            .signers([creator])
+            .rpc();
+          assert.fail("Should have failed - campaign is funded");
+        } catch (e: any) {
+          expect(e.message).to.include("InvalidStatus");
+        }
+      });
+    });
+  });
+
+  // ---------------------------------------------------------------------------
+  // Duplicate Recipients
+  // ---------------------------------------------------------------------------
+  describe("Duplicate Recipients", () => {
+    const id = Array.from(crypto.randomBytes(16));
+    let campaign: PublicKey;
+    let escrow: PublicKey;
+
+    function distribute(startIndex: number, recipients: [PublicKey, PublicKey][]) {
+      return program.methods
+        .distributeBatch(startIndex, recipients.length)
+        .accounts({
+          crankAuthority: crankAuthority.publicKey,
+          campaign,
+          escrowAta: escrow,
+          crankAta: null,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .remainingAccounts(recipientAccounts(campaign, recipients))
+        .signers([crankAuthority])
+        .rpc();
+    }
+
+    before(async () => {
+      [campaign] = PublicKey.findProgramAddressSync(
+        [Buffer.from("campaign"), creator.publicKey.toBuffer(), Buffer.from(id)],
+        program.programId
+      );
+      escrow = getAssociatedTokenAddressSync(tokenMint, campaign, true);
+
+      const fundAmount = AMOUNT_PER_RECIPIENT.toNumber() * 2;
+      await mintTo(
+        provider.connection,
+        creator,
+        tokenMint,
+        creatorAta,
+        creator,
+        fundAmount
+      );
+      await program.methods
+        .createCampaign(id, AMOUNT_PER_RECIPIENT, 2, crankAuthority.publicKey, null)
+        .accounts({
+          creator: creator.publicKey,
+          campaign,
+          tokenMint: tokenMint,
+          escrowAta: escrow,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        })
+        .signers([creator])
+        .rpc();
+      await program.methods
+        .fundCampaign(new anchor.BN(fundAmount))
+        .accounts({
+          creator: creator.publicKey,
+          campaign,
+          creatorAta: creatorAta,
+          escrowAta: escrow,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([creator])
+        .rpc();
+    });
+
+    it("records a receipt for each paid recipient", async () => {
+      await distribute(0, [[recipient1.publicKey, recipient1Ata]]);
+
+      const receipt = await program.account.recipientReceipt.fetch(
+        recipientReceiptPda(campaign, recipient1.publicKey)
+      );
+      assert.ok(receipt.campaign.equals(campaign));
+      assert.ok(receipt.recipient.equals(recipient1.publicKey));
+      assert.equal(receipt.amount.toNumber(), AMOUNT_PER_RECIPIENT.toNumber());
+    });
+
+    it("rejects paying the same recipient again in a later batch", async () => {
+      const recipient1Before = await getAccount(provider.connection, recipient1Ata);
+
+      try {
+        await distribute(1, [[recipient1.publicKey, recipient1Ata]]);
+        assert.fail("Should have failed - recipient1 was paid in the first batch");
+      } catch (e: any) {
+        expect(e.message).to.include("DuplicateRecipient");
+      }
+
+      const recipient1After = await getAccount(provider.connection, recipient1Ata);
+      assert.equal(Number(recipient1After.amount), Number(recipient1Before.amount));
+
+      // The remaining slot still goes to a recipient who hasn't been paid
+      await distribute(1, [[recipient2.publicKey, recipient2Ata]]);
+      const state = await program.account.campaignState.fetch(campaign);
+      assert.deepEqual(state.status, { completed: {} });
+    });
+  });
+
+  // ---------------------------------------------------------------------------
+  // Campaign Status Events
+  // ---------------------------------------------------------------------------
+  describe("Campaign Status Events", () => {
+    const id = Array.from(crypto.randomBytes(16));
+    const eventParser = new anchor.EventParser(
+      program.programId,
+      new anchor.BorshCoder(program.idl)
+    );
+    let campaign: PublicKey;
+    let escrow: PublicKey;
+
+    async function statusChanges(signature: string) {
+      const tx = await provider.connection.getTransaction(signature, {
+        commitment: "confirmed",
+        maxSupportedTransactionVersion: 0,
+      });
+      return Array.from(eventParser.parseLogs(tx.meta.logMessages))
+        .filter((e) => e.name.toLowerCase() === "campaignstatuschanged")
+        .map((e) => [
+          Object.keys(e.data.oldStatus)[0],
+          Object.keys(e.data.newStatus)[0],
+        ]);
+    }
+
+    function distribute(startIndex: number, recipient: Keypair, ata: PublicKey) {
+      return program.methods
+        .distributeBatch(startIndex, 1)
+        .accounts({
+          crankAuthority: crankAuthority.publicKey,
+          campaign,
+          escrowAta: escrow,
+          crankAta: null,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .remainingAccounts(recipientAccounts(campaign, [[recipient.publicKey, ata]]))
+        .signers([crankAuthority])
+        .rpc({ commitment: "confirmed" });
+    }
+
+    before(async () => {
+      [campaign] = PublicKey.findProgramAddressSync(
+        [Buffer.from("campaign"), creator.publicKey.toBuffer(), Buffer.from(id)],
+        program.programId
+      );
+      escrow = getAssociatedTokenAddressSync(tokenMint, campaign, true);
+
+      const fundAmount = AMOUNT_PER_RECIPIENT.toNumber() * 2;
+      await mintTo(
+        provider.connection,
+        creator,
+        tokenMint,
+        creatorAta,
+        creator,
+        fundAmount
+      );
+      await program.methods
+        .createCampaign(id, AMOUNT_PER_RECIPIENT, 2, crankAuthority.publicKey, null)
+        .accounts({
+          creator: creator.publicKey,
+          campaign,
+          tokenMint: tokenMint,
+          escrowAta: escrow,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        })
+        .signers([creator])
+        .rpc();
+      await program.methods
+        .fundCampaign(new anchor.BN(fundAmount))
+        .accounts({
+          creator: creator.publicKey,
+          campaign,
+          creatorAta: creatorAta,
+          escrowAta: escrow,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([creator])
+        .rpc();
+    });
+
+    it("emits Funded -> Processing on the first batch", async () => {
+      const signature = await distribute(0, recipient1, recipient1Ata);
+      assert.deepEqual(await statusChanges(signature), [["funded", "processing"]]);
+    });
+
+    it("emits Processing -> Completed on the final batch", async () => {
+      const signature = await distribute(1, recipient2, recipient2Ata);
+      // Processing -> Processing is not a change, so only the completion shows up
+      assert.deepEqual(await statusChanges(signature), [["processing", "completed"]]);
+    });
+  });
+
+  // ---------------------------------------------------------------------------
+  // Distribution Rate Limit
+  // ---------------------------------------------------------------------------
+  describe("Distribution Rate Limit", () => {
+    let campaign: PublicKey;
+    let escrow: PublicKey;
+
+    function setRateLimit(maxPerSlot: anchor.BN | null) {
+      return program.methods
+        .setDistributionRateLimit(maxPerSlot)
+        .accounts({ creator: creator.publicKey, campaign })
+        .signers([creator])
+        .rpc();
+    }
+
+    function distribute(startIndex: number, recipients: [PublicKey, PublicKey][]) {
+      return program.methods
+        .distributeBatch(startIndex, recipients.length)
+        .accounts({
+          crankAuthority: crankAuthority.publicKey,
+          campaign,
+          escrowAta: escrow,
+          crankAta: null,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .remainingAccounts(recipientAccounts(campaign, recipients))
+        .signers([crankAuthority])
+        .rpc();
+    }
+
+    before(async () => {
+      const id = Array.from(crypto.randomBytes(16));
+      [campaign] = PublicKey.findProgramAddressSync(
+        [Buffer.from("campaign"), creator.publicKey.toBuffer(), Buffer.from(id)],
+        program.programId
+      );
+      escrow = getAssociatedTokenAddressSync(tokenMint, campaign, true);
+      const fundAmount = AMOUNT_PER_RECIPIENT.toNumber() * 2;
+
+      await mintTo(
+        provider.connection,
+        creator,
+        tokenMint,
+        creatorAta,
+        creator,
+        fundAmount
+      );
+      await program.methods
+        .createCampaign(id, AMOUNT_PER_RECIPIENT, 2, crankAuthority.publicKey, null)
+        .accounts({
+          creator: creator.publicKey,
+          campaign,
+          tokenMint: tokenMint,
+          escrowAta: escrow,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        })
+        .signers([creator])
+        .rpc();
+      await program.methods
+        .fundCampaign(new anchor.BN(fundAmount))
+        .accounts({
+          creator: creator.publicKey,
+          campaign,
+          creatorAta: creatorAta,
+          escrowAta: escrow,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([creator])
+        .rpc();
+    });
+
+    it("rejects a zero limit", async () => {
+      try {
+        await setRateLimit(new anchor.BN(0));
+        assert.fail("Should have failed - zero limit");
+      } catch (e: any) {
+        expect(e.message).to.include("InvalidRateLimit");
+      }
+    });
+
+    it("rejects a batch that exceeds the per-slot limit", async () => {
+      // One recipient's worth of tokens per slot
+      await setRateLimit(AMOUNT_PER_RECIPIENT);
+
+      try {
+        await distribute(0, [
+          [recipient1.publicKey, recipient1Ata],
+          [recipient2.publicKey, recipient2Ata],
+        ]);
+        assert.fail("Should have failed - over the per-slot limit");
+      } catch (e: any) {
+        expect(e.message).to.include("DistributionRateLimited");
+      }
+
+      const state = await program.account.campaignState.fetch(campaign);
+      assert.equal(state.distributedCount, 0);
+    });
+
+    it("allows batches within the limit", async () => {
+      await distribute(0, [[recipient1.publicKey, recipient1Ata]]);
+
+      const state = await program.account.campaignState.fetch(campaign);
+      assert.equal(state.distributedCount, 1);
+      assert.equal(
+        state.distributedThisSlot.toNumber(),
+        AMOUNT_PER_RECIPIENT.toNumber()
+      );
+      assert.isAbove(state.lastDistributionSlot.toNumber(), 0);
+    });
+
+    it("lets the creator lift the limit", async () => {
+      await setRateLimit(null);
+      await distribute(1, [[recipient2.publicKey, recipient2Ata]]);
+
+      const state = await program.account.campaignState.fetch(campaign);
+      assert.isNull(state.maxDistributedPerSlot);
+      assert.deepEqual(state.status, { completed: {} });
+    });
+  });
+
+  // ---------------------------------------------------------------------------
+  // Batch Refunds
+  // ---------------------------------------------------------------------------
+  describe("Batch Refunds", () => {
+    const eventParser = new anchor.EventParser(
+      program.programId,
+      new anchor.BorshCoder(program.idl)
+    );
+
+    async function newCampaign(recipients: number, fundAmount: number) {
+      const id = Array.from(crypto.randomBytes(16));
+      const [campaign] = PublicKey.findProgramAddressSync(
+        [Buffer.from("campaign"), creator.publicKey.toBuffer(), Buffer.from(id)],
+        program.programId
+      );
+      const escrow = getAssociatedTokenAddressSync(tokenMint, campaign, true);
+      await program.methods
+        .createCampaign(id, AMOUNT_PER_RECIPIENT, recipients, crankAuthority.publicKey, null)
+        .accounts({
+          creator: creator.publicKey,
+          campaign,
+          tokenMint: tokenMint,
+          escrowAta: escrow,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        })
+        .signers([creator])
+        .rpc();
+      if (fundAmount > 0) {
+        await mintTo(
+          provider.connection,
+          creator,
+          tokenMint,
+          creatorAta,
+          creator,
+          fundAmount
+        );
+        await program.methods
+          .fundCampaign(new anchor.BN(fundAmount))
+          .accounts({
+            creator: creator.publicKey,
+            campaign,
+            creatorAta: creatorAta,
+            escrowAta: escrow,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([creator])
+          .rpc();
+      }
+      return { campaign, escrow };
+    }
+
+    it("refunds eligible campaigns and skips completed ones", async () => {
+      const funded = await newCampaign(2, AMOUNT_PER_RECIPIENT.toNumber() * 2);
+      const draft = await newCampaign(2, 0);
+      const completed = await newCampaign(1, AMOUNT_PER_RECIPIENT.toNumber());
+      await program.methods
+        .distributeBatch(0, 1)
+        .accounts({
+          crankAuthority: crankAuthority.publicKey,
+          campaign: completed.campaign,
+          escrowAta: completed.escrow,
+          crankAta: null,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .remainingAccounts(
+          recipientAccounts(completed.campaign, [[recipient1.publicKey, recipient1Ata]])
+        )
+        .signers([crankAuthority])
+        .rpc();
+
+      const creatorBefore = (await getAccount(provider.connection, creatorAta)).amount;
+      const signature = await program.methods
+        .refundMany()
+        .accounts({
+          creator: creator.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .remainingAccounts(
+          [funded, completed, draft].flatMap(({ campaign, escrow }) => [
+            { pubkey: campaign, isWritable: true, isSigner: false },
+            { pubkey: escrow, isWritable: true, isSigner: false },
+            { pubkey: creatorAta, isWritable: true, isSigner: false },
+          ])
+        )
+        .signers([creator])
+        .rpc({ commitment: "confirmed" });
+
+      const tx = await provider.connection.getTransaction(signature, {
+        commitment: "confirmed",
+        maxSupportedTransactionVersion: 0,
+      });
+      const refunds = Array.from(eventParser.parseLogs(tx.meta.logMessages)).filter(
+        (e) => e.name.toLowerCase() === "campaignrefunded"
+      );
+      assert.equal(refunds.length, 2);
+      assert.deepEqual(
+        refunds.map((e) => e.data.refundAmount.toNumber()),
+        [AMOUNT_PER_RECIPIENT.toNumber() * 2, 0]
+      );
+
+      const creatorAfter = (await getAccount(provider.connection, creatorAta)).amount;
+      assert.equal(
+        Number(creatorAfter - creatorBefore),
+        AMOUNT_PER_RECIPIENT.toNumber() * 2
+      );
+
+      for (const { campaign, escrow } of [funded, draft]) {
+        const state = await program.account.campaignState.fetch(campaign);
+        assert.deepEqual(state.status, { cancelled: {} });
+        assert.isNull(await provider.connection.getAccountInfo(escrow));
+      }
+      const skipped = await program.account.campaignState.fetch(completed.campaign);
+      assert.deepEqual(skipped.status, { completed: {} });
+      assert.isNotNull(await provider.connection.getAccountInfo(completed.escrow));
+    });
+
+    it("rejects a campaign owned by another creator", async () => {
+      const { campaign, escrow } = await newCampaign(1, 0);
+      try {
+        await program.methods
+          .refundMany()
+          .accounts({
+            creator: nonCrankAuthority.publicKey,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .remainingAccounts([
+            { pubkey: campaign, isWritable: true, isSigner: false },
+            { pubkey: escrow, isWritable: true, isSigner: false },
+            { pubkey: creatorAta, isWritable: true, isSigner: false },
+          ])
+          .signers([nonCrankAuthority])
+          .rpc();
+        assert.fail("Should have failed - not the campaign creator");
+      } catch (e: any) {
+        expect(e.message).to.include("ConstraintHasOne");
+      }
+    });
+  });
+
+  // ---------------------------------------------------------------------------
+  // Distribution Progress Events
+  // ---------------------------------------------------------------------------
+  describe("Distribution Progress Events", () => {
+    const id = Array.from(crypto.randomBytes(16));
+    const eventParser = new anchor.EventParser(
+      program.programId,
+      new anchor.BorshCoder(program.idl)
+    );
+    let campaign: PublicKey;
+    let escrow: PublicKey;
+
+    async function batchProgress(signature: string) {
+      const tx = await provider.connection.getTransaction(signature, {
+        commitment: "confirmed",
+        maxSupportedTransactionVersion: 0,
+      });
+      const [event] = Array.from(eventParser.parseLogs(tx.meta.logMessages)).filter(
+        (e) => e.name.toLowerCase() === "batchdistributed"
+      );
+      return [event.data.distributedCount, event.data.totalRecipients];
+    }
+
+    function distribute(startIndex: number, recipients: [PublicKey, PublicKey][]) {
+      return program.methods
+        .distributeBatch(startIndex, recipients.length)
+        .accounts({
+          crankAuthority: crankAuthority.publicKey,
+          campaign,
+          escrowAta: escrow,
+          crankAta: null,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .remainingAccounts(recipientAccounts(campaign, recipients))
+        .signers([crankAuthority])
+        .rpc({ commitment: "confirmed" });
+    }
+
+    before(async () => {
+      [campaign] = PublicKey.findProgramAddressSync(
+        [Buffer.from("campaign"), creator.publicKey.toBuffer(), Buffer.from(id)],
+        program.programId
+      );
+      escrow = getAssociatedTokenAddressSync(tokenMint, campaign, true);
+
+      const fundAmount = AMOUNT_PER_RECIPIENT.toNumber() * 3;
+      await mintTo(
+        provider.connection,
+        creator,
+        tokenMint,
+        creatorAta,
+        creator,
+        fundAmount
+      );
+      await program.methods
+        .createCampaign(id, AMOUNT_PER_RECIPIENT, 3, crankAuthority.publicKey, null)
+        .accounts({
+          creator: creator.publicKey,
+          campaign,
+          tokenMint: tokenMint,
+          escrowAta: escrow,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        })
+        .signers([creator])
+        .rpc();
+      await program.methods
+        .fundCampaign(new anchor.BN(fundAmount))
+        .accounts({
+          creator: creator.publicKey,
+          campaign,
+          creatorAta: creatorAta,
+          escrowAta: escrow,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([creator])
+        .rpc();
+    });
+
+    it("reports distributed and total recipients after each batch", async () => {
+      const first = await distribute(0, [
+        [recipient1.publicKey, recipient1Ata],
+        [recipient2.publicKey, recipient2Ata],
+      ]);
+      assert.deepEqual(await batchProgress(first), [2, 3]);
+
+      const second = await distribute(2, [[recipient3.publicKey, recipient3Ata]]);
+      assert.deepEqual(await batchProgress(second), [3, 3]);
+    });
+  });
+
+  // ---------------------------------------------------------------------------
+  // Campaign Migration
+  // ---------------------------------------------------------------------------
+  describe("Campaign Migration", () => {
+    const id = Array.from(crypto.randomBytes(16));
+    let campaign: PublicKey;
+
+    function migrate(account: PublicKey) {
+      return program.methods
+        .migrateCampaign()
+        .accounts({
+          creator: creator.publicKey,
+          campaign: account,
+          tokenMint,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([creator])
+        .rpc();
+    }
+
+    before(async () => {
+      [campaign] = PublicKey.findProgramAddressSync(
+        [Buffer.from("campaign"), creator.publicKey.toBuffer(), Buffer.from(id)],
+        program.programId
+      );
+      await program.methods
+        .createCampaign(id, AMOUNT_PER_RECIPIENT, 1, crankAuthority.publicKey, null)
+        .accounts({
+          creator: creator.publicKey,
+          campaign,
+          tokenMint: tokenMint,
+          escrowAta: getAssociatedTokenAddressSync(tokenMint, campaign, true),
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        })
+        .signers([creator])
+        .rpc();
+    });
+
+    it("leaves a campaign in the current layout untouched", async () => {
+      const before = await provider.connection.getAccountInfo(campaign);
+      await migrate(campaign);
+      const after = await provider.connection.getAccountInfo(campaign);
+
+      assert.equal(after.data.length, before.data.length);
+      assert.equal(after.lamports, before.lamports);
+      assert.isTrue(after.data.equals(before.data));
+    });
+
+    it("rejects accounts the program doesn't own", async () => {
+      try {
+        await migrate(creatorAta);
+        assert.fail("Should have thrown");
+      } catch (e: any) {
+        expect(e.message).to.include("AccountOwnedByWrongProgram");
+      }
+    });
+  });
+
+  // ---------------------------------------------------------------------------
+  // Campaign Rent Check
+  // ---------------------------------------------------------------------------
+  describe("Campaign Rent Check", () => {
+    it("rejects a creator who can't cover the campaign and escrow rent", async () => {
+      const poorCreator = Keypair.generate();
+      const sig = await provider.connection.requestAirdrop(
+        poorCreator.publicKey,
+        0.003 * LAMPORTS_PER_SOL
+      );
+      await provider.connection.confirmTransaction(sig);
+
+      const id = Array.from(crypto.randomBytes(16));
+      const [campaign] = PublicKey.findProgramAddressSync(
+        [Buffer.from("campaign"), poorCreator.publicKey.toBuffer(), Buffer.from(id)],
+        program.programId
+      );
+      try {
+        await program.methods
+          .createCampaign(id, AMOUNT_PER_RECIPIENT, 1, crankAuthority.publicKey, null)
+          .accounts({
+            creator: poorCreator.publicKey,
+            campaign,
+            tokenMint: tokenMint,
+            escrowAta: getAssociatedTokenAddressSync(tokenMint, campaign, true),
+            systemProgram: SystemProgram.programId,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          })
+          .signers([poorCreator])
+          .rpc();
+        assert.fail("Should have thrown");
+      } catch (e: any) {
+        expect(e.message).to.include("InsufficientRentForCampaign");
+      }
+      assert.isNull(await provider.connection.getAccountInfo(campaign));
+    });
+  });
+
+  // ---------------------------------------------------------------------------
+  // Mint Decimals
+  // ---------------------------------------------------------------------------
+  describe("Mint Decimals", () => {
+    const eventParser = new anchor.EventParser(
+      program.programId,
+      new anchor.BorshCoder(program.idl)
+    );
+
+    async function createCampaignFor(decimals: number) {
+      const mint = await createMint(
+        provider.connection,
+        creator,
+        creator.publicKey,
+        null,
+        decimals
+      );
+      const id = Array.from(crypto.randomBytes(16));
+      const [campaign] = PublicKey.findProgramAddressSync(
+        [Buffer.from("campaign"), creator.publicKey.toBuffer(), Buffer.from(id)],
+        program.programId
+      );
+      const signature = await program.methods
+        .createCampaign(id, AMOUNT_PER_RECIPIENT, 1, crankAuthority.publicKey, null)
+        .accounts({
+          creator: creator.publicKey,
+          campaign,
+          tokenMint: mint,
+          escrowAta: getAssociatedTokenAddressSync(mint, campaign, true),
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        })
+        .signers([creator])
+        .rpc({ commitment: "confirmed" });
+
+      const tx = await provider.connection.getTransaction(signature, {
+        commitment: "confirmed",
+        maxSupportedTransactionVersion: 0,
+      });
+      const [event] = Array.from(eventParser.parseLogs(tx.meta.logMessages)).filter(
+        (e) => e.name.toLowerCase() === "campaigncreated"
+      );
+      const state = await program.account.campaignState.fetch(campaign);
+      return [state.decimals, event.data.decimals];
+    }
+
+    it("records the decimals of a 6-decimal mint", async () => {
+      assert.deepEqual(await createCampaignFor(6), [6, 6]);
+    });
+
+    it("records the decimals of a 9-decimal mint", async () => {
+      assert.deepEqual(await createCampaignFor(9), [9, 9]);
+    });
+  });
+
+  // ---------------------------------------------------------------------------
+  // Campaign Funding Status
+  // ---------------------------------------------------------------------------
+  describe("Campaign Funding Status", () => {
+    // 2 recipients at 1 token each
+    const RECIPIENTS = 2;
+    const BUDGET = AMOUNT_PER_RECIPIENT.toNumber() * RECIPIENTS;
+
+    before(async () => {
+      await mintTo(provider.connection, creator, tokenMint, creatorAta, creator, 3 * BUDGET);
+    });
+
+    async function fundedCampaign(amount: number) {
+      const id = Array.from(crypto.randomBytes(16));
+      const [campaign] = PublicKey.findProgramAddressSync(
+        [Buffer.from("campaign"), creator.publicKey.toBuffer(), Buffer.from(id)],
+        program.programId
+      );
+      const escrow = getAssociatedTokenAddressSync(tokenMint, campaign, true);
+      await program.methods
+        .createCampaign(id, AMOUNT_PER_RECIPIENT, RECIPIENTS, crankAuthority.publicKey, null)
+        .accounts({
+          creator: creator.publicKey,
+          campaign,
+          tokenMint,
+          escrowAta: escrow,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        })
+        .signers([creator])
+        .rpc();
+      await program.methods
+        .fundCampaign(new anchor.BN(amount))
+        .accounts({
+          creator: creator.publicKey,
+          campaign,
+          creatorAta,
+          escrowAta: escrow,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([creator])
+        .rpc();
+
+      return program.methods.getCampaignFunding().accounts({ campaign }).view();
+    }
+
+    it("reports an underfunded campaign", async () => {
+      const funding = await fundedCampaign(BUDGET / 2);
+      assert.equal(funding.totalAmount.toNumber(), BUDGET / 2);
+      assert.equal(funding.requiredAmount.toNumber(), BUDGET);
+      assert.isFalse(funding.fullyFunded);
+    });
+
+    it("reports an exactly funded campaign", async () => {
+      const funding = await fundedCampaign(BUDGET);
+      assert.equal(funding.totalAmount.toNumber(), BUDGET);
+      assert.equal(funding.requiredAmount.toNumber(), BUDGET);
+      assert.isTrue(funding.fullyFunded);
+    });
+
+    it("reports an overfunded campaign", async () => {
+      const funding = await fundedCampaign(BUDGET + 1);
+      assert.equal(funding.totalAmount.toNumber(), BUDGET + 1);
+      assert.equal(funding.requiredAmount.toNumber(), BUDGET);
+      assert.isTrue(funding.fullyFunded);
+    });
+  });
+
+  // ---------------------------------------------------------------------------
+  // Funding History
+  // ---------------------------------------------------------------------------
+  describe("Funding History", () => {
+    const eventParser = new anchor.EventParser(
+      program.programId,
+      new anchor.BorshCoder(program.idl)
+    );
+
+    it("reports the running total and deposit number with each deposit", async () => {
+      await mintTo(provider.connection, creator, tokenMint, creatorAta, creator, 5_000_000);
+      const id = Array.from(crypto.randomBytes(16));
+      const [campaign] = PublicKey.findProgramAddressSync(
+        [Buffer.from("campaign"), creator.publicKey.toBuffer(), Buffer.from(id)],
+        program.programId
+      );
+      const escrow = getAssociatedTokenAddressSync(tokenMint, campaign, true);
+      await program.methods
+        .createCampaign(id, AMOUNT_PER_RECIPIENT, 5, crankAuthority.publicKey, null)
+        .accounts({
+          creator: creator.publicKey,
+          campaign,
+          tokenMint,
+          escrowAta: escrow,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        })
+        .signers([creator])
+        .rpc();
+      assert.equal((await program.account.campaignState.fetch(campaign)).fundingEventsCount, 0);
+
+      const signature = await program.methods
+        .fundCampaign(new anchor.BN(5_000_000))
+        .accounts({
+          creator: creator.publicKey,
+          campaign,
+          creatorAta,
+          escrowAta: escrow,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([creator])
+        .rpc({ commitment: "confirmed" });
+
+      const tx = await provider.connection.getTransaction(signature, {
+        commitment: "confirmed",
+        maxSupportedTransactionVersion: 0,
+      });
+      const [event] = Array.from(eventParser.parseLogs(tx.meta.logMessages)).filter(
+        (e) => e.name.toLowerCase() === "campaignfunded"
+      );
+      assert.equal(event.data.amount.toNumber(), 5_000_000);
+      assert.equal(event.data.totalAmount.toNumber(), 5_000_000);
+      assert.equal(event.data.fundingEventsCount, 1);
+
+      const state = await program.account.campaignState.fetch(campaign);
+      assert.equal(state.totalAmount.toNumber(), 5_000_000);
+      assert.equal(state.fundingEventsCount, 1);
+    });
+  });
+
+  // ---------------------------------------------------------------------------
+  // Batch Interval
+  // ---------------------------------------------------------------------------
+  describe("Batch Interval", () => {
+    let campaign: PublicKey;
+    let escrow: PublicKey;
+
+    function setBatchInterval(seconds: number) {
+      return program.methods
+        .setBatchInterval(seconds)
+        .accounts({ creator: creator.publicKey, campaign })
+        .signers([creator])
+        .rpc();
+    }
+
+    function distribute(startIndex: number, recipient: Keypair, recipientAta: PublicKey) {
+      return program.methods
+        .distributeBatch(startIndex, 1)
+        .accounts({
+          crankAuthority: crankAuthority.publicKey,
+          campaign,
+          escrowAta: escrow,
+          crankAta: null,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .remainingAccounts(recipientAccounts(campaign, [[recipient.publicKey, recipientAta]]))
+        .signers([crankAuthority])
+        .rpc();
+    }
+
+    before(async () => {
+      const id = Array.from(crypto.randomBytes(16));
+      [campaign] = PublicKey.findProgramAddressSync(
+        [Buffer.from("campaign"), creator.publicKey.toBuffer(), Buffer.from(id)],
+        program.programId
+      );
+      escrow = getAssociatedTokenAddressSync(tokenMint, campaign, true);
+      const fundAmount = AMOUNT_PER_RECIPIENT.toNumber() * 2;
+
+      await mintTo(provider.connection, creator, tokenMint, creatorAta, creator, fundAmount);
+      await program.methods
+        .createCampaign(id, AMOUNT_PER_RECIPIENT, 2, crankAuthority.publicKey, null)
+        .accounts({
+          creator: creator.publicKey,
+          campaign,
+          tokenMint,
+          escrowAta: escrow,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        })
+        .signers([creator])
+        .rpc();
+      await program.methods
+        .fundCampaign(new anchor.BN(fundAmount))
+        .accounts({
+          creator: creator.publicKey,
+          campaign,
+          creatorAta,
+          escrowAta: escrow,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([creator])
+        .rpc();
+    });
+
+    it("only lets the creator set the interval", async () => {
+      try {
+        await program.methods
+          .setBatchInterval(60)
+          .accounts({ creator: crankAuthority.publicKey, campaign })
+          .signers([crankAuthority])
+          .rpc();
+        assert.fail("Should have failed - not the campaign creator");
+      } catch (e: any) {
+        expect(e.message).to.include("ConstraintHasOne");
+      }
+    });
+
+    it("rejects a batch right after the previous one", async () => {
+      await setBatchInterval(3600);
+      await distribute(0, recipient1, recipient1Ata);
+
+      const state = await program.account.campaignState.fetch(campaign);
+      assert.equal(state.minBatchIntervalSeconds, 3600);
+      assert.isAbove(state.lastBatchAt.toNumber(), 0);
+
+      try {
+        await distribute(1, recipient2, recipient2Ata);
+        assert.fail("Should have failed - interval has not passed");
+      } catch (e: any) {
+        expect(e.message).to.include("BatchTooSoon");
+      }
+    });
+
+    it("lets batches run back to back once the interval is 0", async () => {
+      await setBatchInterval(0);
+      await distribute(1, recipient2, recipient2Ata);
+
+      const state = await program.account.campaignState.fetch(campaign);
+      assert.deepEqual(state.status, { completed: {} });
+    });
   });
 });
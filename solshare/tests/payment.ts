@@ -1,8 +1,22 @@
 import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { SolsharePayment } from "../target/types/solshare_payment";
+import overdrawnCreatorKey from "./fixtures/overdrawn-creator-keypair.json";
+import legacyTipperKey from "./fixtures/legacy-tipper-keypair.json";
+import renewalCreatorKey from "./fixtures/renewal-creator-keypair.json";
+import prepaidSubscriberKey from "./fixtures/prepaid-subscriber-keypair.json";
 import { assert, expect } from "chai";
 import { Keypair, PublicKey, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import {
+  createMint,
+  createAssociatedTokenAccount,
+  mintTo,
+  getAccount,
+  getAssociatedTokenAddressSync,
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  NATIVE_MINT,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
 
 describe("solshare-payment", () => {
   const provider = anchor.AnchorProvider.env();
@@ -18,9 +32,22 @@ describe("solshare-payment", () => {
 
   let platformConfigPda: PublicKey;
   let creatorVaultPda: PublicKey;
+  let treasuryPda: PublicKey;
 
   const FEE_BASIS_POINTS = 200; // 2%
 
+  const SOCIAL_PROGRAM_ID = new PublicKey(
+    "sGLNkcQKvfTVYvhJX8KVo4RrzEZL32UTo8ruwpFEHmG"
+  );
+
+  function tipperStatsPda(from: PublicKey, to: PublicKey) {
+    const [pda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("tipper_stats"), from.toBuffer(), to.toBuffer()],
+      program.programId
+    );
+    return pda;
+  }
+
   before(async () => {
     // Airdrop SOL to test users
     const users = [platformAuthority, feeRecipient, creator, tipper, subscriber];
@@ -38,6 +65,11 @@ describe("solshare-payment", () => {
       program.programId
     );
 
+    [treasuryPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("treasury")],
+      program.programId
+    );
+
     [creatorVaultPda] = PublicKey.findProgramAddressSync(
       [Buffer.from("vault"), creator.publicKey.toBuffer()],
       program.programId
@@ -45,6 +77,26 @@ describe("solshare-payment", () => {
   });
 
   describe("Platform Initialization", () => {
+    it("rejects a program-owned fee recipient", async () => {
+      try {
+        await program.methods
+          .initializePlatform(FEE_BASIS_POINTS)
+          .accounts({
+            config: platformConfigPda,
+            authority: platformAuthority.publicKey,
+            // Owned by the BPF loader, so fees swept here could never be spent
+            feeRecipient: program.programId,
+            treasury: treasuryPda,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .signers([platformAuthority])
+          .rpc();
+        assert.fail("Should have failed - program-owned fee recipient");
+      } catch (e: any) {
+        expect(e.message).to.include("InvalidFeeRecipient");
+      }
+    });
+
     it("initializes the platform config", async () => {
       await program.methods
         .initializePlatform(FEE_BASIS_POINTS)
@@ -52,6 +104,7 @@ describe("solshare-payment", () => {
           config: platformConfigPda,
           authority: platformAuthority.publicKey,
           feeRecipient: feeRecipient.publicKey,
+          treasury: treasuryPda,
           systemProgram: anchor.web3.SystemProgram.programId,
         })
         .signers([platformAuthority])
@@ -61,6 +114,10 @@ describe("solshare-payment", () => {
       assert.equal(config.feeBasisPoints, FEE_BASIS_POINTS);
       assert.deepEqual(config.authority, platformAuthority.publicKey);
       assert.deepEqual(config.feeRecipient, feeRecipient.publicKey);
+
+      const treasury = await program.account.treasury.fetch(treasuryPda);
+      assert.equal(treasury.totalCollected.toNumber(), 0);
+      assert.equal(treasury.totalSwept.toNumber(), 0);
     });
 
     it("fails with invalid fee basis points (>10000)", async () => {
@@ -79,11 +136,13 @@ describe("solshare-payment", () => {
   describe("Creator Vault", () => {
     it("initializes a creator vault", async () => {
       await program.methods
-        .initializeVault()
+        .initializeVault(null)
         .accounts({
+          config: platformConfigPda,
           vault: creatorVaultPda,
           creator: creator.publicKey,
           systemProgram: anchor.web3.SystemProgram.programId,
+          platformStats: null,
         })
         .signers([creator])
         .rpc();
@@ -111,23 +170,25 @@ describe("solshare-payment", () => {
       );
 
       const vaultBalanceBefore = await provider.connection.getBalance(creatorVaultPda);
-      const feeRecipientBalanceBefore = await provider.connection.getBalance(feeRecipient.publicKey);
+      const treasuryBalanceBefore = await provider.connection.getBalance(treasuryPda);
 
       await program.methods
-        .tipCreator(new anchor.BN(tipAmount), null, tipIndex)
+        .tipCreator(new anchor.BN(tipAmount), null, tipIndex, null)
         .accounts({
           config: platformConfigPda,
           creatorVault: creatorVaultPda,
           tipRecord: tipRecordPda,
           tipper: tipper.publicKey,
-          feeRecipient: feeRecipient.publicKey,
+          tipperStats: tipperStatsPda(tipper.publicKey, creator.publicKey),
+          treasury: treasuryPda,
           systemProgram: anchor.web3.SystemProgram.programId,
+          platformStats: null,
         })
         .signers([tipper])
         .rpc();
 
       const vaultBalanceAfter = await provider.connection.getBalance(creatorVaultPda);
-      const feeRecipientBalanceAfter = await provider.connection.getBalance(feeRecipient.publicKey);
+      const treasuryBalanceAfter = await provider.connection.getBalance(treasuryPda);
 
       const expectedFee = Math.floor((tipAmount * FEE_BASIS_POINTS) / 10000);
       const expectedCreatorAmount = tipAmount - expectedFee;
@@ -138,7 +199,7 @@ describe("solshare-payment", () => {
         expectedCreatorAmount
       );
       assert.equal(
-        feeRecipientBalanceAfter - feeRecipientBalanceBefore,
+        treasuryBalanceAfter - treasuryBalanceBefore,
         expectedFee
       );
 
@@ -168,14 +229,16 @@ describe("solshare-payment", () => {
       );
 
       await program.methods
-        .tipCreator(new anchor.BN(tipAmount), postPubkey, tipIndex)
+        .tipCreator(new anchor.BN(tipAmount), postPubkey, tipIndex, null)
         .accounts({
           config: platformConfigPda,
           creatorVault: creatorVaultPda,
           tipRecord: tipRecordPda,
           tipper: tipper.publicKey,
-          feeRecipient: feeRecipient.publicKey,
+          tipperStats: tipperStatsPda(tipper.publicKey, creator.publicKey),
+          treasury: treasuryPda,
           systemProgram: anchor.web3.SystemProgram.programId,
+          platformStats: null,
         })
         .signers([tipper])
         .rpc();
@@ -200,14 +263,16 @@ describe("solshare-payment", () => {
       const vaultBefore = await provider.connection.getBalance(creatorVaultPda);
 
       await program.methods
-        .tipCreator(new anchor.BN(tipAmount), null, tipIndex)
+        .tipCreator(new anchor.BN(tipAmount), null, tipIndex, null)
         .accounts({
           config: platformConfigPda,
           creatorVault: creatorVaultPda,
           tipRecord: tipRecordPda,
           tipper: tipper.publicKey,
-          feeRecipient: feeRecipient.publicKey,
+          tipperStats: tipperStatsPda(tipper.publicKey, creator.publicKey),
+          treasury: treasuryPda,
           systemProgram: anchor.web3.SystemProgram.programId,
+          platformStats: null,
         })
         .signers([tipper])
         .rpc();
@@ -233,14 +298,16 @@ describe("solshare-payment", () => {
 
       try {
         await program.methods
-          .tipCreator(new anchor.BN(0.01 * LAMPORTS_PER_SOL), null, tipIndex)
+          .tipCreator(new anchor.BN(0.01 * LAMPORTS_PER_SOL), null, tipIndex, null)
           .accounts({
             config: platformConfigPda,
             creatorVault: creatorVaultPda,
             tipRecord: tipRecordPda,
             tipper: creator.publicKey,
-            feeRecipient: feeRecipient.publicKey,
+            tipperStats: tipperStatsPda(creator.publicKey, creator.publicKey),
+            treasury: treasuryPda,
             systemProgram: anchor.web3.SystemProgram.programId,
+            platformStats: null,
           })
           .signers([creator])
           .rpc();
@@ -263,14 +330,16 @@ describe("solshare-payment", () => {
 
       try {
         await program.methods
-          .tipCreator(new anchor.BN(0), null, tipIndex)
+          .tipCreator(new anchor.BN(0), null, tipIndex, null)
           .accounts({
             config: platformConfigPda,
             creatorVault: creatorVaultPda,
             tipRecord: tipRecordPda,
             tipper: tipper.publicKey,
-            feeRecipient: feeRecipient.publicKey,
+            tipperStats: tipperStatsPda(tipper.publicKey, creator.publicKey),
+            treasury: treasuryPda,
             systemProgram: anchor.web3.SystemProgram.programId,
+            platformStats: null,
           })
           .signers([tipper])
           .rpc();
@@ -296,17 +365,20 @@ describe("solshare-payment", () => {
       );
 
       const vaultBalanceBefore = await provider.connection.getBalance(creatorVaultPda);
-      const feeRecipientBalanceBefore = await provider.connection.getBalance(feeRecipient.publicKey);
+      const treasuryBalanceBefore = await provider.connection.getBalance(treasuryPda);
 
       await program.methods
-        .subscribe(new anchor.BN(subscriptionAmount))
+        .subscribe(new anchor.BN(subscriptionAmount), null, null, null)
         .accounts({
           config: platformConfigPda,
           creatorVault: creatorVaultPda,
           subscription: subscriptionPda,
           subscriber: subscriber.publicKey,
-          feeRecipient: feeRecipient.publicKey,
+          treasury: treasuryPda,
           systemProgram: anchor.web3.SystemProgram.programId,
+          platformStats: null,
+          subscriptionTiers: null,
+          subscriberIndex: null,
         })
         .signers([subscriber])
         .rpc();
@@ -316,6 +388,8 @@ describe("solshare-payment", () => {
       assert.deepEqual(subscription.creator, creator.publicKey);
       assert.equal(subscription.amountPerMonth.toNumber(), subscriptionAmount);
       assert.equal(subscription.isActive, true);
+      // The first month's payment is sequence number 1
+      assert.equal(subscription.paymentCount, 1);
 
       const vault = await program.account.creatorVault.fetch(creatorVaultPda);
       assert.equal(vault.subscribers.toNumber(), 1);
@@ -329,10 +403,10 @@ describe("solshare-payment", () => {
         expectedCreatorAmount
       );
 
-      // Verify fee recipient received the fee
-      const feeRecipientBalanceAfter = await provider.connection.getBalance(feeRecipient.publicKey);
+      // Verify the treasury received the fee
+      const treasuryBalanceAfter = await provider.connection.getBalance(treasuryPda);
       assert.equal(
-        feeRecipientBalanceAfter - feeRecipientBalanceBefore,
+        treasuryBalanceAfter - treasuryBalanceBefore,
         expectedFee
       );
     });
@@ -358,14 +432,17 @@ describe("solshare-payment", () => {
 
       try {
         await program.methods
-          .subscribe(new anchor.BN(subscriptionAmount))
+          .subscribe(new anchor.BN(subscriptionAmount), null, null, null)
           .accounts({
             config: platformConfigPda,
             creatorVault: creatorVaultPda,
             subscription: selfSubPda,
             subscriber: creator.publicKey,
-            feeRecipient: feeRecipient.publicKey,
+            treasury: treasuryPda,
             systemProgram: anchor.web3.SystemProgram.programId,
+            platformStats: null,
+            subscriptionTiers: null,
+            subscriberIndex: null,
           })
           .signers([creator])
           .rpc();
@@ -382,6 +459,7 @@ describe("solshare-payment", () => {
           creatorVault: creatorVaultPda,
           subscription: subscriptionPda,
           subscriber: subscriber.publicKey,
+          subscriberIndex: null,
         })
         .signers([subscriber])
         .rpc();
@@ -401,6 +479,7 @@ describe("solshare-payment", () => {
             creatorVault: creatorVaultPda,
             subscription: subscriptionPda,
             subscriber: subscriber.publicKey,
+            subscriberIndex: null,
           })
           .signers([subscriber])
           .rpc();
@@ -423,8 +502,10 @@ describe("solshare-payment", () => {
       await program.methods
         .withdraw(new anchor.BN(withdrawAmount))
         .accounts({
+          config: platformConfigPda,
           vault: creatorVaultPda,
           creator: creator.publicKey,
+          treasury: treasuryPda,
         })
         .signers([creator])
         .rpc();
@@ -457,8 +538,10 @@ describe("solshare-payment", () => {
       await program.methods
         .withdraw(new anchor.BN(available))
         .accounts({
+          config: platformConfigPda,
           vault: creatorVaultPda,
           creator: creator.publicKey,
+          treasury: treasuryPda,
         })
         .signers([creator])
         .rpc();
@@ -473,22 +556,24 @@ describe("solshare-payment", () => {
       );
     });
 
-    it("fails to withdraw more than available balance", async () => {
+    it("reports NothingToWithdraw once the vault is emptied", async () => {
       const vaultData = await program.account.creatorVault.fetch(creatorVaultPda);
-      const available = vaultData.totalEarned.toNumber() - vaultData.withdrawn.toNumber();
+      assert.equal(vaultData.withdrawn.toNumber(), vaultData.totalEarned.toNumber());
 
       try {
         await program.methods
-          .withdraw(new anchor.BN(available + 1000))
+          .withdraw(new anchor.BN(1000))
           .accounts({
+            config: platformConfigPda,
             vault: creatorVaultPda,
             creator: creator.publicKey,
+            treasury: treasuryPda,
           })
           .signers([creator])
           .rpc();
-        assert.fail("Should have failed - withdrawal exceeds balance");
+        assert.fail("Should have failed - nothing to withdraw");
       } catch (e: any) {
-        expect(e.message).to.include("WithdrawalExceedsBalance");
+        expect(e.message).to.include("NothingToWithdraw");
       }
     });
 
@@ -506,14 +591,16 @@ describe("solshare-payment", () => {
       );
 
       await program.methods
-        .tipCreator(new anchor.BN(tipAmount), null, tipIndex)
+        .tipCreator(new anchor.BN(tipAmount), null, tipIndex, null)
         .accounts({
           config: platformConfigPda,
           creatorVault: creatorVaultPda,
           tipRecord: tipRecordPda,
           tipper: tipper.publicKey,
-          feeRecipient: feeRecipient.publicKey,
+          tipperStats: tipperStatsPda(tipper.publicKey, creator.publicKey),
+          treasury: treasuryPda,
           systemProgram: anchor.web3.SystemProgram.programId,
+          platformStats: null,
         })
         .signers([tipper])
         .rpc();
@@ -534,8 +621,10 @@ describe("solshare-payment", () => {
         await program.methods
           .withdraw(new anchor.BN(maxWithdrawable + 1))
           .accounts({
+            config: platformConfigPda,
             vault: creatorVaultPda,
             creator: creator.publicKey,
+            treasury: treasuryPda,
           })
           .signers([creator])
           .rpc();
@@ -549,13 +638,36 @@ describe("solshare-payment", () => {
       }
     });
 
+    it("fails to withdraw more than available balance", async () => {
+      const vaultData = await program.account.creatorVault.fetch(creatorVaultPda);
+      const available = vaultData.totalEarned.toNumber() - vaultData.withdrawn.toNumber();
+
+      try {
+        await program.methods
+          .withdraw(new anchor.BN(available + 1000))
+          .accounts({
+            config: platformConfigPda,
+            vault: creatorVaultPda,
+            creator: creator.publicKey,
+            treasury: treasuryPda,
+          })
+          .signers([creator])
+          .rpc();
+        assert.fail("Should have failed - withdrawal exceeds balance");
+      } catch (e: any) {
+        expect(e.message).to.include("WithdrawalExceedsBalance");
+      }
+    });
+
     it("fails to withdraw zero amount", async () => {
       try {
         await program.methods
           .withdraw(new anchor.BN(0))
           .accounts({
+            config: platformConfigPda,
             vault: creatorVaultPda,
             creator: creator.publicKey,
+            treasury: treasuryPda,
           })
           .signers([creator])
           .rpc();
@@ -580,8 +692,10 @@ describe("solshare-payment", () => {
         await program.methods
           .withdraw(new anchor.BN(1000))
           .accounts({
+            config: platformConfigPda,
             vault: creatorVaultPda,
             creator: attacker.publicKey,
+            treasury: treasuryPda,
           })
           .signers([attacker])
           .rpc();
@@ -593,4 +707,3587 @@ describe("solshare-payment", () => {
       }
     });
   });
+
+  // ---------------------------------------------------------------------------
+  // Tip Post Validation
+  // ---------------------------------------------------------------------------
+  describe("Tip Post Validation", () => {
+    function tipRecordPda(tipIndex: anchor.BN): PublicKey {
+      const [pda] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("tip"),
+          tipper.publicKey.toBuffer(),
+          tipIndex.toArrayLike(Buffer, "le", 8),
+        ],
+        program.programId
+      );
+      return pda;
+    }
+
+    function tipWithPost(post: PublicKey, tipIndex: anchor.BN, postIndex: anchor.BN) {
+      return program.methods
+        .tipCreator(new anchor.BN(0.01 * LAMPORTS_PER_SOL), post, tipIndex, postIndex)
+        .accounts({
+          config: platformConfigPda,
+          creatorVault: creatorVaultPda,
+          tipRecord: tipRecordPda(tipIndex),
+          tipper: tipper.publicKey,
+          tipperStats: tipperStatsPda(tipper.publicKey, creator.publicKey),
+          treasury: treasuryPda,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          platformStats: null,
+        })
+        .signers([tipper])
+        .rpc();
+    }
+
+    it("accepts a post that derives from the tipped creator", async () => {
+      const postIndex = new anchor.BN(0);
+      const [creatorPost] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("post"),
+          creator.publicKey.toBuffer(),
+          postIndex.toArrayLike(Buffer, "le", 8),
+        ],
+        SOCIAL_PROGRAM_ID
+      );
+
+      const tipIndex = new anchor.BN(200);
+      await tipWithPost(creatorPost, tipIndex, postIndex);
+
+      const tipRecord = await program.account.tipRecord.fetch(tipRecordPda(tipIndex));
+      assert.deepEqual(tipRecord.post, creatorPost);
+    });
+
+    it("rejects a spoofed post when validation is requested", async () => {
+      // Another wallet's post, claimed as the creator's
+      const postIndex = new anchor.BN(0);
+      const [otherPost] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("post"),
+          tipper.publicKey.toBuffer(),
+          postIndex.toArrayLike(Buffer, "le", 8),
+        ],
+        SOCIAL_PROGRAM_ID
+      );
+
+      try {
+        await tipWithPost(otherPost, new anchor.BN(201), postIndex);
+        assert.fail("Should have failed - post belongs to another wallet");
+      } catch (e: any) {
+        expect(e.message).to.include("InvalidPost");
+      }
+    });
+  });
+
+  // ---------------------------------------------------------------------------
+  // Treasury
+  // ---------------------------------------------------------------------------
+  describe("Treasury", () => {
+    it("tracks fees collected across tips and subscriptions", async () => {
+      const treasury = await program.account.treasury.fetch(treasuryPda);
+      assert.isAbove(treasury.totalCollected.toNumber(), 0);
+    });
+
+    it("rejects a sweep from a non-authority", async () => {
+      const impostor = Keypair.generate();
+      try {
+        await program.methods
+          .sweepTreasury()
+          .accounts({
+            config: platformConfigPda,
+            treasury: treasuryPda,
+            feeRecipient: feeRecipient.publicKey,
+            authority: impostor.publicKey,
+          })
+          .signers([impostor])
+          .rpc();
+        assert.fail("Expected Unauthorized error");
+      } catch (e: any) {
+        expect(e.message).to.include("Unauthorized");
+      }
+    });
+
+    it("sweeps accumulated fees to the fee recipient", async () => {
+      const before = await program.account.treasury.fetch(treasuryPda);
+      const treasuryBalanceBefore = await provider.connection.getBalance(treasuryPda);
+      const feeRecipientBalanceBefore = await provider.connection.getBalance(feeRecipient.publicKey);
+
+      await program.methods
+        .sweepTreasury()
+        .accounts({
+          config: platformConfigPda,
+          treasury: treasuryPda,
+          feeRecipient: feeRecipient.publicKey,
+          authority: platformAuthority.publicKey,
+        })
+        .signers([platformAuthority])
+        .rpc();
+
+      const treasuryBalanceAfter = await provider.connection.getBalance(treasuryPda);
+      const feeRecipientBalanceAfter = await provider.connection.getBalance(feeRecipient.publicKey);
+      const swept = treasuryBalanceBefore - treasuryBalanceAfter;
+
+      assert.isAbove(swept, 0);
+      assert.equal(feeRecipientBalanceAfter - feeRecipientBalanceBefore, swept);
+
+      const after = await program.account.treasury.fetch(treasuryPda);
+      assert.equal(after.totalSwept.toNumber(), before.totalSwept.toNumber() + swept);
+      assert.equal(after.totalCollected.toNumber(), before.totalCollected.toNumber());
+    });
+
+    it("fails to sweep an empty treasury", async () => {
+      try {
+        await program.methods
+          .sweepTreasury()
+          .accounts({
+            config: platformConfigPda,
+            treasury: treasuryPda,
+            feeRecipient: feeRecipient.publicKey,
+            authority: platformAuthority.publicKey,
+          })
+          .signers([platformAuthority])
+          .rpc();
+        assert.fail("Expected InsufficientFunds error");
+      } catch (e: any) {
+        expect(e.message).to.include("InsufficientFunds");
+      }
+    });
+
+    function initializeTreasury(authority: Keypair) {
+      return program.methods
+        .initializeTreasury()
+        .accounts({
+          config: platformConfigPda,
+          treasury: treasuryPda,
+          authority: authority.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([authority])
+        .rpc();
+    }
+
+    it("only lets the platform authority create the treasury", async () => {
+      const impostor = Keypair.generate();
+      try {
+        await initializeTreasury(impostor);
+        assert.fail("Expected Unauthorized error");
+      } catch (e: any) {
+        expect(e.message).to.include("Unauthorized");
+      }
+    });
+
+    it("won't recreate an existing treasury", async () => {
+      const before = await program.account.treasury.fetch(treasuryPda);
+      try {
+        await initializeTreasury(platformAuthority);
+        assert.fail("Expected the treasury to already exist");
+      } catch (e: any) {
+        expect(e.message).to.include("already in use");
+      }
+      const after = await program.account.treasury.fetch(treasuryPda);
+      assert.equal(after.totalCollected.toNumber(), before.totalCollected.toNumber());
+    });
+  });
+
+  // ---------------------------------------------------------------------------
+  // Subscription Amount Ceiling
+  // ---------------------------------------------------------------------------
+  describe("Subscription Amount Ceiling", () => {
+    const MAX_AMOUNT_PER_MONTH = 0.05 * LAMPORTS_PER_SOL;
+    const cappedSubscriber = Keypair.generate();
+    let cappedSubscriptionPda: PublicKey;
+
+    before(async () => {
+      const airdropSig = await provider.connection.requestAirdrop(
+        cappedSubscriber.publicKey,
+        5 * LAMPORTS_PER_SOL
+      );
+      await provider.connection.confirmTransaction(airdropSig);
+
+      [cappedSubscriptionPda] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("subscription"),
+          cappedSubscriber.publicKey.toBuffer(),
+          creator.publicKey.toBuffer(),
+        ],
+        program.programId
+      );
+
+      await program.methods
+        .setMaxSubscriptionAmount(new anchor.BN(MAX_AMOUNT_PER_MONTH))
+        .accounts({
+          config: platformConfigPda,
+          authority: platformAuthority.publicKey,
+        })
+        .signers([platformAuthority])
+        .rpc();
+    });
+
+    after(async () => {
+      // Later suites share the platform config
+      await program.methods
+        .setMaxSubscriptionAmount(null)
+        .accounts({
+          config: platformConfigPda,
+          authority: platformAuthority.publicKey,
+        })
+        .signers([platformAuthority])
+        .rpc();
+    });
+
+    it("rejects a subscription above the ceiling", async () => {
+      try {
+        await program.methods
+          .subscribe(new anchor.BN(LAMPORTS_PER_SOL), null, null, null)
+          .accounts({
+            config: platformConfigPda,
+            creatorVault: creatorVaultPda,
+            subscription: cappedSubscriptionPda,
+            subscriber: cappedSubscriber.publicKey,
+            treasury: treasuryPda,
+            systemProgram: anchor.web3.SystemProgram.programId,
+            platformStats: null,
+            subscriptionTiers: null,
+            subscriberIndex: null,
+          })
+          .signers([cappedSubscriber])
+          .rpc();
+        assert.fail("Expected SubscriptionAmountTooHigh error");
+      } catch (e: any) {
+        expect(e.message).to.include("SubscriptionAmountTooHigh");
+      }
+    });
+
+    it("accepts a subscription at the ceiling", async () => {
+      await program.methods
+        .subscribe(new anchor.BN(MAX_AMOUNT_PER_MONTH), null, null, null)
+        .accounts({
+          config: platformConfigPda,
+          creatorVault: creatorVaultPda,
+          subscription: cappedSubscriptionPda,
+          subscriber: cappedSubscriber.publicKey,
+          treasury: treasuryPda,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          platformStats: null,
+          subscriptionTiers: null,
+          subscriberIndex: null,
+        })
+        .signers([cappedSubscriber])
+        .rpc();
+
+      const subscription = await program.account.subscription.fetch(cappedSubscriptionPda);
+      assert.equal(subscription.amountPerMonth.toNumber(), MAX_AMOUNT_PER_MONTH);
+    });
+
+    it("fails when a non-authority sets the ceiling", async () => {
+      try {
+        await program.methods
+          .setMaxSubscriptionAmount(null)
+          .accounts({
+            config: platformConfigPda,
+            authority: cappedSubscriber.publicKey,
+          })
+          .signers([cappedSubscriber])
+          .rpc();
+        assert.fail("Expected Unauthorized error");
+      } catch (e: any) {
+        expect(e.message).to.include("Unauthorized");
+      }
+    });
+  });
+
+  // ---------------------------------------------------------------------------
+  // Tip Record Cleanup
+  // ---------------------------------------------------------------------------
+  describe("Tip Record Cleanup", () => {
+    let tipRecordPda: PublicKey;
+
+    before(async () => {
+      const tipIndex = new anchor.BN(300);
+      [tipRecordPda] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("tip"),
+          tipper.publicKey.toBuffer(),
+          tipIndex.toArrayLike(Buffer, "le", 8),
+        ],
+        program.programId
+      );
+
+      await program.methods
+        .tipCreator(new anchor.BN(0.01 * LAMPORTS_PER_SOL), null, tipIndex, null)
+        .accounts({
+          config: platformConfigPda,
+          creatorVault: creatorVaultPda,
+          tipRecord: tipRecordPda,
+          tipper: tipper.publicKey,
+          tipperStats: tipperStatsPda(tipper.publicKey, creator.publicKey),
+          treasury: treasuryPda,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          platformStats: null,
+        })
+        .signers([tipper])
+        .rpc();
+    });
+
+    it("fails when someone other than the tipper closes the record", async () => {
+      try {
+        await program.methods
+          .closeTipRecord()
+          .accounts({
+            tipRecord: tipRecordPda,
+            tipper: subscriber.publicKey,
+          })
+          .signers([subscriber])
+          .rpc();
+        assert.fail("Expected Unauthorized error");
+      } catch (e: any) {
+        expect(e.message).to.include("Unauthorized");
+      }
+    });
+
+    it("closes the record and refunds rent to the tipper", async () => {
+      const rent = await provider.connection.getBalance(tipRecordPda);
+      const tipperBalanceBefore = await provider.connection.getBalance(tipper.publicKey);
+
+      await program.methods
+        .closeTipRecord()
+        .accounts({
+          tipRecord: tipRecordPda,
+          tipper: tipper.publicKey,
+        })
+        .signers([tipper])
+        .rpc();
+
+      const tipRecordInfo = await provider.connection.getAccountInfo(tipRecordPda);
+      assert.isNull(tipRecordInfo);
+
+      // The tipper also pays the transaction fee
+      const tipperBalanceAfter = await provider.connection.getBalance(tipper.publicKey);
+      assert.isAbove(tipperBalanceAfter - tipperBalanceBefore, rent - 10_000);
+    });
+  });
+
+  // ---------------------------------------------------------------------------
+  // SPL Token Subscriptions
+  // ---------------------------------------------------------------------------
+  describe("SPL Token Subscriptions", () => {
+    const USDC_DECIMALS = 6;
+    const AMOUNT_PER_MONTH = 5_000_000; // 5 USDC
+    const splSubscriber = Keypair.generate();
+
+    let usdcMint: PublicKey;
+    let subscriberTokenAccount: PublicKey;
+    let creatorTokenAccount: PublicKey;
+    let feeTokenAccount: PublicKey;
+    let splSubscriptionPda: PublicKey;
+
+    before(async () => {
+      const airdropSig = await provider.connection.requestAirdrop(
+        splSubscriber.publicKey,
+        5 * LAMPORTS_PER_SOL
+      );
+      await provider.connection.confirmTransaction(airdropSig);
+
+      usdcMint = await createMint(
+        provider.connection,
+        splSubscriber,
+        splSubscriber.publicKey,
+        null,
+        USDC_DECIMALS
+      );
+      subscriberTokenAccount = await createAssociatedTokenAccount(
+        provider.connection,
+        splSubscriber,
+        usdcMint,
+        splSubscriber.publicKey
+      );
+      creatorTokenAccount = await createAssociatedTokenAccount(
+        provider.connection,
+        splSubscriber,
+        usdcMint,
+        creator.publicKey
+      );
+      feeTokenAccount = await createAssociatedTokenAccount(
+        provider.connection,
+        splSubscriber,
+        usdcMint,
+        feeRecipient.publicKey
+      );
+      await mintTo(
+        provider.connection,
+        splSubscriber,
+        usdcMint,
+        subscriberTokenAccount,
+        splSubscriber,
+        10 * AMOUNT_PER_MONTH
+      );
+
+      [splSubscriptionPda] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("subscription"),
+          splSubscriber.publicKey.toBuffer(),
+          creator.publicKey.toBuffer(),
+        ],
+        program.programId
+      );
+    });
+
+    it("rejects an SPL subscription above the ceiling", async () => {
+      await program.methods
+        .setMaxSubscriptionAmount(new anchor.BN(AMOUNT_PER_MONTH - 1))
+        .accounts({
+          config: platformConfigPda,
+          authority: platformAuthority.publicKey,
+        })
+        .signers([platformAuthority])
+        .rpc();
+
+      try {
+        await program.methods
+          .subscribeSpl(new anchor.BN(AMOUNT_PER_MONTH), null, null, null)
+          .accounts({
+            config: platformConfigPda,
+            creatorVault: creatorVaultPda,
+            subscription: splSubscriptionPda,
+            subscriber: splSubscriber.publicKey,
+            mint: usdcMint,
+            subscriberTokenAccount,
+            creatorTokenAccount,
+            feeTokenAccount,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: anchor.web3.SystemProgram.programId,
+            subscriberIndex: null,
+            subscriptionTiers: null,
+          })
+          .signers([splSubscriber])
+          .rpc();
+        assert.fail("Expected SubscriptionAmountTooHigh error");
+      } catch (e: any) {
+        expect(e.message).to.include("SubscriptionAmountTooHigh");
+      } finally {
+        await program.methods
+          .setMaxSubscriptionAmount(null)
+          .accounts({
+            config: platformConfigPda,
+            authority: platformAuthority.publicKey,
+          })
+          .signers([platformAuthority])
+          .rpc();
+      }
+    });
+
+    it("pays the first month in the subscription's mint", async () => {
+      await program.methods
+        .subscribeSpl(new anchor.BN(AMOUNT_PER_MONTH), null, null, null)
+        .accounts({
+          config: platformConfigPda,
+          creatorVault: creatorVaultPda,
+          subscription: splSubscriptionPda,
+          subscriber: splSubscriber.publicKey,
+          mint: usdcMint,
+          subscriberTokenAccount,
+          creatorTokenAccount,
+          feeTokenAccount,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          subscriberIndex: null,
+          subscriptionTiers: null,
+        })
+        .signers([splSubscriber])
+        .rpc();
+
+      const expectedFee = Math.floor((AMOUNT_PER_MONTH * FEE_BASIS_POINTS) / 10000);
+      const creatorAccount = await getAccount(provider.connection, creatorTokenAccount);
+      const feeAccount = await getAccount(provider.connection, feeTokenAccount);
+      assert.equal(Number(creatorAccount.amount), AMOUNT_PER_MONTH - expectedFee);
+      assert.equal(Number(feeAccount.amount), expectedFee);
+
+      const subscription = await program.account.subscription.fetch(splSubscriptionPda);
+      assert.deepEqual(subscription.mint, usdcMint);
+      assert.equal(subscription.amountPerMonth.toNumber(), AMOUNT_PER_MONTH);
+      assert.equal(subscription.paymentCount, 1);
+      assert.equal(subscription.isActive, true);
+    });
+
+    it("rejects a crank before the next payment is due", async () => {
+      // The 30-day period can't be advanced on the local validator
+      try {
+        await program.methods
+          .processSubscriptionSpl()
+          .accounts({
+            config: platformConfigPda,
+            creatorVault: creatorVaultPda,
+            subscription: splSubscriptionPda,
+            subscriber: splSubscriber.publicKey,
+            mint: usdcMint,
+            subscriberTokenAccount,
+            creatorTokenAccount,
+            feeTokenAccount,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([splSubscriber])
+          .rpc();
+        assert.fail("Expected PaymentNotDue error");
+      } catch (e: any) {
+        expect(e.message).to.include("PaymentNotDue");
+      }
+    });
+
+    it("rejects cranking an SPL subscription through the SOL path", async () => {
+      try {
+        await program.methods
+          .processSubscription()
+          .accounts({
+            config: platformConfigPda,
+            creatorVault: creatorVaultPda,
+            subscription: splSubscriptionPda,
+            subscriber: splSubscriber.publicKey,
+            treasury: treasuryPda,
+            systemProgram: anchor.web3.SystemProgram.programId,
+            platformStats: null,
+            crank: null,
+          })
+          .signers([splSubscriber])
+          .rpc();
+        assert.fail("Expected SubscriptionMintMismatch error");
+      } catch (e: any) {
+        expect(e.message).to.include("SubscriptionMintMismatch");
+      }
+    });
+  });
+
+  // ---------------------------------------------------------------------------
+  // Creator Required Mint
+  // ---------------------------------------------------------------------------
+  describe("Creator Required Mint", () => {
+    const AMOUNT_PER_MONTH = 1_000_000;
+    const payer = Keypair.generate();
+    let acceptedMint: PublicKey;
+    let junkMint: PublicKey;
+
+    async function subscribeWith(mint: PublicKey) {
+      const subscriberTokenAccount = await createAssociatedTokenAccount(
+        provider.connection,
+        payer,
+        mint,
+        payer.publicKey
+      );
+      await mintTo(
+        provider.connection,
+        payer,
+        mint,
+        subscriberTokenAccount,
+        payer,
+        AMOUNT_PER_MONTH
+      );
+      const creatorTokenAccount = await createAssociatedTokenAccount(
+        provider.connection,
+        payer,
+        mint,
+        creator.publicKey
+      );
+      const feeTokenAccount = await createAssociatedTokenAccount(
+        provider.connection,
+        payer,
+        mint,
+        feeRecipient.publicKey
+      );
+      const [subscriptionPda] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("subscription"),
+          payer.publicKey.toBuffer(),
+          creator.publicKey.toBuffer(),
+        ],
+        program.programId
+      );
+
+      await program.methods
+        .subscribeSpl(new anchor.BN(AMOUNT_PER_MONTH), null, null, null)
+        .accounts({
+          config: platformConfigPda,
+          creatorVault: creatorVaultPda,
+          subscription: subscriptionPda,
+          subscriber: payer.publicKey,
+          mint,
+          subscriberTokenAccount,
+          creatorTokenAccount,
+          feeTokenAccount,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          subscriberIndex: null,
+          subscriptionTiers: null,
+        })
+        .signers([payer])
+        .rpc();
+      return subscriptionPda;
+    }
+
+    before(async () => {
+      const airdropSig = await provider.connection.requestAirdrop(
+        payer.publicKey,
+        5 * LAMPORTS_PER_SOL
+      );
+      await provider.connection.confirmTransaction(airdropSig);
+
+      acceptedMint = await createMint(provider.connection, payer, payer.publicKey, null, 6);
+      junkMint = await createMint(provider.connection, payer, payer.publicKey, null, 6);
+
+      await program.methods
+        .setRequiredMint(acceptedMint)
+        .accounts({
+          vault: creatorVaultPda,
+          creator: creator.publicKey,
+        })
+        .signers([creator])
+        .rpc();
+    });
+
+    after(async () => {
+      await program.methods
+        .setRequiredMint(null)
+        .accounts({
+          vault: creatorVaultPda,
+          creator: creator.publicKey,
+        })
+        .signers([creator])
+        .rpc();
+    });
+
+    it("rejects payments in a mint the creator doesn't accept", async () => {
+      try {
+        await subscribeWith(junkMint);
+        assert.fail("Expected UnacceptedMint error");
+      } catch (e: any) {
+        expect(e.message).to.include("UnacceptedMint");
+      }
+    });
+
+    it("accepts payments in the required mint", async () => {
+      const subscriptionPda = await subscribeWith(acceptedMint);
+      const subscription = await program.account.subscription.fetch(subscriptionPda);
+      assert.deepEqual(subscription.mint, acceptedMint);
+    });
+
+    it("fails when someone other than the creator sets the mint", async () => {
+      try {
+        await program.methods
+          .setRequiredMint(null)
+          .accounts({
+            vault: creatorVaultPda,
+            creator: payer.publicKey,
+          })
+          .signers([payer])
+          .rpc();
+        assert.fail("Expected the vault seeds check to fail");
+      } catch (e: any) {
+        expect(e).to.not.be.null;
+      }
+    });
+  });
+
+  // ---------------------------------------------------------------------------
+  // Daily Earnings Rollup
+  // ---------------------------------------------------------------------------
+  describe("Daily Earnings Rollup", () => {
+    it("records a rollup of earnings since the last one", async () => {
+      await program.methods
+        .recordDailyRollup()
+        .accounts({ vault: creatorVaultPda })
+        .rpc();
+
+      const vault = await program.account.creatorVault.fetch(creatorVaultPda);
+      const today = Math.floor(Date.now() / 1000 / 86400);
+      assert.approximately(vault.lastRollupDay.toNumber(), today, 1);
+      assert.equal(
+        vault.lastRollupEarned.toNumber(),
+        vault.totalEarned.toNumber()
+      );
+    });
+
+    it("rejects a second rollup on the same day", async () => {
+      try {
+        await program.methods
+          .recordDailyRollup()
+          .accounts({ vault: creatorVaultPda })
+          .rpc();
+        assert.fail("Expected RollupAlreadyRecorded error");
+      } catch (e: any) {
+        expect(e.message).to.include("RollupAlreadyRecorded");
+      }
+    });
+  });
+
+  // ---------------------------------------------------------------------------
+  // Subscription funding pre-check
+  // ---------------------------------------------------------------------------
+  describe("Subscription Funding", () => {
+    const amountPerMonth = 0.01 * LAMPORTS_PER_SOL;
+    const underfundedSubscriber = Keypair.generate();
+
+    before(async () => {
+      // Covers the first payment but not the subscription account rent
+      const airdropSig = await provider.connection.requestAirdrop(
+        underfundedSubscriber.publicKey,
+        amountPerMonth + 900_000
+      );
+      await provider.connection.confirmTransaction(airdropSig);
+    });
+
+    it("rejects a subscriber who cannot cover payment plus rent", async () => {
+      const [underfundedSubscriptionPda] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("subscription"),
+          underfundedSubscriber.publicKey.toBuffer(),
+          creator.publicKey.toBuffer(),
+        ],
+        program.programId
+      );
+
+      try {
+        await program.methods
+          .subscribe(new anchor.BN(amountPerMonth), null, null, null)
+          .accounts({
+            config: platformConfigPda,
+            creatorVault: creatorVaultPda,
+            subscription: underfundedSubscriptionPda,
+            subscriber: underfundedSubscriber.publicKey,
+            treasury: treasuryPda,
+            systemProgram: anchor.web3.SystemProgram.programId,
+            platformStats: null,
+            subscriptionTiers: null,
+            subscriberIndex: null,
+          })
+          .signers([underfundedSubscriber])
+          .rpc();
+        assert.fail("Expected InsufficientFundsForSubscription error");
+      } catch (e: any) {
+        expect(e.message).to.include("InsufficientFundsForSubscription");
+      }
+
+      const info = await provider.connection.getAccountInfo(
+        underfundedSubscriptionPda
+      );
+      assert.isNull(info);
+    });
+  });
+
+  // ---------------------------------------------------------------------------
+  // Wrapped SOL Tips
+  // ---------------------------------------------------------------------------
+  describe("Wrapped SOL Tips", () => {
+    const tipAmount = 0.05 * LAMPORTS_PER_SOL;
+    let creatorWsolAccount: PublicKey;
+
+    before(() => {
+      creatorWsolAccount = getAssociatedTokenAddressSync(
+        NATIVE_MINT,
+        creator.publicKey
+      );
+    });
+
+    async function tipWrapped(
+      tipIndex: anchor.BN,
+      creatorWallet: PublicKey = creator.publicKey
+    ) {
+      const [tipRecord] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("tip"),
+          tipper.publicKey.toBuffer(),
+          tipIndex.toArrayLike(Buffer, "le", 8),
+        ],
+        program.programId
+      );
+
+      await program.methods
+        .tipCreatorWrapped(new anchor.BN(tipAmount), null, tipIndex, null)
+        .accounts({
+          config: platformConfigPda,
+          creatorVault: creatorVaultPda,
+          tipRecord,
+          tipper: tipper.publicKey,
+          tipperStats: tipperStatsPda(tipper.publicKey, creator.publicKey),
+          treasury: treasuryPda,
+          creator: creatorWallet,
+          wsolMint: NATIVE_MINT,
+          creatorWsolAccount: getAssociatedTokenAddressSync(
+            NATIVE_MINT,
+            creatorWallet
+          ),
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          platformStats: null,
+        })
+        .signers([tipper])
+        .rpc();
+
+      return tipRecord;
+    }
+
+    it("creates the creator's wSOL account and credits the tip", async () => {
+      const config = await program.account.platformConfig.fetch(platformConfigPda);
+      const fee = Math.floor((tipAmount * config.feeBasisPoints) / 10000);
+
+      const tipRecord = await tipWrapped(new anchor.BN(400));
+
+      const wsol = await getAccount(provider.connection, creatorWsolAccount);
+      assert.deepEqual(wsol.mint, NATIVE_MINT);
+      assert.equal(Number(wsol.amount), tipAmount - fee);
+
+      const record = await program.account.tipRecord.fetch(tipRecord);
+      assert.equal(record.amount.toNumber(), tipAmount - fee);
+      assert.deepEqual(record.mint, NATIVE_MINT);
+    });
+
+    it("credits an existing wSOL account", async () => {
+      const before = await getAccount(provider.connection, creatorWsolAccount);
+      const config = await program.account.platformConfig.fetch(platformConfigPda);
+      const fee = Math.floor((tipAmount * config.feeBasisPoints) / 10000);
+
+      await tipWrapped(new anchor.BN(401));
+
+      const after = await getAccount(provider.connection, creatorWsolAccount);
+      assert.equal(Number(after.amount) - Number(before.amount), tipAmount - fee);
+    });
+
+    it("counts wrapped tips in the tipper's stats", async () => {
+      const stats = tipperStatsPda(tipper.publicKey, creator.publicKey);
+      const before = await program.account.tipperStats.fetch(stats);
+
+      await tipWrapped(new anchor.BN(403));
+
+      const after = await program.account.tipperStats.fetch(stats);
+      assert.equal(after.count.toNumber() - before.count.toNumber(), 1);
+      assert.equal(after.totalAmount.toNumber() - before.totalAmount.toNumber(), tipAmount);
+    });
+
+    it("rejects a creator wallet that doesn't own the vault", async () => {
+      // Tip lands on the real creator's vault but names another wallet as the creator
+      const impostor = Keypair.generate();
+
+      try {
+        await tipWrapped(new anchor.BN(402), impostor.publicKey);
+        assert.fail("Should have failed - creator does not own the vault");
+      } catch (e: any) {
+        expect(e.message).to.include("InvalidCreatorAccount");
+      }
+    });
+  });
+
+  // ---------------------------------------------------------------------------
+  // Auto-Renew
+  // ---------------------------------------------------------------------------
+  describe("Auto-Renew", () => {
+    const amountPerMonth = 0.01 * LAMPORTS_PER_SOL;
+    const renewingSubscriber = Keypair.generate();
+    let renewingSubscriptionPda: PublicKey;
+
+    function setAutoRenew(autoRenew: boolean, signer: Keypair = renewingSubscriber) {
+      return program.methods
+        .setAutoRenew(autoRenew)
+        .accounts({
+          creatorVault: creatorVaultPda,
+          subscription: renewingSubscriptionPda,
+          subscriber: signer.publicKey,
+        })
+        .signers([signer])
+        .rpc();
+    }
+
+    before(async () => {
+      const airdropSig = await provider.connection.requestAirdrop(
+        renewingSubscriber.publicKey,
+        LAMPORTS_PER_SOL
+      );
+      await provider.connection.confirmTransaction(airdropSig);
+
+      [renewingSubscriptionPda] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("subscription"),
+          renewingSubscriber.publicKey.toBuffer(),
+          creator.publicKey.toBuffer(),
+        ],
+        program.programId
+      );
+
+      await program.methods
+        .subscribe(new anchor.BN(amountPerMonth), null, null, null)
+        .accounts({
+          config: platformConfigPda,
+          creatorVault: creatorVaultPda,
+          subscription: renewingSubscriptionPda,
+          subscriber: renewingSubscriber.publicKey,
+          treasury: treasuryPda,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          platformStats: null,
+          subscriptionTiers: null,
+          subscriberIndex: null,
+        })
+        .signers([renewingSubscriber])
+        .rpc();
+    });
+
+    it("renews automatically by default", async () => {
+      const subscription = await program.account.subscription.fetch(
+        renewingSubscriptionPda
+      );
+      assert.equal(subscription.autoRenew, true);
+    });
+
+    it("lets the subscriber cancel at period end", async () => {
+      await setAutoRenew(false);
+
+      const subscription = await program.account.subscription.fetch(
+        renewingSubscriptionPda
+      );
+      assert.equal(subscription.autoRenew, false);
+      // Still inside the paid period, so access continues
+      assert.equal(subscription.isActive, true);
+    });
+
+    it("does not deactivate before the paid period ends", async () => {
+      try {
+        await program.methods
+          .processSubscription()
+          .accounts({
+            config: platformConfigPda,
+            creatorVault: creatorVaultPda,
+            subscription: renewingSubscriptionPda,
+            subscriber: renewingSubscriber.publicKey,
+            treasury: treasuryPda,
+            systemProgram: anchor.web3.SystemProgram.programId,
+            platformStats: null,
+            crank: null,
+          })
+          .signers([renewingSubscriber])
+          .rpc();
+        assert.fail("Expected PaymentNotDue error");
+      } catch (e: any) {
+        expect(e.message).to.include("PaymentNotDue");
+      }
+
+      const subscription = await program.account.subscription.fetch(
+        renewingSubscriptionPda
+      );
+      assert.equal(subscription.isActive, true);
+      assert.equal(subscription.paymentCount, 1);
+    });
+
+    it("rejects toggling someone else's subscription", async () => {
+      try {
+        await setAutoRenew(true, tipper);
+        assert.fail("Should have failed - not the subscriber");
+      } catch (e: any) {
+        expect(e.message).to.include("ConstraintSeeds");
+      }
+    });
+
+    it("lets the subscriber turn auto-renew back on", async () => {
+      await setAutoRenew(true);
+
+      const subscription = await program.account.subscription.fetch(
+        renewingSubscriptionPda
+      );
+      assert.equal(subscription.autoRenew, true);
+    });
+  });
+
+  // ---------------------------------------------------------------------------
+  // Platform Stats
+  // ---------------------------------------------------------------------------
+  describe("Platform Stats", () => {
+    const statsCreator = Keypair.generate();
+    const statsSubscriber = Keypair.generate();
+    let platformStatsPda: PublicKey;
+    let statsVaultPda: PublicKey;
+
+    function tip(lamports: number, tipIndex: number, stats: PublicKey | null) {
+      const index = new anchor.BN(tipIndex);
+      const [tipRecord] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("tip"),
+          tipper.publicKey.toBuffer(),
+          index.toArrayLike(Buffer, "le", 8),
+        ],
+        program.programId
+      );
+
+      return program.methods
+        .tipCreator(new anchor.BN(lamports), null, index, null)
+        .accounts({
+          config: platformConfigPda,
+          creatorVault: statsVaultPda,
+          tipRecord,
+          tipper: tipper.publicKey,
+          tipperStats: tipperStatsPda(tipper.publicKey, statsCreator.publicKey),
+          treasury: treasuryPda,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          platformStats: stats,
+        })
+        .signers([tipper])
+        .rpc();
+    }
+
+    function setStatsTracking(trackStats: boolean, signer: Keypair = platformAuthority) {
+      return program.methods
+        .setStatsTracking(trackStats)
+        .accounts({ config: platformConfigPda, authority: signer.publicKey })
+        .signers([signer])
+        .rpc();
+    }
+
+    before(async () => {
+      for (const user of [statsCreator, statsSubscriber]) {
+        const airdropSig = await provider.connection.requestAirdrop(
+          user.publicKey,
+          LAMPORTS_PER_SOL
+        );
+        await provider.connection.confirmTransaction(airdropSig);
+      }
+
+      [platformStatsPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("platform_stats")],
+        program.programId
+      );
+      [statsVaultPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("vault"), statsCreator.publicKey.toBuffer()],
+        program.programId
+      );
+    });
+
+    after(async () => {
+      // Later suites don't pass the stats account
+      await setStatsTracking(false);
+    });
+
+    it("only lets the platform authority create the stats account", async () => {
+      try {
+        await program.methods
+          .initializePlatformStats()
+          .accounts({
+            config: platformConfigPda,
+            platformStats: platformStatsPda,
+            authority: tipper.publicKey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .signers([tipper])
+          .rpc();
+        assert.fail("Expected Unauthorized error");
+      } catch (e: any) {
+        expect(e.message).to.include("Unauthorized");
+      }
+    });
+
+    it("starts every counter at zero", async () => {
+      await program.methods
+        .initializePlatformStats()
+        .accounts({
+          config: platformConfigPda,
+          platformStats: platformStatsPda,
+          authority: platformAuthority.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([platformAuthority])
+        .rpc();
+
+      const stats = await program.account.platformStats.fetch(platformStatsPda);
+      assert.equal(stats.totalTipVolume.toNumber(), 0);
+      assert.equal(stats.totalSubscriptionVolume.toNumber(), 0);
+      assert.equal(stats.totalVaults.toNumber(), 0);
+
+      const config = await program.account.platformConfig.fetch(platformConfigPda);
+      assert.isTrue(config.trackStats);
+    });
+
+    it("counts new vaults", async () => {
+      await program.methods
+        .initializeVault(null)
+        .accounts({
+          config: platformConfigPda,
+          vault: statsVaultPda,
+          creator: statsCreator.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          platformStats: platformStatsPda,
+        })
+        .signers([statsCreator])
+        .rpc();
+
+      const stats = await program.account.platformStats.fetch(platformStatsPda);
+      assert.equal(stats.totalVaults.toNumber(), 1);
+    });
+
+    it("does not count a vault twice when it is initialized again", async () => {
+      await program.methods
+        .initializeVault(null)
+        .accounts({
+          config: platformConfigPda,
+          vault: statsVaultPda,
+          creator: statsCreator.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          platformStats: platformStatsPda,
+        })
+        .signers([statsCreator])
+        .rpc();
+
+      const stats = await program.account.platformStats.fetch(platformStatsPda);
+      assert.equal(stats.totalVaults.toNumber(), 1);
+    });
+
+    it("aggregates gross tip volume across tips", async () => {
+      await tip(0.1 * LAMPORTS_PER_SOL, 500, platformStatsPda);
+      await tip(0.25 * LAMPORTS_PER_SOL, 501, platformStatsPda);
+
+      const stats = await program.account.platformStats.fetch(platformStatsPda);
+      assert.equal(stats.totalTipVolume.toNumber(), 0.35 * LAMPORTS_PER_SOL);
+    });
+
+    it("requires the stats account once tracking is on", async () => {
+      try {
+        await tip(0.05 * LAMPORTS_PER_SOL, 502, null);
+        assert.fail("Expected PlatformStatsRequired error");
+      } catch (e: any) {
+        expect(e.message).to.include("PlatformStatsRequired");
+      }
+
+      const stats = await program.account.platformStats.fetch(platformStatsPda);
+      assert.equal(stats.totalTipVolume.toNumber(), 0.35 * LAMPORTS_PER_SOL);
+    });
+
+    it("fails when a non-authority toggles tracking", async () => {
+      try {
+        await setStatsTracking(false, tipper);
+        assert.fail("Expected Unauthorized error");
+      } catch (e: any) {
+        expect(e.message).to.include("Unauthorized");
+      }
+    });
+
+    it("aggregates subscription volume", async () => {
+      const amountPerMonth = 0.02 * LAMPORTS_PER_SOL;
+      const [subscriptionPda] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("subscription"),
+          statsSubscriber.publicKey.toBuffer(),
+          statsCreator.publicKey.toBuffer(),
+        ],
+        program.programId
+      );
+
+      await program.methods
+        .subscribe(new anchor.BN(amountPerMonth), null, null, null)
+        .accounts({
+          config: platformConfigPda,
+          creatorVault: statsVaultPda,
+          subscription: subscriptionPda,
+          subscriber: statsSubscriber.publicKey,
+          treasury: treasuryPda,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          platformStats: platformStatsPda,
+          subscriptionTiers: null,
+          subscriberIndex: null,
+        })
+        .signers([statsSubscriber])
+        .rpc();
+
+      const stats = await program.account.platformStats.fetch(platformStatsPda);
+      assert.equal(stats.totalSubscriptionVolume.toNumber(), amountPerMonth);
+      assert.equal(stats.totalTipVolume.toNumber(), 0.35 * LAMPORTS_PER_SOL);
+      assert.equal(stats.totalVaults.toNumber(), 1);
+    });
+  });
+
+  // ---------------------------------------------------------------------------
+  // Subscription Disputes
+  // ---------------------------------------------------------------------------
+  describe("Subscription Disputes", () => {
+    const amountPerMonth = 0.01 * LAMPORTS_PER_SOL;
+    const disputingSubscriber = Keypair.generate();
+    let disputedSubscriptionPda: PublicKey;
+
+    function processSubscription() {
+      return program.methods
+        .processSubscription()
+        .accounts({
+          config: platformConfigPda,
+          creatorVault: creatorVaultPda,
+          subscription: disputedSubscriptionPda,
+          subscriber: disputingSubscriber.publicKey,
+          treasury: treasuryPda,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          platformStats: null,
+          crank: null,
+        })
+        .signers([disputingSubscriber])
+        .rpc();
+    }
+
+    function disputeSubscription() {
+      return program.methods
+        .disputeSubscription()
+        .accounts({
+          creatorVault: creatorVaultPda,
+          subscription: disputedSubscriptionPda,
+          subscriber: disputingSubscriber.publicKey,
+        })
+        .signers([disputingSubscriber])
+        .rpc();
+    }
+
+    function resolveDispute(signer: Keypair) {
+      return program.methods
+        .resolveDispute()
+        .accounts({
+          creatorVault: creatorVaultPda,
+          subscription: disputedSubscriptionPda,
+          creator: signer.publicKey,
+        })
+        .signers([signer])
+        .rpc();
+    }
+
+    before(async () => {
+      const airdropSig = await provider.connection.requestAirdrop(
+        disputingSubscriber.publicKey,
+        LAMPORTS_PER_SOL
+      );
+      await provider.connection.confirmTransaction(airdropSig);
+
+      [disputedSubscriptionPda] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("subscription"),
+          disputingSubscriber.publicKey.toBuffer(),
+          creator.publicKey.toBuffer(),
+        ],
+        program.programId
+      );
+
+      await program.methods
+        .subscribe(new anchor.BN(amountPerMonth), null, null, null)
+        .accounts({
+          config: platformConfigPda,
+          creatorVault: creatorVaultPda,
+          subscription: disputedSubscriptionPda,
+          subscriber: disputingSubscriber.publicKey,
+          treasury: treasuryPda,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          platformStats: null,
+          subscriptionTiers: null,
+          subscriberIndex: null,
+        })
+        .signers([disputingSubscriber])
+        .rpc();
+    });
+
+    it("lets the subscriber dispute their subscription", async () => {
+      await disputeSubscription();
+
+      const subscription = await program.account.subscription.fetch(
+        disputedSubscriptionPda
+      );
+      assert.equal(subscription.disputed, true);
+      assert.equal(subscription.isActive, true);
+    });
+
+    it("blocks charges while disputed", async () => {
+      try {
+        await processSubscription();
+        assert.fail("Expected SubscriptionDisputed error");
+      } catch (e: any) {
+        expect(e.message).to.include("SubscriptionDisputed");
+      }
+
+      const subscription = await program.account.subscription.fetch(
+        disputedSubscriptionPda
+      );
+      assert.equal(subscription.paymentCount, 1);
+    });
+
+    it("rejects disputing twice", async () => {
+      try {
+        await disputeSubscription();
+        assert.fail("Expected SubscriptionDisputed error");
+      } catch (e: any) {
+        expect(e.message).to.include("SubscriptionDisputed");
+      }
+    });
+
+    it("only lets the creator resolve the dispute", async () => {
+      try {
+        await resolveDispute(disputingSubscriber);
+        assert.fail("Expected Unauthorized error");
+      } catch (e: any) {
+        expect(e.message).to.include("Unauthorized");
+      }
+    });
+
+    it("resumes charging once the creator resolves the dispute", async () => {
+      await resolveDispute(creator);
+
+      const subscription = await program.account.subscription.fetch(
+        disputedSubscriptionPda
+      );
+      assert.equal(subscription.disputed, false);
+
+      // No longer blocked by the dispute; only the billing period gates it now
+      try {
+        await processSubscription();
+        assert.fail("Expected PaymentNotDue error");
+      } catch (e: any) {
+        expect(e.message).to.include("PaymentNotDue");
+      }
+    });
+
+    it("rejects resolving a subscription that isn't disputed", async () => {
+      try {
+        await resolveDispute(creator);
+        assert.fail("Expected SubscriptionNotDisputed error");
+      } catch (e: any) {
+        expect(e.message).to.include("SubscriptionNotDisputed");
+      }
+    });
+  });
+
+  // ---------------------------------------------------------------------------
+  // Subscription References
+  // ---------------------------------------------------------------------------
+  describe("Subscription References", () => {
+    const amountPerMonth = 0.01 * LAMPORTS_PER_SOL;
+    const businessSubscriber = Keypair.generate();
+    const invoiceId = Array.from(Buffer.from("INV-2026-000042\0"));
+    const eventParser = new anchor.EventParser(
+      program.programId,
+      new anchor.BorshCoder(program.idl)
+    );
+
+    before(async () => {
+      const airdropSig = await provider.connection.requestAirdrop(
+        businessSubscriber.publicKey,
+        LAMPORTS_PER_SOL
+      );
+      await provider.connection.confirmTransaction(airdropSig);
+    });
+
+    it("stores the reference and echoes it in the payment event", async () => {
+      const [subscriptionPda] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("subscription"),
+          businessSubscriber.publicKey.toBuffer(),
+          creator.publicKey.toBuffer(),
+        ],
+        program.programId
+      );
+
+      const signature = await program.methods
+        .subscribe(new anchor.BN(amountPerMonth), invoiceId, null, null)
+        .accounts({
+          config: platformConfigPda,
+          creatorVault: creatorVaultPda,
+          subscription: subscriptionPda,
+          subscriber: businessSubscriber.publicKey,
+          treasury: treasuryPda,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          platformStats: null,
+          subscriptionTiers: null,
+          subscriberIndex: null,
+        })
+        .signers([businessSubscriber])
+        .rpc({ commitment: "confirmed" });
+
+      const subscription = await program.account.subscription.fetch(
+        subscriptionPda
+      );
+      assert.deepEqual(subscription.reference, invoiceId);
+
+      // Renewals read the same stored reference, so the first payment's event
+      // is representative of every SubscriptionProcessed event
+      const tx = await provider.connection.getTransaction(signature, {
+        commitment: "confirmed",
+        maxSupportedTransactionVersion: 0,
+      });
+      const created = Array.from(
+        eventParser.parseLogs(tx.meta.logMessages)
+      ).find((e) => e.name.toLowerCase() === "subscriptioncreated");
+      assert.ok(created, "SubscriptionCreated event should be emitted");
+      assert.deepEqual(Array.from(created.data.reference as number[]), invoiceId);
+    });
+  });
+
+  // ---------------------------------------------------------------------------
+  // Vault Namespaces
+  // ---------------------------------------------------------------------------
+  describe("Vault Namespaces", () => {
+    const brandA = Array.from(Buffer.from("brand-a\0"));
+    const brandB = Array.from(Buffer.from("brand-b\0"));
+
+    function namespacedVaultPda(namespace: number[]) {
+      const [pda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("vault"), creator.publicKey.toBuffer(), Buffer.from(namespace)],
+        program.programId
+      );
+      return pda;
+    }
+
+    function initializeVault(namespace: number[]) {
+      return program.methods
+        .initializeVault(namespace)
+        .accounts({
+          config: platformConfigPda,
+          vault: namespacedVaultPda(namespace),
+          creator: creator.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          platformStats: null,
+        })
+        .signers([creator])
+        .rpc();
+    }
+
+    function tip(vault: PublicKey, lamports: number, tipIndex: number) {
+      const index = new anchor.BN(tipIndex);
+      const [tipRecord] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("tip"),
+          tipper.publicKey.toBuffer(),
+          index.toArrayLike(Buffer, "le", 8),
+        ],
+        program.programId
+      );
+
+      return program.methods
+        .tipCreator(new anchor.BN(lamports), null, index, null)
+        .accounts({
+          config: platformConfigPda,
+          creatorVault: vault,
+          tipRecord,
+          tipper: tipper.publicKey,
+          tipperStats: tipperStatsPda(tipper.publicKey, creator.publicKey),
+          treasury: treasuryPda,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          platformStats: null,
+        })
+        .signers([tipper])
+        .rpc();
+    }
+
+    it("gives the same creator a separate vault per namespace", async () => {
+      await initializeVault(brandA);
+      await initializeVault(brandB);
+
+      const vaultA = namespacedVaultPda(brandA);
+      const vaultB = namespacedVaultPda(brandB);
+      assert.notEqual(vaultA.toBase58(), vaultB.toBase58());
+      assert.notEqual(vaultA.toBase58(), creatorVaultPda.toBase58());
+
+      const a = await program.account.creatorVault.fetch(vaultA);
+      const b = await program.account.creatorVault.fetch(vaultB);
+      assert.deepEqual(a.creator, creator.publicKey);
+      assert.deepEqual(b.creator, creator.publicKey);
+      assert.deepEqual(a.namespace, brandA);
+      assert.deepEqual(b.namespace, brandB);
+
+      // The original vault is the default namespace
+      const original = await program.account.creatorVault.fetch(creatorVaultPda);
+      assert.isNull(original.namespace);
+    });
+
+    it("keeps earnings independent across namespaces", async () => {
+      const vaultA = namespacedVaultPda(brandA);
+      const vaultB = namespacedVaultPda(brandB);
+      const originalBefore = await program.account.creatorVault.fetch(
+        creatorVaultPda
+      );
+
+      await tip(vaultA, 0.1 * LAMPORTS_PER_SOL, 600);
+
+      const config = await program.account.platformConfig.fetch(platformConfigPda);
+      const tipAmount = 0.1 * LAMPORTS_PER_SOL;
+      const fee = Math.floor((tipAmount * config.feeBasisPoints) / 10000);
+
+      const a = await program.account.creatorVault.fetch(vaultA);
+      const b = await program.account.creatorVault.fetch(vaultB);
+      const originalAfter = await program.account.creatorVault.fetch(
+        creatorVaultPda
+      );
+      assert.equal(a.totalEarned.toNumber(), tipAmount - fee);
+      assert.equal(b.totalEarned.toNumber(), 0);
+      assert.equal(
+        originalAfter.totalEarned.toNumber(),
+        originalBefore.totalEarned.toNumber()
+      );
+    });
+
+    it("leaves an existing vault untouched when initialized again", async () => {
+      const vaultA = namespacedVaultPda(brandA);
+      const before = await program.account.creatorVault.fetch(vaultA);
+      assert.isAbove(before.totalEarned.toNumber(), 0);
+
+      await initializeVault(brandA);
+
+      const after = await program.account.creatorVault.fetch(vaultA);
+      assert.equal(after.totalEarned.toNumber(), before.totalEarned.toNumber());
+      assert.equal(after.withdrawn.toNumber(), before.withdrawn.toNumber());
+      assert.equal(after.subscribers, before.subscribers);
+      assert.deepEqual(after.namespace, brandA);
+    });
+  });
+
+  // ---------------------------------------------------------------------------
+  // Subscription Tiers
+  // ---------------------------------------------------------------------------
+  describe("Subscription Tiers", () => {
+    const fan = { name: "Fan", amountPerMonth: new anchor.BN(0.01 * LAMPORTS_PER_SOL) };
+    const superfan = {
+      name: "Superfan",
+      amountPerMonth: new anchor.BN(0.05 * LAMPORTS_PER_SOL),
+    };
+    const tierSubscriber = Keypair.generate();
+    let tiersPda: PublicKey;
+    let subscriptionPda: PublicKey;
+
+    function subscribeTier(amount: anchor.BN, tierIndex: number) {
+      return program.methods
+        .subscribe(amount, null, tierIndex, null)
+        .accounts({
+          config: platformConfigPda,
+          creatorVault: creatorVaultPda,
+          subscription: subscriptionPda,
+          subscriber: tierSubscriber.publicKey,
+          treasury: treasuryPda,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          platformStats: null,
+          subscriptionTiers: tiersPda,
+          subscriberIndex: null,
+        })
+        .signers([tierSubscriber])
+        .rpc();
+    }
+
+    before(async () => {
+      [tiersPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("tiers"), creatorVaultPda.toBuffer()],
+        program.programId
+      );
+      [subscriptionPda] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("subscription"),
+          tierSubscriber.publicKey.toBuffer(),
+          creator.publicKey.toBuffer(),
+        ],
+        program.programId
+      );
+
+      const airdropSig = await provider.connection.requestAirdrop(
+        tierSubscriber.publicKey,
+        LAMPORTS_PER_SOL
+      );
+      await provider.connection.confirmTransaction(airdropSig);
+
+      await program.methods
+        .setSubscriptionTiers([fan, superfan])
+        .accounts({
+          vault: creatorVaultPda,
+          subscriptionTiers: tiersPda,
+          creator: creator.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([creator])
+        .rpc();
+    });
+
+    it("stores the creator's tier catalog", async () => {
+      const catalog = await program.account.subscriptionTiers.fetch(tiersPda);
+      assert.ok(catalog.vault.equals(creatorVaultPda));
+      assert.deepEqual(
+        catalog.tiers.map((tier) => tier.name),
+        ["Fan", "Superfan"]
+      );
+      assert.equal(
+        catalog.tiers[1].amountPerMonth.toNumber(),
+        superfan.amountPerMonth.toNumber()
+      );
+    });
+
+    it("rejects a catalog update from anyone but the creator", async () => {
+      try {
+        await program.methods
+          .setSubscriptionTiers([fan])
+          .accounts({
+            vault: creatorVaultPda,
+            subscriptionTiers: tiersPda,
+            creator: tierSubscriber.publicKey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .signers([tierSubscriber])
+          .rpc();
+        assert.fail("Should have failed - not the creator");
+      } catch (e: any) {
+        expect(e.message).to.include("ConstraintSeeds");
+      }
+    });
+
+    it("rejects an out-of-range tier index", async () => {
+      try {
+        await subscribeTier(fan.amountPerMonth, 2);
+        assert.fail("Should have failed - no tier at index 2");
+      } catch (e: any) {
+        expect(e.message).to.include("InvalidTierIndex");
+      }
+    });
+
+    it("rejects an amount that differs from the tier price", async () => {
+      try {
+        await subscribeTier(fan.amountPerMonth, 1);
+        assert.fail("Should have failed - Fan price for the Superfan tier");
+      } catch (e: any) {
+        expect(e.message).to.include("TierAmountMismatch");
+      }
+    });
+
+    it("subscribes at the selected tier's price", async () => {
+      await subscribeTier(superfan.amountPerMonth, 1);
+
+      const subscription = await program.account.subscription.fetch(
+        subscriptionPda
+      );
+      assert.equal(
+        subscription.amountPerMonth.toNumber(),
+        superfan.amountPerMonth.toNumber()
+      );
+      assert.equal(subscription.isActive, true);
+    });
+  });
+
+  // ---------------------------------------------------------------------------
+  // Vault Verification
+  // ---------------------------------------------------------------------------
+  describe("Vault Verification", () => {
+    function setVerified(authority: Keypair, verified: boolean) {
+      return program.methods
+        .setVaultVerified(verified)
+        .accounts({
+          config: platformConfigPda,
+          vault: creatorVaultPda,
+          authority: authority.publicKey,
+        })
+        .signers([authority])
+        .rpc();
+    }
+
+    it("starts unverified", async () => {
+      const vault = await program.account.creatorVault.fetch(creatorVaultPda);
+      assert.equal(vault.verified, false);
+    });
+
+    it("rejects verification by the vault's own creator", async () => {
+      try {
+        await setVerified(creator, true);
+        assert.fail("Should have failed - creator is not the platform authority");
+      } catch (e: any) {
+        expect(e.message).to.include("Unauthorized");
+      }
+
+      const vault = await program.account.creatorVault.fetch(creatorVaultPda);
+      assert.equal(vault.verified, false);
+    });
+
+    it("lets the platform authority verify and unverify a vault", async () => {
+      await setVerified(platformAuthority, true);
+      let vault = await program.account.creatorVault.fetch(creatorVaultPda);
+      assert.equal(vault.verified, true);
+
+      await setVerified(platformAuthority, false);
+      vault = await program.account.creatorVault.fetch(creatorVaultPda);
+      assert.equal(vault.verified, false);
+    });
+  });
+
+  // ---------------------------------------------------------------------------
+  // Withdrawal Fees
+  // ---------------------------------------------------------------------------
+  describe("Withdrawal Fees", () => {
+    const FREE_THRESHOLD = 0.1 * LAMPORTS_PER_SOL;
+    const withdrawTipper = Keypair.generate();
+
+    function setThreshold(threshold: number | null) {
+      return program.methods
+        .setFreeWithdrawalThreshold(
+          threshold === null ? null : new anchor.BN(threshold)
+        )
+        .accounts({
+          config: platformConfigPda,
+          authority: platformAuthority.publicKey,
+        })
+        .signers([platformAuthority])
+        .rpc();
+    }
+
+    async function withdrawAndMeasure(amount: number) {
+      const vaultBefore = await provider.connection.getBalance(creatorVaultPda);
+      const treasuryBefore = await provider.connection.getBalance(treasuryPda);
+
+      await program.methods
+        .withdraw(new anchor.BN(amount))
+        .accounts({
+          config: platformConfigPda,
+          vault: creatorVaultPda,
+          creator: creator.publicKey,
+          treasury: treasuryPda,
+        })
+        .signers([creator])
+        .rpc();
+
+      const vaultAfter = await provider.connection.getBalance(creatorVaultPda);
+      const treasuryAfter = await provider.connection.getBalance(treasuryPda);
+      return {
+        vaultDelta: vaultBefore - vaultAfter,
+        fee: treasuryAfter - treasuryBefore,
+      };
+    }
+
+    before(async () => {
+      const airdropSig = await provider.connection.requestAirdrop(
+        withdrawTipper.publicKey,
+        LAMPORTS_PER_SOL
+      );
+      await provider.connection.confirmTransaction(airdropSig);
+
+      // Make sure the vault holds enough for both withdrawals
+      const index = new anchor.BN(700);
+      const [tipRecord] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("tip"),
+          withdrawTipper.publicKey.toBuffer(),
+          index.toArrayLike(Buffer, "le", 8),
+        ],
+        program.programId
+      );
+      await program.methods
+        .tipCreator(new anchor.BN(0.2 * LAMPORTS_PER_SOL), null, index, null)
+        .accounts({
+          config: platformConfigPda,
+          creatorVault: creatorVaultPda,
+          tipRecord,
+          tipper: withdrawTipper.publicKey,
+          tipperStats: tipperStatsPda(withdrawTipper.publicKey, creator.publicKey),
+          treasury: treasuryPda,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          platformStats: null,
+        })
+        .signers([withdrawTipper])
+        .rpc();
+
+      await setThreshold(FREE_THRESHOLD);
+    });
+
+    after(async () => {
+      // Later suites share the platform config
+      await setThreshold(null);
+    });
+
+    it("rejects a threshold change from anyone but the platform authority", async () => {
+      try {
+        await program.methods
+          .setFreeWithdrawalThreshold(null)
+          .accounts({
+            config: platformConfigPda,
+            authority: creator.publicKey,
+          })
+          .signers([creator])
+          .rpc();
+        assert.fail("Should have failed - not the platform authority");
+      } catch (e: any) {
+        expect(e.message).to.include("Unauthorized");
+      }
+    });
+
+    it("withdraws below the threshold without a fee", async () => {
+      const amount = 0.05 * LAMPORTS_PER_SOL;
+      const { vaultDelta, fee } = await withdrawAndMeasure(amount);
+
+      assert.equal(vaultDelta, amount);
+      assert.equal(fee, 0);
+    });
+
+    it("charges the fee only on the portion above the threshold", async () => {
+      const amount = 0.14 * LAMPORTS_PER_SOL;
+      const { vaultDelta, fee } = await withdrawAndMeasure(amount);
+
+      assert.equal(vaultDelta, amount);
+      assert.equal(
+        fee,
+        Math.floor(((amount - FREE_THRESHOLD) * FEE_BASIS_POINTS) / 10000)
+      );
+    });
+  });
+
+  // ---------------------------------------------------------------------------
+  // Closing Inactive Subscriptions
+  // ---------------------------------------------------------------------------
+  describe("Closing Inactive Subscriptions", () => {
+    const lapsedSubscriber = Keypair.generate();
+    let subscriptionPda: PublicKey;
+
+    function closeInactive(closer: Keypair) {
+      return program.methods
+        .closeInactiveSubscription()
+        .accounts({
+          creatorVault: creatorVaultPda,
+          subscription: subscriptionPda,
+          subscriber: lapsedSubscriber.publicKey,
+          closer: closer.publicKey,
+          subscriberIndex: null,
+        })
+        .signers([closer])
+        .rpc();
+    }
+
+    before(async () => {
+      [subscriptionPda] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("subscription"),
+          lapsedSubscriber.publicKey.toBuffer(),
+          creator.publicKey.toBuffer(),
+        ],
+        program.programId
+      );
+
+      const airdropSig = await provider.connection.requestAirdrop(
+        lapsedSubscriber.publicKey,
+        LAMPORTS_PER_SOL
+      );
+      await provider.connection.confirmTransaction(airdropSig);
+
+      await program.methods
+        .subscribe(new anchor.BN(0.01 * LAMPORTS_PER_SOL), null, null, null)
+        .accounts({
+          config: platformConfigPda,
+          creatorVault: creatorVaultPda,
+          subscription: subscriptionPda,
+          subscriber: lapsedSubscriber.publicKey,
+          treasury: treasuryPda,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          platformStats: null,
+          subscriptionTiers: null,
+          subscriberIndex: null,
+        })
+        .signers([lapsedSubscriber])
+        .rpc();
+    });
+
+    it("rejects closing an active subscription", async () => {
+      try {
+        await closeInactive(lapsedSubscriber);
+        assert.fail("Should have failed - subscription is active");
+      } catch (e: any) {
+        expect(e.message).to.include("SubscriptionStillActive");
+      }
+    });
+
+    it("rejects closing a subscription cancelled less than 30 days ago", async () => {
+      await program.methods
+        .cancelSubscription()
+        .accounts({
+          creatorVault: creatorVaultPda,
+          subscription: subscriptionPda,
+          subscriber: lapsedSubscriber.publicKey,
+          subscriberIndex: null,
+        })
+        .signers([lapsedSubscriber])
+        .rpc();
+
+      const subscription = await program.account.subscription.fetch(
+        subscriptionPda
+      );
+      assert.isAbove(subscription.deactivatedAt.toNumber(), 0);
+
+      try {
+        await closeInactive(creator);
+        assert.fail("Should have failed - inactive for less than 30 days");
+      } catch (e: any) {
+        expect(e.message).to.include("SubscriptionRecentlyActive");
+      }
+    });
+
+    it("rejects a close by anyone but the subscriber or creator", async () => {
+      const stranger = Keypair.generate();
+      try {
+        await closeInactive(stranger);
+        assert.fail("Should have failed - unrelated signer");
+      } catch (e: any) {
+        expect(e.message).to.include("Unauthorized");
+      }
+
+      const info = await provider.connection.getAccountInfo(subscriptionPda);
+      assert.isNotNull(info);
+    });
+  });
+
+  // ---------------------------------------------------------------------------
+  // Subscription Due Check
+  // ---------------------------------------------------------------------------
+  describe("Subscription Due Check", () => {
+    const dueSubscriber = Keypair.generate();
+    let subscriptionPda: PublicKey;
+
+    function isDue(): Promise<boolean> {
+      return program.methods
+        .isSubscriptionDue()
+        .accounts({ subscription: subscriptionPda })
+        .view();
+    }
+
+    before(async () => {
+      [subscriptionPda] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("subscription"),
+          dueSubscriber.publicKey.toBuffer(),
+          creator.publicKey.toBuffer(),
+        ],
+        program.programId
+      );
+
+      const airdropSig = await provider.connection.requestAirdrop(
+        dueSubscriber.publicKey,
+        LAMPORTS_PER_SOL
+      );
+      await provider.connection.confirmTransaction(airdropSig);
+
+      await program.methods
+        .subscribe(new anchor.BN(0.01 * LAMPORTS_PER_SOL), null, null, null)
+        .accounts({
+          config: platformConfigPda,
+          creatorVault: creatorVaultPda,
+          subscription: subscriptionPda,
+          subscriber: dueSubscriber.publicKey,
+          treasury: treasuryPda,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          platformStats: null,
+          subscriptionTiers: null,
+          subscriberIndex: null,
+        })
+        .signers([dueSubscriber])
+        .rpc();
+    });
+
+    it("reports a just-paid subscription as not due", async () => {
+      assert.isFalse(await isDue());
+    });
+
+    it("reports the next payment one period after the last", async () => {
+      const subscription = await program.account.subscription.fetch(
+        subscriptionPda
+      );
+      const nextPaymentDue = await program.methods
+        .nextPaymentDue()
+        .accounts({ subscription: subscriptionPda })
+        .view();
+
+      const THIRTY_DAYS = 30 * 24 * 60 * 60;
+      assert.equal(
+        nextPaymentDue.toNumber(),
+        subscription.lastPayment.toNumber() + THIRTY_DAYS
+      );
+      assert.isAbove(nextPaymentDue.toNumber(), Math.floor(Date.now() / 1000));
+    });
+
+    it("reports a cancelled subscription as not due", async () => {
+      await program.methods
+        .cancelSubscription()
+        .accounts({
+          creatorVault: creatorVaultPda,
+          subscription: subscriptionPda,
+          subscriber: dueSubscriber.publicKey,
+          subscriberIndex: null,
+        })
+        .signers([dueSubscriber])
+        .rpc();
+
+      assert.isFalse(await isDue());
+    });
+  });
+
+  // ---------------------------------------------------------------------------
+  // Minimum Commitment
+  // ---------------------------------------------------------------------------
+  describe("Minimum Commitment", () => {
+    const MIN_DURATION_SECONDS = 3;
+    const committedSubscriber = Keypair.generate();
+    let subscriptionPda: PublicKey;
+
+    function subscribeWithMinimum(minDuration: number) {
+      return program.methods
+        .subscribe(
+          new anchor.BN(0.01 * LAMPORTS_PER_SOL),
+          null,
+          null,
+          new anchor.BN(minDuration)
+        )
+        .accounts({
+          config: platformConfigPda,
+          creatorVault: creatorVaultPda,
+          subscription: subscriptionPda,
+          subscriber: committedSubscriber.publicKey,
+          treasury: treasuryPda,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          platformStats: null,
+          subscriptionTiers: null,
+          subscriberIndex: null,
+        })
+        .signers([committedSubscriber])
+        .rpc();
+    }
+
+    function cancel() {
+      return program.methods
+        .cancelSubscription()
+        .accounts({
+          creatorVault: creatorVaultPda,
+          subscription: subscriptionPda,
+          subscriber: committedSubscriber.publicKey,
+          subscriberIndex: null,
+        })
+        .signers([committedSubscriber])
+        .rpc();
+    }
+
+    before(async () => {
+      [subscriptionPda] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("subscription"),
+          committedSubscriber.publicKey.toBuffer(),
+          creator.publicKey.toBuffer(),
+        ],
+        program.programId
+      );
+
+      const airdropSig = await provider.connection.requestAirdrop(
+        committedSubscriber.publicKey,
+        LAMPORTS_PER_SOL
+      );
+      await provider.connection.confirmTransaction(airdropSig);
+    });
+
+    it("rejects a zero minimum duration", async () => {
+      try {
+        await subscribeWithMinimum(0);
+        assert.fail("Should have failed - zero minimum duration");
+      } catch (e: any) {
+        expect(e.message).to.include("InvalidMinDuration");
+      }
+    });
+
+    it("stores the commitment on the subscription", async () => {
+      await subscribeWithMinimum(MIN_DURATION_SECONDS);
+
+      const subscription = await program.account.subscription.fetch(
+        subscriptionPda
+      );
+      assert.equal(subscription.minDurationSeconds.toNumber(), MIN_DURATION_SECONDS);
+    });
+
+    it("rejects cancelling before the commitment ends", async () => {
+      try {
+        await cancel();
+        assert.fail("Should have failed - still within the commitment");
+      } catch (e: any) {
+        expect(e.message).to.include("MinimumDurationNotMet");
+      }
+    });
+
+    it("rejects turning off auto-renew before the commitment ends", async () => {
+      try {
+        await program.methods
+          .setAutoRenew(false)
+          .accounts({
+            creatorVault: creatorVaultPda,
+            subscription: subscriptionPda,
+            subscriber: committedSubscriber.publicKey,
+          })
+          .signers([committedSubscriber])
+          .rpc();
+        assert.fail("Should have failed - still within the commitment");
+      } catch (e: any) {
+        expect(e.message).to.include("MinimumDurationNotMet");
+      }
+    });
+
+    it("allows cancelling once the commitment has ended", async () => {
+      await new Promise((resolve) =>
+        setTimeout(resolve, (MIN_DURATION_SECONDS + 2) * 1000)
+      );
+
+      await cancel();
+
+      const subscription = await program.account.subscription.fetch(
+        subscriptionPda
+      );
+      assert.equal(subscription.isActive, false);
+    });
+  });
+
+  // ---------------------------------------------------------------------------
+  // Percentage Tips
+  // ---------------------------------------------------------------------------
+  describe("Percentage Tips", () => {
+    function tipRecordFor(tipIndex: anchor.BN) {
+      return PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("tip"),
+          tipper.publicKey.toBuffer(),
+          tipIndex.toArrayLike(Buffer, "le", 8),
+        ],
+        program.programId
+      )[0];
+    }
+
+    function tipPercentage(baseAmount: number, bps: number, tipIndex: anchor.BN) {
+      return program.methods
+        .tipCreatorPercentage(new anchor.BN(baseAmount), null, tipIndex, null, bps)
+        .accounts({
+          config: platformConfigPda,
+          creatorVault: creatorVaultPda,
+          tipRecord: tipRecordFor(tipIndex),
+          tipper: tipper.publicKey,
+          tipperStats: tipperStatsPda(tipper.publicKey, creator.publicKey),
+          treasury: treasuryPda,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          platformStats: null,
+        })
+        .signers([tipper])
+        .rpc();
+    }
+
+    it("tips a percentage of the base amount", async () => {
+      const basePrice = 0.5 * LAMPORTS_PER_SOL;
+
+      // 10%, 20% and 50%
+      for (const [tipIndex, bps] of [[800, 1000], [801, 2000], [802, 5000]]) {
+        const index = new anchor.BN(tipIndex);
+        const vaultBalanceBefore = await provider.connection.getBalance(creatorVaultPda);
+
+        await tipPercentage(basePrice, bps, index);
+
+        const tip = Math.floor((basePrice * bps) / 10000);
+        const expectedCreatorAmount = tip - Math.floor((tip * FEE_BASIS_POINTS) / 10000);
+        const tipRecord = await program.account.tipRecord.fetch(tipRecordFor(index));
+        const vaultBalanceAfter = await provider.connection.getBalance(creatorVaultPda);
+
+        assert.equal(tipRecord.amount.toNumber(), expectedCreatorAmount);
+        assert.equal(vaultBalanceAfter - vaultBalanceBefore, expectedCreatorAmount);
+      }
+    });
+
+    it("rounds fractional lamports down", async () => {
+      const tipIndex = new anchor.BN(803);
+
+      // 10% of 9999 lamports is 999.9, so the tip is 999 lamports
+      await tipPercentage(9999, 1000, tipIndex);
+
+      const expectedCreatorAmount = 999 - Math.floor((999 * FEE_BASIS_POINTS) / 10000);
+      const tipRecord = await program.account.tipRecord.fetch(tipRecordFor(tipIndex));
+      assert.equal(tipRecord.amount.toNumber(), expectedCreatorAmount);
+    });
+
+    it("rejects a percentage above 100%", async () => {
+      try {
+        await tipPercentage(LAMPORTS_PER_SOL, 10001, new anchor.BN(804));
+        assert.fail("Should have failed - percentage above 100%");
+      } catch (e: any) {
+        expect(e.message).to.include("InvalidTipPercentage");
+      }
+    });
+
+    it("rejects a percentage that rounds to a zero tip", async () => {
+      try {
+        await tipPercentage(9, 1000, new anchor.BN(805));
+        assert.fail("Should have failed - zero tip");
+      } catch (e: any) {
+        expect(e.message).to.include("InvalidAmount");
+      }
+    });
+  });
+
+  // ---------------------------------------------------------------------------
+  // First Supporter
+  // ---------------------------------------------------------------------------
+  describe("First Supporter", () => {
+    const newCreator = Keypair.generate();
+    const laterTipper = Keypair.generate();
+    let vaultPda: PublicKey;
+    const eventParser = new anchor.EventParser(
+      program.programId,
+      new anchor.BorshCoder(program.idl)
+    );
+
+    async function tipAndGetEvents(from: Keypair, tipIndex: number) {
+      const index = new anchor.BN(tipIndex);
+      const [tipRecord] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("tip"),
+          from.publicKey.toBuffer(),
+          index.toArrayLike(Buffer, "le", 8),
+        ],
+        program.programId
+      );
+
+      const signature = await program.methods
+        .tipCreator(new anchor.BN(0.01 * LAMPORTS_PER_SOL), null, index, null)
+        .accounts({
+          config: platformConfigPda,
+          creatorVault: vaultPda,
+          tipRecord,
+          tipper: from.publicKey,
+          tipperStats: tipperStatsPda(from.publicKey, newCreator.publicKey),
+          treasury: treasuryPda,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          platformStats: null,
+        })
+        .signers([from])
+        .rpc({ commitment: "confirmed" });
+
+      const tx = await provider.connection.getTransaction(signature, {
+        commitment: "confirmed",
+        maxSupportedTransactionVersion: 0,
+      });
+      return Array.from(eventParser.parseLogs(tx.meta.logMessages));
+    }
+
+    before(async () => {
+      for (const wallet of [newCreator, laterTipper]) {
+        const airdropSig = await provider.connection.requestAirdrop(
+          wallet.publicKey,
+          LAMPORTS_PER_SOL
+        );
+        await provider.connection.confirmTransaction(airdropSig);
+      }
+
+      [vaultPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("vault"), newCreator.publicKey.toBuffer()],
+        program.programId
+      );
+      await program.methods
+        .initializeVault(null)
+        .accounts({
+          config: platformConfigPda,
+          vault: vaultPda,
+          creator: newCreator.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          platformStats: null,
+        })
+        .signers([newCreator])
+        .rpc();
+    });
+
+    it("starts with no first supporter", async () => {
+      const vault = await program.account.creatorVault.fetch(vaultPda);
+      assert.isNull(vault.firstSupporter);
+    });
+
+    it("records the first tipper and emits FirstSupporterRecorded", async () => {
+      const events = await tipAndGetEvents(tipper, 900);
+
+      const recorded = events.find(
+        (e) => e.name.toLowerCase() === "firstsupporterrecorded"
+      );
+      assert.ok(recorded, "FirstSupporterRecorded event should be emitted");
+      assert.deepEqual(recorded.data.supporter, tipper.publicKey);
+      assert.deepEqual(recorded.data.creator, newCreator.publicKey);
+
+      const vault = await program.account.creatorVault.fetch(vaultPda);
+      assert.deepEqual(vault.firstSupporter, tipper.publicKey);
+    });
+
+    it("keeps the first supporter when others tip later", async () => {
+      const events = await tipAndGetEvents(laterTipper, 0);
+
+      assert.isUndefined(
+        events.find((e) => e.name.toLowerCase() === "firstsupporterrecorded")
+      );
+      const vault = await program.account.creatorVault.fetch(vaultPda);
+      assert.deepEqual(vault.firstSupporter, tipper.publicKey);
+    });
+  });
+
+  // ---------------------------------------------------------------------------
+  // Crank Rewards
+  // ---------------------------------------------------------------------------
+  describe("Crank Rewards", () => {
+    const CRANK_REWARD = 5000;
+    const rewardSubscriber = Keypair.generate();
+    const crank = Keypair.generate();
+    let subscriptionPda: PublicKey;
+
+    // A subscription preloaded from tests/fixtures/prepaid-subscription.json,
+    // long overdue and holding 0.05 SOL above its rent. Only the creator signs
+    // anything, to open the vault it pays into.
+    const renewalCreator = Keypair.fromSecretKey(Uint8Array.from(renewalCreatorKey));
+    const prepaidSubscriber = Keypair.fromSecretKey(Uint8Array.from(prepaidSubscriberKey));
+    const [renewalVaultPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), renewalCreator.publicKey.toBuffer()],
+      program.programId
+    );
+    const [prepaidSubscriptionPda] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("subscription"),
+        prepaidSubscriber.publicKey.toBuffer(),
+        renewalCreator.publicKey.toBuffer(),
+      ],
+      program.programId
+    );
+
+    function setCrankReward(reward: anchor.BN | null, signer: Keypair = platformAuthority) {
+      return program.methods
+        .setCrankReward(reward)
+        .accounts({ config: platformConfigPda, authority: signer.publicKey })
+        .signers([signer])
+        .rpc();
+    }
+
+    before(async () => {
+      [subscriptionPda] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("subscription"),
+          rewardSubscriber.publicKey.toBuffer(),
+          creator.publicKey.toBuffer(),
+        ],
+        program.programId
+      );
+
+      for (const wallet of [rewardSubscriber, crank]) {
+        const airdropSig = await provider.connection.requestAirdrop(
+          wallet.publicKey,
+          LAMPORTS_PER_SOL
+        );
+        await provider.connection.confirmTransaction(airdropSig);
+      }
+
+      await program.methods
+        .subscribe(new anchor.BN(0.01 * LAMPORTS_PER_SOL), null, null, null)
+        .accounts({
+          config: platformConfigPda,
+          creatorVault: creatorVaultPda,
+          subscription: subscriptionPda,
+          subscriber: rewardSubscriber.publicKey,
+          treasury: treasuryPda,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          platformStats: null,
+          subscriptionTiers: null,
+          subscriberIndex: null,
+        })
+        .signers([rewardSubscriber])
+        .rpc();
+
+      const airdropSig = await provider.connection.requestAirdrop(
+        renewalCreator.publicKey,
+        LAMPORTS_PER_SOL
+      );
+      await provider.connection.confirmTransaction(airdropSig);
+
+      await program.methods
+        .initializeVault(null)
+        .accounts({
+          config: platformConfigPda,
+          vault: renewalVaultPda,
+          creator: renewalCreator.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          platformStats: null,
+        })
+        .signers([renewalCreator])
+        .rpc();
+    });
+
+    after(async () => {
+      await setCrankReward(null);
+    });
+
+    it("rejects a crank reward set by a non-authority", async () => {
+      try {
+        await setCrankReward(new anchor.BN(CRANK_REWARD), creator);
+        assert.fail("Should have failed - not the platform authority");
+      } catch (e: any) {
+        expect(e.message).to.include("Unauthorized");
+      }
+    });
+
+    it("lets the authority set the crank reward", async () => {
+      await setCrankReward(new anchor.BN(CRANK_REWARD));
+
+      const config = await program.account.platformConfig.fetch(platformConfigPda);
+      assert.equal(config.crankRewardLamports.toNumber(), CRANK_REWARD);
+    });
+
+    it("pays nothing to a crank when the renewal is not due", async () => {
+      const crankBalanceBefore = await provider.connection.getBalance(
+        crank.publicKey
+      );
+
+      try {
+        await program.methods
+          .processSubscription()
+          .accounts({
+            config: platformConfigPda,
+            creatorVault: creatorVaultPda,
+            subscription: subscriptionPda,
+            subscriber: rewardSubscriber.publicKey,
+            treasury: treasuryPda,
+            systemProgram: anchor.web3.SystemProgram.programId,
+            platformStats: null,
+            crank: crank.publicKey,
+          })
+          .signers([rewardSubscriber, crank])
+          .rpc();
+        assert.fail("Expected PaymentNotDue error");
+      } catch (e: any) {
+        expect(e.message).to.include("PaymentNotDue");
+      }
+
+      const crankBalanceAfter = await provider.connection.getBalance(
+        crank.publicKey
+      );
+      assert.equal(crankBalanceAfter, crankBalanceBefore);
+    });
+
+    it("pays a third-party crank out of a prepaid subscription", async () => {
+      const amountPerMonth = 0.01 * LAMPORTS_PER_SOL;
+      const crankBalanceBefore = await provider.connection.getBalance(
+        crank.publicKey
+      );
+      const subscriptionBalanceBefore = await provider.connection.getBalance(
+        prepaidSubscriptionPda
+      );
+      const subscriberBalanceBefore = await provider.connection.getBalance(
+        prepaidSubscriber.publicKey
+      );
+
+      // Signed by the crank alone; the provider wallet pays the transaction fee
+      await program.methods
+        .processSubscriptionPrepaid()
+        .accounts({
+          config: platformConfigPda,
+          creatorVault: renewalVaultPda,
+          subscription: prepaidSubscriptionPda,
+          subscriber: prepaidSubscriber.publicKey,
+          treasury: treasuryPda,
+          platformStats: null,
+          crank: crank.publicKey,
+        })
+        .signers([crank])
+        .rpc();
+
+      const crankBalanceAfter = await provider.connection.getBalance(
+        crank.publicKey
+      );
+      assert.equal(crankBalanceAfter - crankBalanceBefore, CRANK_REWARD);
+
+      const subscriptionBalanceAfter = await provider.connection.getBalance(
+        prepaidSubscriptionPda
+      );
+      assert.equal(
+        subscriptionBalanceBefore - subscriptionBalanceAfter,
+        amountPerMonth + CRANK_REWARD
+      );
+      assert.equal(
+        await provider.connection.getBalance(prepaidSubscriber.publicKey),
+        subscriberBalanceBefore
+      );
+
+      const subscription = await program.account.subscription.fetch(
+        prepaidSubscriptionPda
+      );
+      assert.equal(subscription.paymentCount, 2);
+
+      const vault = await program.account.creatorVault.fetch(renewalVaultPda);
+      assert.isAbove(vault.totalEarned.toNumber(), 0);
+      assert.isAtMost(vault.totalEarned.toNumber(), amountPerMonth);
+    });
+
+    it("rejects a prepaid renewal submitted for the wrong subscriber", async () => {
+      try {
+        await program.methods
+          .processSubscriptionPrepaid()
+          .accounts({
+            config: platformConfigPda,
+            creatorVault: renewalVaultPda,
+            subscription: prepaidSubscriptionPda,
+            subscriber: rewardSubscriber.publicKey,
+            treasury: treasuryPda,
+            platformStats: null,
+            crank: crank.publicKey,
+          })
+          .signers([crank])
+          .rpc();
+        assert.fail("Should have failed - subscription belongs to someone else");
+      } catch (e: any) {
+        expect(e.message).to.include("ConstraintSeeds");
+      }
+    });
+  });
+
+  // ---------------------------------------------------------------------------
+  // Tip And Initialize Vault
+  // ---------------------------------------------------------------------------
+  describe("Tip And Initialize Vault", () => {
+    const newCreator = Keypair.generate();
+    let newVaultPda: PublicKey;
+
+    function tipNewCreator(amount: number, tipIndex: anchor.BN) {
+      const [tipRecord] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("tip"),
+          tipper.publicKey.toBuffer(),
+          tipIndex.toArrayLike(Buffer, "le", 8),
+        ],
+        program.programId
+      );
+      return program.methods
+        .tipAndInitVault(new anchor.BN(amount), null, tipIndex, null)
+        .accounts({
+          config: platformConfigPda,
+          creator: newCreator.publicKey,
+          creatorVault: newVaultPda,
+          tipRecord,
+          tipper: tipper.publicKey,
+          tipperStats: tipperStatsPda(tipper.publicKey, newCreator.publicKey),
+          treasury: treasuryPda,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          platformStats: null,
+        })
+        .signers([tipper])
+        .rpc();
+    }
+
+    before(() => {
+      [newVaultPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("vault"), newCreator.publicKey.toBuffer()],
+        program.programId
+      );
+    });
+
+    it("creates the vault for a creator who has never used the program", async () => {
+      assert.isNull(await provider.connection.getAccountInfo(newVaultPda));
+
+      const tipAmount = 0.1 * LAMPORTS_PER_SOL;
+      await tipNewCreator(tipAmount, new anchor.BN(1000));
+
+      const creatorAmount =
+        tipAmount - Math.floor((tipAmount * FEE_BASIS_POINTS) / 10000);
+      const vault = await program.account.creatorVault.fetch(newVaultPda);
+      assert.deepEqual(vault.creator, newCreator.publicKey);
+      assert.equal(vault.totalEarned.toNumber(), creatorAmount);
+      assert.deepEqual(vault.firstSupporter, tipper.publicKey);
+    });
+
+    it("tips into the existing vault without resetting it", async () => {
+      const tipAmount = 0.05 * LAMPORTS_PER_SOL;
+      const before = await program.account.creatorVault.fetch(newVaultPda);
+      await tipNewCreator(tipAmount, new anchor.BN(1001));
+
+      const creatorAmount =
+        tipAmount - Math.floor((tipAmount * FEE_BASIS_POINTS) / 10000);
+      const vault = await program.account.creatorVault.fetch(newVaultPda);
+      assert.equal(
+        vault.totalEarned.toNumber(),
+        before.totalEarned.toNumber() + creatorAmount
+      );
+    });
+
+    it("lets only the creator withdraw from the vault the tipper paid for", async () => {
+      const sig = await provider.connection.requestAirdrop(
+        newCreator.publicKey,
+        LAMPORTS_PER_SOL
+      );
+      await provider.connection.confirmTransaction(sig);
+
+      try {
+        await program.methods
+          .withdraw(new anchor.BN(1000))
+          .accounts({
+            config: platformConfigPda,
+            vault: newVaultPda,
+            creator: tipper.publicKey,
+            treasury: treasuryPda,
+          })
+          .signers([tipper])
+          .rpc();
+        assert.fail("Should have failed - the tipper doesn't own the vault");
+      } catch (e: any) {
+        expect(e.message).to.match(/Unauthorized|ConstraintSeeds/);
+      }
+
+      await program.methods
+        .withdraw(new anchor.BN(1000))
+        .accounts({
+          config: platformConfigPda,
+          vault: newVaultPda,
+          creator: newCreator.publicKey,
+          treasury: treasuryPda,
+        })
+        .signers([newCreator])
+        .rpc();
+      const vault = await program.account.creatorVault.fetch(newVaultPda);
+      assert.equal(vault.withdrawn.toNumber(), 1000);
+    });
+  });
+
+  // ---------------------------------------------------------------------------
+  // Subscriber Index
+  // ---------------------------------------------------------------------------
+  describe("Subscriber Index", () => {
+    const namespace = Array.from(Buffer.from("roster\0\0"));
+    const fans = [Keypair.generate(), Keypair.generate(), Keypair.generate()];
+    let vaultPda: PublicKey;
+    let pagePda: PublicKey;
+
+    function subscriptionPda(fan: Keypair) {
+      const [pda] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("subscription"),
+          fan.publicKey.toBuffer(),
+          creator.publicKey.toBuffer(),
+          Buffer.from(namespace),
+        ],
+        program.programId
+      );
+      return pda;
+    }
+
+    function openPage(payer: Keypair) {
+      return program.methods
+        .openSubscriberIndexPage()
+        .accounts({
+          creatorVault: vaultPda,
+          subscriberIndex: pagePda,
+          payer: payer.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([payer])
+        .rpc();
+    }
+
+    function subscribe(fan: Keypair, subscriberIndex: PublicKey | null) {
+      return program.methods
+        .subscribe(new anchor.BN(0.01 * LAMPORTS_PER_SOL), null, null, null)
+        .accounts({
+          config: platformConfigPda,
+          creatorVault: vaultPda,
+          subscription: subscriptionPda(fan),
+          subscriber: fan.publicKey,
+          treasury: treasuryPda,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          platformStats: null,
+          subscriptionTiers: null,
+          subscriberIndex,
+        })
+        .signers([fan])
+        .rpc();
+    }
+
+    function cancel(fan: Keypair, subscriberIndex: PublicKey | null) {
+      return program.methods
+        .cancelSubscription()
+        .accounts({
+          creatorVault: vaultPda,
+          subscription: subscriptionPda(fan),
+          subscriber: fan.publicKey,
+          subscriberIndex,
+        })
+        .signers([fan])
+        .rpc();
+    }
+
+    async function roster(): Promise<string[]> {
+      const page = await program.account.subscriberIndex.fetch(pagePda);
+      return page.subscribers.map((key: PublicKey) => key.toBase58()).sort();
+    }
+
+    before(async () => {
+      [vaultPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("vault"), creator.publicKey.toBuffer(), Buffer.from(namespace)],
+        program.programId
+      );
+      [pagePda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("subscriber_index"), vaultPda.toBuffer(), Buffer.alloc(4)],
+        program.programId
+      );
+      await program.methods
+        .initializeVault(namespace)
+        .accounts({
+          config: platformConfigPda,
+          vault: vaultPda,
+          creator: creator.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          platformStats: null,
+        })
+        .signers([creator])
+        .rpc();
+
+      for (const fan of fans) {
+        const sig = await provider.connection.requestAirdrop(
+          fan.publicKey,
+          LAMPORTS_PER_SOL
+        );
+        await provider.connection.confirmTransaction(sig);
+      }
+    });
+
+    it("lets only the creator turn the roster on", async () => {
+      try {
+        await openPage(fans[0]);
+        assert.fail("Should have failed - only the creator opens the first page");
+      } catch (e: any) {
+        expect(e.message).to.include("Unauthorized");
+      }
+
+      await openPage(creator);
+      const vault = await program.account.creatorVault.fetch(vaultPda);
+      assert.equal(vault.subscriberIndexPages, 1);
+    });
+
+    it("lists new subscribers on the page they join", async () => {
+      await subscribe(fans[0], pagePda);
+      await subscribe(fans[1], pagePda);
+
+      assert.deepEqual(
+        await roster(),
+        [fans[0].publicKey.toBase58(), fans[1].publicKey.toBase58()].sort()
+      );
+      const subscription = await program.account.subscription.fetch(
+        subscriptionPda(fans[0])
+      );
+      assert.equal(subscription.indexPage, 0);
+    });
+
+    it("requires the page once the roster is on", async () => {
+      try {
+        await subscribe(fans[2], null);
+        assert.fail("Should have failed - subscriber index page missing");
+      } catch (e: any) {
+        expect(e.message).to.include("SubscriberIndexRequired");
+      }
+    });
+
+    it("removes cancelled subscribers from their page", async () => {
+      try {
+        await cancel(fans[0], null);
+        assert.fail("Should have failed - subscriber index page missing");
+      } catch (e: any) {
+        expect(e.message).to.include("SubscriberIndexRequired");
+      }
+
+      await cancel(fans[0], pagePda);
+      assert.deepEqual(await roster(), [fans[1].publicKey.toBase58()]);
+      const subscription = await program.account.subscription.fetch(
+        subscriptionPda(fans[0])
+      );
+      assert.isNull(subscription.indexPage);
+
+      const vault = await program.account.creatorVault.fetch(vaultPda);
+      assert.equal(vault.subscribers.toNumber(), 1);
+    });
+  });
+
+  // ---------------------------------------------------------------------------
+  // Gifted Subscription Periods
+  // ---------------------------------------------------------------------------
+  describe("Gifted Subscription Periods", () => {
+    const SECONDS_PER_MONTH = 30 * 24 * 60 * 60;
+    const giftedSubscriber = Keypair.generate();
+    const gifter = Keypair.generate();
+    const amount = 0.01 * LAMPORTS_PER_SOL;
+    let subscriptionPda: PublicKey;
+
+    before(async () => {
+      [subscriptionPda] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("subscription"),
+          giftedSubscriber.publicKey.toBuffer(),
+          creator.publicKey.toBuffer(),
+        ],
+        program.programId
+      );
+      for (const wallet of [giftedSubscriber, gifter]) {
+        const sig = await provider.connection.requestAirdrop(
+          wallet.publicKey,
+          LAMPORTS_PER_SOL
+        );
+        await provider.connection.confirmTransaction(sig);
+      }
+
+      await program.methods
+        .subscribe(new anchor.BN(amount), null, null, null)
+        .accounts({
+          config: platformConfigPda,
+          creatorVault: creatorVaultPda,
+          subscription: subscriptionPda,
+          subscriber: giftedSubscriber.publicKey,
+          treasury: treasuryPda,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          platformStats: null,
+          subscriptionTiers: null,
+          subscriberIndex: null,
+        })
+        .signers([giftedSubscriber])
+        .rpc();
+    });
+
+    it("lets a third party pay a month of someone else's subscription", async () => {
+      const before = await program.account.subscription.fetch(subscriptionPda);
+      const vaultBefore = await program.account.creatorVault.fetch(creatorVaultPda);
+      const subscriberBalance = await provider.connection.getBalance(
+        giftedSubscriber.publicKey
+      );
+
+      await program.methods
+        .giftSubscriptionPeriod()
+        .accounts({
+          config: platformConfigPda,
+          creatorVault: creatorVaultPda,
+          subscription: subscriptionPda,
+          gifter: gifter.publicKey,
+          treasury: treasuryPda,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          platformStats: null,
+        })
+        .signers([gifter])
+        .rpc();
+
+      const after = await program.account.subscription.fetch(subscriptionPda);
+      assert.equal(
+        after.lastPayment.toNumber(),
+        before.lastPayment.toNumber() + SECONDS_PER_MONTH
+      );
+      assert.equal(after.paymentCount, before.paymentCount + 1);
+
+      const creatorAmount = amount - Math.floor((amount * FEE_BASIS_POINTS) / 10000);
+      const vault = await program.account.creatorVault.fetch(creatorVaultPda);
+      assert.equal(
+        vault.totalEarned.toNumber(),
+        vaultBefore.totalEarned.toNumber() + creatorAmount
+      );
+      // The subscriber neither signed nor paid
+      assert.equal(
+        await provider.connection.getBalance(giftedSubscriber.publicKey),
+        subscriberBalance
+      );
+    });
+  });
+
+  // ---------------------------------------------------------------------------
+  // Subscriber Cap
+  // ---------------------------------------------------------------------------
+  describe("Subscriber Cap", () => {
+    const namespace = Array.from(Buffer.from("capped\0\0"));
+    const fans = [Keypair.generate(), Keypair.generate()];
+    let vaultPda: PublicKey;
+
+    function subscribe(fan: Keypair) {
+      const [subscription] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("subscription"),
+          fan.publicKey.toBuffer(),
+          creator.publicKey.toBuffer(),
+          Buffer.from(namespace),
+        ],
+        program.programId
+      );
+      return program.methods
+        .subscribe(new anchor.BN(0.01 * LAMPORTS_PER_SOL), null, null, null)
+        .accounts({
+          config: platformConfigPda,
+          creatorVault: vaultPda,
+          subscription,
+          subscriber: fan.publicKey,
+          treasury: treasuryPda,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          platformStats: null,
+          subscriptionTiers: null,
+          subscriberIndex: null,
+        })
+        .signers([fan])
+        .rpc();
+    }
+
+    before(async () => {
+      [vaultPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("vault"), creator.publicKey.toBuffer(), Buffer.from(namespace)],
+        program.programId
+      );
+      await program.methods
+        .initializeVault(namespace)
+        .accounts({
+          config: platformConfigPda,
+          vault: vaultPda,
+          creator: creator.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          platformStats: null,
+        })
+        .signers([creator])
+        .rpc();
+
+      for (const fan of fans) {
+        const sig = await provider.connection.requestAirdrop(
+          fan.publicKey,
+          LAMPORTS_PER_SOL
+        );
+        await provider.connection.confirmTransaction(sig);
+      }
+    });
+
+    it("lets only the creator set the cap", async () => {
+      try {
+        await program.methods
+          .setMaxSubscribers(new anchor.BN(1))
+          .accounts({ vault: vaultPda, creator: fans[0].publicKey })
+          .signers([fans[0]])
+          .rpc();
+        assert.fail("Should have failed - not the creator");
+      } catch (e: any) {
+        expect(e.message).to.match(/Unauthorized|ConstraintSeeds/);
+      }
+
+      await program.methods
+        .setMaxSubscribers(new anchor.BN(1))
+        .accounts({ vault: vaultPda, creator: creator.publicKey })
+        .signers([creator])
+        .rpc();
+      const vault = await program.account.creatorVault.fetch(vaultPda);
+      assert.equal(vault.maxSubscribers.toNumber(), 1);
+    });
+
+    it("accepts subscribers up to the cap and rejects the next", async () => {
+      await subscribe(fans[0]);
+
+      try {
+        await subscribe(fans[1]);
+        assert.fail("Should have failed - subscriber cap reached");
+      } catch (e: any) {
+        expect(e.message).to.include("SubscriberLimitReached");
+      }
+
+      const vault = await program.account.creatorVault.fetch(vaultPda);
+      assert.equal(vault.subscribers.toNumber(), 1);
+    });
+
+    it("treats a cap of zero as unlimited", async () => {
+      await program.methods
+        .setMaxSubscribers(new anchor.BN(0))
+        .accounts({ vault: vaultPda, creator: creator.publicKey })
+        .signers([creator])
+        .rpc();
+
+      await subscribe(fans[1]);
+      const vault = await program.account.creatorVault.fetch(vaultPda);
+      assert.equal(vault.subscribers.toNumber(), 2);
+    });
+  });
+
+  // ---------------------------------------------------------------------------
+  // Vault Bump Checks
+  // ---------------------------------------------------------------------------
+  describe("Vault Bump Checks", () => {
+    it("rejects a vault address derived with a non-canonical bump", async () => {
+      const fan = Keypair.generate();
+      const sig = await provider.connection.requestAirdrop(fan.publicKey, LAMPORTS_PER_SOL);
+      await provider.connection.confirmTransaction(sig);
+
+      const [, canonicalBump] = PublicKey.findProgramAddressSync(
+        [Buffer.from("vault"), creator.publicKey.toBuffer()],
+        program.programId
+      );
+      let nonCanonicalVault: PublicKey | null = null;
+      for (let bump = canonicalBump - 1; bump >= 0 && !nonCanonicalVault; bump--) {
+        try {
+          nonCanonicalVault = PublicKey.createProgramAddressSync(
+            [Buffer.from("vault"), creator.publicKey.toBuffer(), Buffer.from([bump])],
+            program.programId
+          );
+        } catch {
+          // On the curve; try the next bump down
+        }
+      }
+      const [subscription] = PublicKey.findProgramAddressSync(
+        [Buffer.from("subscription"), fan.publicKey.toBuffer(), creator.publicKey.toBuffer()],
+        program.programId
+      );
+
+      try {
+        await program.methods
+          .subscribe(new anchor.BN(0.01 * LAMPORTS_PER_SOL), null, null, null)
+          .accounts({
+            config: platformConfigPda,
+            creatorVault: nonCanonicalVault,
+            subscription,
+            subscriber: fan.publicKey,
+            treasury: treasuryPda,
+            systemProgram: anchor.web3.SystemProgram.programId,
+            platformStats: null,
+            subscriptionTiers: null,
+            subscriberIndex: null,
+          })
+          .signers([fan])
+          .rpc();
+        assert.fail("Should have failed - non-canonical vault");
+      } catch (e: any) {
+        expect(e.message).to.match(/AccountNotInitialized|ConstraintSeeds/);
+      }
+      assert.isNull(await provider.connection.getAccountInfo(subscription));
+    });
+  });
+
+  // ---------------------------------------------------------------------------
+  // Tipper Stats
+  // ---------------------------------------------------------------------------
+  describe("Tipper Stats", () => {
+    const fan = Keypair.generate();
+
+    before(async () => {
+      const sig = await provider.connection.requestAirdrop(fan.publicKey, LAMPORTS_PER_SOL);
+      await provider.connection.confirmTransaction(sig);
+    });
+
+    function tip(lamports: number, tipIndex: number) {
+      const index = new anchor.BN(tipIndex);
+      const [tipRecord] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("tip"),
+          fan.publicKey.toBuffer(),
+          index.toArrayLike(Buffer, "le", 8),
+        ],
+        program.programId
+      );
+      return program.methods
+        .tipCreator(new anchor.BN(lamports), null, index, null)
+        .accounts({
+          config: platformConfigPda,
+          creatorVault: creatorVaultPda,
+          tipRecord,
+          tipper: fan.publicKey,
+          tipperStats: tipperStatsPda(fan.publicKey, creator.publicKey),
+          treasury: treasuryPda,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          platformStats: null,
+        })
+        .signers([fan])
+        .rpc();
+    }
+
+    it("creates the stats on the first tip and accumulates later ones", async () => {
+      const statsPda = tipperStatsPda(fan.publicKey, creator.publicKey);
+      assert.isNull(await provider.connection.getAccountInfo(statsPda));
+
+      await tip(0.1 * LAMPORTS_PER_SOL, 0);
+      let stats = await program.account.tipperStats.fetch(statsPda);
+      assert.ok(stats.tipper.equals(fan.publicKey));
+      assert.ok(stats.creator.equals(creator.publicKey));
+      assert.equal(stats.count.toNumber(), 1);
+      assert.equal(stats.totalAmount.toNumber(), 0.1 * LAMPORTS_PER_SOL);
+
+      await tip(0.25 * LAMPORTS_PER_SOL, 1);
+      stats = await program.account.tipperStats.fetch(statsPda);
+      assert.equal(stats.count.toNumber(), 2);
+      assert.equal(stats.totalAmount.toNumber(), 0.35 * LAMPORTS_PER_SOL);
+    });
+  });
+
+  // ---------------------------------------------------------------------------
+  // Fee Rounding
+  // ---------------------------------------------------------------------------
+  describe("Fee Rounding", () => {
+    const fan = Keypair.generate();
+    // 2% of 1_001 lamports is 20.02
+    const TIP = 1_001;
+
+    function setFeeRounding(roundFeeUp: boolean, signer: Keypair = platformAuthority) {
+      return program.methods
+        .setFeeRounding(roundFeeUp)
+        .accounts({ config: platformConfigPda, authority: signer.publicKey })
+        .signers([signer])
+        .rpc();
+    }
+
+    async function tipAndGetFee(tipIndex: number) {
+      const index = new anchor.BN(tipIndex);
+      const [tipRecord] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("tip"),
+          fan.publicKey.toBuffer(),
+          index.toArrayLike(Buffer, "le", 8),
+        ],
+        program.programId
+      );
+      const before = await program.account.treasury.fetch(treasuryPda);
+      await program.methods
+        .tipCreator(new anchor.BN(TIP), null, index, null)
+        .accounts({
+          config: platformConfigPda,
+          creatorVault: creatorVaultPda,
+          tipRecord,
+          tipper: fan.publicKey,
+          tipperStats: tipperStatsPda(fan.publicKey, creator.publicKey),
+          treasury: treasuryPda,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          platformStats: null,
+        })
+        .signers([fan])
+        .rpc();
+      const after = await program.account.treasury.fetch(treasuryPda);
+      return after.totalCollected.sub(before.totalCollected).toNumber();
+    }
+
+    before(async () => {
+      const sig = await provider.connection.requestAirdrop(fan.publicKey, LAMPORTS_PER_SOL);
+      await provider.connection.confirmTransaction(sig);
+    });
+
+    after(async () => {
+      await setFeeRounding(false);
+    });
+
+    it("rejects a rounding change by a non-authority", async () => {
+      try {
+        await setFeeRounding(true, creator);
+        assert.fail("Should have failed - not the platform authority");
+      } catch (e: any) {
+        expect(e.message).to.include("Unauthorized");
+      }
+    });
+
+    it("rounds fees down by default and up once enabled", async () => {
+      const config = await program.account.platformConfig.fetch(platformConfigPda);
+      assert.isFalse(config.roundFeeUp);
+      assert.equal(await tipAndGetFee(0), 20);
+
+      await setFeeRounding(true);
+      assert.equal(await tipAndGetFee(1), 21);
+    });
+  });
+
+  // ---------------------------------------------------------------------------
+  // Vault Transfer
+  // ---------------------------------------------------------------------------
+  describe("Vault Transfer", () => {
+    const oldWallet = Keypair.generate();
+    const newWallet = Keypair.generate();
+    const stranger = Keypair.generate();
+    const TIP = 0.5 * LAMPORTS_PER_SOL;
+
+    function vaultPdaOf(wallet: PublicKey) {
+      const [pda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("vault"), wallet.toBuffer()],
+        program.programId
+      );
+      return pda;
+    }
+
+    function acceptVault(signer: Keypair) {
+      return program.methods
+        .acceptVault()
+        .accounts({
+          oldVault: vaultPdaOf(oldWallet.publicKey),
+          creator: oldWallet.publicKey,
+          newVault: vaultPdaOf(signer.publicKey),
+          newCreator: signer.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([signer])
+        .rpc();
+    }
+
+    before(async () => {
+      for (const wallet of [oldWallet, newWallet, stranger]) {
+        const sig = await provider.connection.requestAirdrop(wallet.publicKey, LAMPORTS_PER_SOL);
+        await provider.connection.confirmTransaction(sig);
+      }
+
+      await program.methods
+        .initializeVault(null)
+        .accounts({
+          config: platformConfigPda,
+          vault: vaultPdaOf(oldWallet.publicKey),
+          creator: oldWallet.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          platformStats: null,
+        })
+        .signers([oldWallet])
+        .rpc();
+
+      const index = new anchor.BN(0);
+      const [tipRecord] = PublicKey.findProgramAddressSync(
+        [Buffer.from("tip"), stranger.publicKey.toBuffer(), index.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+      await program.methods
+        .tipCreator(new anchor.BN(TIP), null, index, null)
+        .accounts({
+          config: platformConfigPda,
+          creatorVault: vaultPdaOf(oldWallet.publicKey),
+          tipRecord,
+          tipper: stranger.publicKey,
+          tipperStats: tipperStatsPda(stranger.publicKey, oldWallet.publicKey),
+          treasury: treasuryPda,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          platformStats: null,
+        })
+        .signers([stranger])
+        .rpc();
+    });
+
+    it("only lets the creator offer the vault", async () => {
+      try {
+        await program.methods
+          .transferVault(stranger.publicKey)
+          .accounts({ vault: vaultPdaOf(oldWallet.publicKey), creator: stranger.publicKey })
+          .signers([stranger])
+          .rpc();
+        assert.fail("Should have failed - not the vault's creator");
+      } catch (e: any) {
+        expect(e.message).to.match(/Unauthorized|ConstraintSeeds/);
+      }
+    });
+
+    it("only lets the offered wallet accept", async () => {
+      await program.methods
+        .transferVault(newWallet.publicKey)
+        .accounts({ vault: vaultPdaOf(oldWallet.publicKey), creator: oldWallet.publicKey })
+        .signers([oldWallet])
+        .rpc();
+      const offered = await program.account.creatorVault.fetch(vaultPdaOf(oldWallet.publicKey));
+      assert.ok(offered.pendingCreator.equals(newWallet.publicKey));
+
+      try {
+        await acceptVault(stranger);
+        assert.fail("Should have failed - vault not offered to this wallet");
+      } catch (e: any) {
+        expect(e.message).to.include("NotPendingCreator");
+      }
+    });
+
+    it("moves the books and escrow to the new wallet's vault", async () => {
+      const oldVaultPda = vaultPdaOf(oldWallet.publicKey);
+      const newVaultPda = vaultPdaOf(newWallet.publicKey);
+      const before = await program.account.creatorVault.fetch(oldVaultPda);
+      const oldVaultLamports = await provider.connection.getBalance(oldVaultPda);
+      const oldWalletBefore = await provider.connection.getBalance(oldWallet.publicKey);
+
+      await acceptVault(newWallet);
+
+      assert.isNull(await provider.connection.getAccountInfo(oldVaultPda));
+      const moved = await program.account.creatorVault.fetch(newVaultPda);
+      assert.ok(moved.creator.equals(newWallet.publicKey));
+      assert.equal(moved.totalEarned.toNumber(), before.totalEarned.toNumber());
+      assert.equal(moved.withdrawn.toNumber(), 0);
+      assert.isNull(moved.pendingCreator);
+
+      // The old vault's rent went back to the old wallet and its escrow into
+      // the new vault, whose own rent the new wallet paid
+      const rent = await provider.connection.getMinimumBalanceForRentExemption(
+        (await provider.connection.getAccountInfo(newVaultPda)).data.length
+      );
+      const oldWalletAfter = await provider.connection.getBalance(oldWallet.publicKey);
+      assert.equal(oldWalletAfter - oldWalletBefore, rent);
+      assert.equal(await provider.connection.getBalance(newVaultPda), oldVaultLamports);
+    });
+
+    it("lets the new wallet withdraw the earnings", async () => {
+      const newVaultPda = vaultPdaOf(newWallet.publicKey);
+      const vault = await program.account.creatorVault.fetch(newVaultPda);
+      const available = vault.totalEarned.sub(vault.withdrawn);
+      const balanceBefore = await provider.connection.getBalance(newWallet.publicKey);
+
+      await program.methods
+        .withdraw(available)
+        .accounts({
+          config: platformConfigPda,
+          vault: newVaultPda,
+          creator: newWallet.publicKey,
+          treasury: treasuryPda,
+        })
+        .signers([newWallet])
+        .rpc();
+
+      const after = await program.account.creatorVault.fetch(newVaultPda);
+      assert.equal(after.withdrawn.toNumber(), after.totalEarned.toNumber());
+      assert.isAbove(
+        await provider.connection.getBalance(newWallet.publicKey),
+        balanceBefore
+      );
+    });
+  });
+
+  // ---------------------------------------------------------------------------
+  // Platform Config Migration
+  // ---------------------------------------------------------------------------
+  describe("Platform Config Migration", () => {
+    function migrate(authority: Keypair) {
+      return program.methods
+        .migratePlatformConfig()
+        .accounts({
+          config: platformConfigPda,
+          authority: authority.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([authority])
+        .rpc();
+    }
+
+    it("leaves a config in the current layout untouched", async () => {
+      const before = await provider.connection.getAccountInfo(platformConfigPda);
+      await migrate(platformAuthority);
+      const after = await provider.connection.getAccountInfo(platformConfigPda);
+
+      assert.equal(after.data.length, before.data.length);
+      assert.equal(after.lamports, before.lamports);
+      assert.isTrue(after.data.equals(before.data));
+    });
+  });
+
+  // ---------------------------------------------------------------------------
+  // Vault Migration
+  // ---------------------------------------------------------------------------
+  describe("Vault Migration", () => {
+    function migrate(signer: Keypair) {
+      const [vault] = PublicKey.findProgramAddressSync(
+        [Buffer.from("vault"), signer.publicKey.toBuffer()],
+        program.programId
+      );
+      return program.methods
+        .migrateVault()
+        .accounts({
+          vault,
+          creator: signer.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([signer])
+        .rpc();
+    }
+
+    it("leaves a vault in the current layout untouched", async () => {
+      const before = await provider.connection.getAccountInfo(creatorVaultPda);
+      await migrate(creator);
+      const after = await provider.connection.getAccountInfo(creatorVaultPda);
+
+      assert.equal(after.data.length, before.data.length);
+      assert.equal(after.lamports, before.lamports);
+      assert.isTrue(after.data.equals(before.data));
+    });
+
+    it("rejects a wallet without a vault", async () => {
+      const stranger = Keypair.generate();
+      const sig = await provider.connection.requestAirdrop(stranger.publicKey, LAMPORTS_PER_SOL);
+      await provider.connection.confirmTransaction(sig);
+
+      try {
+        await migrate(stranger);
+        assert.fail("Should have thrown");
+      } catch (e: any) {
+        expect(e.message).to.include("AccountOwnedByWrongProgram");
+      }
+    });
+  });
+
+  // ---------------------------------------------------------------------------
+  // Subscription Migration
+  // ---------------------------------------------------------------------------
+  describe("Subscription Migration", () => {
+    function migrate(subscription: PublicKey, signer: Keypair) {
+      return program.methods
+        .migrateSubscription()
+        .accounts({
+          subscription,
+          subscriber: signer.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([signer])
+        .rpc();
+    }
+
+    it("leaves a subscription in the current layout untouched", async () => {
+      const [{ publicKey: subscription }] = await program.account.subscription.all();
+
+      // A current subscription returns before the subscriber is checked
+      const before = await provider.connection.getAccountInfo(subscription);
+      await migrate(subscription, subscriber);
+      const after = await provider.connection.getAccountInfo(subscription);
+
+      assert.equal(after.data.length, before.data.length);
+      assert.equal(after.lamports, before.lamports);
+      assert.isTrue(after.data.equals(before.data));
+    });
+
+    it("rejects an account the program doesn't own", async () => {
+      try {
+        await migrate(subscriber.publicKey, subscriber);
+        assert.fail("Should have thrown");
+      } catch (e: any) {
+        expect(e.message).to.include("AccountOwnedByWrongProgram");
+      }
+    });
+  });
+
+  // ---------------------------------------------------------------------------
+  // Overdrawn Vault Recovery
+  // ---------------------------------------------------------------------------
+  describe("Overdrawn Vault Recovery", () => {
+    // Preloaded from tests/fixtures/overdrawn-vault.json with withdrawn
+    // 500_000 lamports above total_earned
+    const overdrawnCreator = Keypair.fromSecretKey(Uint8Array.from(overdrawnCreatorKey));
+    const overdrawnTipper = Keypair.generate();
+    const [overdrawnVault] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), overdrawnCreator.publicKey.toBuffer()],
+      program.programId
+    );
+    const eventParser = new anchor.EventParser(
+      program.programId,
+      new anchor.BorshCoder(program.idl)
+    );
+
+    function withdraw(amount: number) {
+      return program.methods
+        .withdraw(new anchor.BN(amount))
+        .accounts({
+          config: platformConfigPda,
+          vault: overdrawnVault,
+          creator: overdrawnCreator.publicKey,
+          treasury: treasuryPda,
+        })
+        .signers([overdrawnCreator])
+        .rpc({ commitment: "confirmed" });
+    }
+
+    before(async () => {
+      for (const wallet of [overdrawnCreator, overdrawnTipper]) {
+        const sig = await provider.connection.requestAirdrop(wallet.publicKey, LAMPORTS_PER_SOL);
+        await provider.connection.confirmTransaction(sig);
+      }
+    });
+
+    it("saves the correction even though nothing is withdrawable yet", async () => {
+      const balanceBefore = await provider.connection.getBalance(overdrawnVault);
+
+      const sig = await withdraw(1_000);
+      const tx = await provider.connection.getTransaction(sig, {
+        commitment: "confirmed",
+        maxSupportedTransactionVersion: 0,
+      });
+      const [correction] = Array.from(eventParser.parseLogs(tx.meta.logMessages));
+      assert.equal(correction.name.toLowerCase(), "statecorrectionapplied");
+      assert.equal(correction.data.previousWithdrawn.toNumber(), 1_500_000);
+
+      const vault = await program.account.creatorVault.fetch(overdrawnVault);
+      assert.equal(vault.withdrawn.toNumber(), vault.totalEarned.toNumber());
+      assert.equal(await provider.connection.getBalance(overdrawnVault), balanceBefore);
+    });
+
+    it("pays out new earnings straight away", async () => {
+      const tipAmount = 0.01 * LAMPORTS_PER_SOL;
+      const tipIndex = new anchor.BN(0);
+      const [tipRecordPda] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("tip"),
+          overdrawnTipper.publicKey.toBuffer(),
+          tipIndex.toArrayLike(Buffer, "le", 8),
+        ],
+        program.programId
+      );
+      await program.methods
+        .tipCreator(new anchor.BN(tipAmount), null, tipIndex, null)
+        .accounts({
+          config: platformConfigPda,
+          creatorVault: overdrawnVault,
+          tipRecord: tipRecordPda,
+          tipper: overdrawnTipper.publicKey,
+          tipperStats: tipperStatsPda(overdrawnTipper.publicKey, overdrawnCreator.publicKey),
+          treasury: treasuryPda,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          platformStats: null,
+        })
+        .signers([overdrawnTipper])
+        .rpc();
+
+      const vault = await program.account.creatorVault.fetch(overdrawnVault);
+      const available = vault.totalEarned.sub(vault.withdrawn).toNumber();
+      assert.isAbove(available, 0);
+
+      await withdraw(available);
+      const after = await program.account.creatorVault.fetch(overdrawnVault);
+      assert.equal(after.withdrawn.toNumber(), after.totalEarned.toNumber());
+    });
+  });
+
+  // ---------------------------------------------------------------------------
+  // Tip Record Migration
+  // ---------------------------------------------------------------------------
+  describe("Tip Record Migration", () => {
+    // Preloaded from tests/fixtures/legacy-tip-record.json in the layout from
+    // before `mint`, with a post so no byte is left over for it
+    const legacyTipper = Keypair.fromSecretKey(Uint8Array.from(legacyTipperKey));
+    const [legacyTipRecord] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("tip"),
+        legacyTipper.publicKey.toBuffer(),
+        new anchor.BN(0).toArrayLike(Buffer, "le", 8),
+      ],
+      program.programId
+    );
+
+    function closeTipRecord() {
+      return program.methods
+        .closeTipRecord()
+        .accounts({ tipRecord: legacyTipRecord, tipper: legacyTipper.publicKey })
+        .signers([legacyTipper])
+        .rpc();
+    }
+
+    before(async () => {
+      const sig = await provider.connection.requestAirdrop(legacyTipper.publicKey, LAMPORTS_PER_SOL);
+      await provider.connection.confirmTransaction(sig);
+    });
+
+    it("can't read a legacy tip record before it is migrated", async () => {
+      try {
+        await closeTipRecord();
+        assert.fail("Should have thrown");
+      } catch (e: any) {
+        expect(e.message).to.include("AccountDidNotDeserialize");
+      }
+    });
+
+    it("rejects a migration signed by someone other than the tipper", async () => {
+      try {
+        await program.methods
+          .migrateTipRecord()
+          .accounts({
+            tipRecord: legacyTipRecord,
+            tipper: tipper.publicKey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .signers([tipper])
+          .rpc();
+        assert.fail("Should have thrown");
+      } catch (e: any) {
+        expect(e.message).to.include("ConstraintHasOne");
+      }
+    });
+
+    it("grows a legacy tip record so it can be closed", async () => {
+      await program.methods
+        .migrateTipRecord()
+        .accounts({
+          tipRecord: legacyTipRecord,
+          tipper: legacyTipper.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([legacyTipper])
+        .rpc();
+
+      const tipRecord = await program.account.tipRecord.fetch(legacyTipRecord);
+      assert.isTrue(tipRecord.from.equals(legacyTipper.publicKey));
+      assert.equal(tipRecord.amount.toNumber(), 10_000_000);
+      assert.isNotNull(tipRecord.post);
+      assert.isNull(tipRecord.mint);
+
+      await closeTipRecord();
+      assert.isNull(await provider.connection.getAccountInfo(legacyTipRecord));
+    });
+  });
 });